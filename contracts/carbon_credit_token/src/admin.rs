@@ -1,4 +1,4 @@
-use soroban_sdk::{Address, Env};
+use soroban_sdk::{contracttype, Address, Env};
 
 use crate::storage::DataKey;
 
@@ -28,44 +28,24 @@ pub fn write_super_admin(e: &Env, admin: &Address) {
     e.storage().instance().set(&DataKey::SuperAdmin, admin);
 }
 
-// ── Verifier Role ─────────────────────────────────────────────────────────────
-
-pub fn is_verifier(e: &Env, addr: &Address) -> bool {
-    e.storage()
-        .persistent()
-        .get::<DataKey, bool>(&DataKey::Verifier(addr.clone()))
-        .unwrap_or(false)
-}
-
-pub fn grant_verifier(e: &Env, verifier: &Address) {
-    e.storage()
-        .persistent()
-        .set(&DataKey::Verifier(verifier.clone()), &true);
-}
-
-pub fn revoke_verifier(e: &Env, verifier: &Address) {
-    e.storage()
-        .persistent()
-        .remove(&DataKey::Verifier(verifier.clone()));
+/// A super admin handover waiting for the successor to accept it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PendingSuperAdmin {
+    pub successor: Address,
+    pub expiration_ledger: u32,
 }
 
-// ── Blacklist ─────────────────────────────────────────────────────────────────
-
-pub fn is_blacklisted(e: &Env, addr: &Address) -> bool {
-    e.storage()
-        .persistent()
-        .get::<DataKey, bool>(&DataKey::Blacklisted(addr.clone()))
-        .unwrap_or(false)
+pub fn read_pending_super_admin(e: &Env) -> Option<PendingSuperAdmin> {
+    e.storage().instance().get(&DataKey::PendingSuperAdmin)
 }
 
-pub fn blacklist_address(e: &Env, addr: &Address) {
+pub fn write_pending_super_admin(e: &Env, pending: &PendingSuperAdmin) {
     e.storage()
-        .persistent()
-        .set(&DataKey::Blacklisted(addr.clone()), &true);
+        .instance()
+        .set(&DataKey::PendingSuperAdmin, pending);
 }
 
-pub fn unblacklist_address(e: &Env, addr: &Address) {
-    e.storage()
-        .persistent()
-        .remove(&DataKey::Blacklisted(addr.clone()));
+pub fn remove_pending_super_admin(e: &Env) {
+    e.storage().instance().remove(&DataKey::PendingSuperAdmin);
 }
@@ -1,27 +1,38 @@
-use soroban_sdk::{Address, Env};
+use soroban_sdk::{Address, Env, Vec};
 
 use crate::error::Error;
-use crate::storage::{AllowanceDataKey, AllowanceValue, DataKey};
+use crate::storage::{
+    AllowanceDataKey, AllowancePurpose, AllowanceValue, DataKey, BALANCE_BUMP_AMOUNT,
+    BALANCE_LIFETIME_THRESHOLD,
+};
 
-pub fn read_allowance(e: &Env, from: Address, spender: Address) -> i128 {
-    let key = DataKey::Allowance(AllowanceDataKey { from, spender });
-    if let Some(allowance) = e.storage().temporary().get::<DataKey, AllowanceValue>(&key) {
-        if allowance.expiration_ledger < e.ledger().sequence() {
-            0
-        } else {
-            allowance.amount
-        }
-    } else {
-        0
+fn read_allowance_value(e: &Env, from: Address, spender: Address) -> Option<AllowanceValue> {
+    let key = DataKey::Approval(AllowanceDataKey { from, spender });
+    match e.storage().temporary().get::<DataKey, AllowanceValue>(&key) {
+        Some(allowance) if allowance.expiration_ledger >= e.ledger().sequence() => Some(allowance),
+        _ => None,
     }
 }
 
+pub fn read_allowance(e: &Env, from: Address, spender: Address) -> i128 {
+    read_allowance_value(e, from, spender)
+        .map(|allowance| allowance.amount)
+        .unwrap_or(0)
+}
+
+pub fn read_allowance_purpose(e: &Env, from: Address, spender: Address) -> AllowancePurpose {
+    read_allowance_value(e, from, spender)
+        .map(|allowance| allowance.purpose)
+        .unwrap_or(AllowancePurpose::Any)
+}
+
 pub fn write_allowance(
     e: &Env,
     from: Address,
     spender: Address,
     amount: i128,
     expiration_ledger: u32,
+    purpose: AllowancePurpose,
 ) -> Result<(), Error> {
     if amount > 0 && expiration_ledger < e.ledger().sequence() {
         return Err(Error::InvalidExpirationLedger);
@@ -30,9 +41,12 @@ pub fn write_allowance(
     let allowance = AllowanceValue {
         amount,
         expiration_ledger,
+        purpose,
     };
 
-    let key = DataKey::Allowance(AllowanceDataKey { from, spender });
+    index_spender(e, &from, &spender, amount > 0);
+
+    let key = DataKey::Approval(AllowanceDataKey { from, spender });
     e.storage().temporary().set(&key, &allowance);
 
     if amount > 0 {
@@ -43,18 +57,147 @@ pub fn write_allowance(
     Ok(())
 }
 
+/// Adds `amount` to the live allowance, moving it to `expiration_ledger`. An
+/// expired or missing allowance counts as zero. `purpose` is always explicit
+/// and must match a live allowance, so a top-up can neither widen nor narrow
+/// what the spender may do.
+pub fn increase_allowance(
+    e: &Env,
+    from: Address,
+    spender: Address,
+    amount: i128,
+    expiration_ledger: u32,
+    purpose: AllowancePurpose,
+) -> Result<AllowanceValue, Error> {
+    let current = match read_allowance_value(e, from.clone(), spender.clone()) {
+        Some(allowance) if allowance.amount > 0 && allowance.purpose != purpose => {
+            return Err(Error::AllowancePurposeMismatch);
+        }
+        Some(allowance) => allowance.amount,
+        None => 0,
+    };
+    let allowance = AllowanceValue {
+        amount: current + amount,
+        expiration_ledger,
+        purpose,
+    };
+    write_allowance(e, from, spender, allowance.amount, expiration_ledger, purpose)?;
+    Ok(allowance)
+}
+
+/// Subtracts `amount` from the live allowance, keeping its expiration and
+/// purpose. Fails rather than clamping if the allowance is smaller.
+pub fn decrease_allowance(
+    e: &Env,
+    from: Address,
+    spender: Address,
+    amount: i128,
+) -> Result<AllowanceValue, Error> {
+    let current = read_allowance_value(e, from.clone(), spender.clone()).unwrap_or(AllowanceValue {
+        amount: 0,
+        expiration_ledger: 0,
+        purpose: AllowancePurpose::Any,
+    });
+    if current.amount < amount {
+        return Err(Error::InsufficientAllowance);
+    }
+    let allowance = AllowanceValue {
+        amount: current.amount - amount,
+        ..current
+    };
+    write_allowance(
+        e,
+        from,
+        spender,
+        allowance.amount,
+        allowance.expiration_ledger,
+        allowance.purpose,
+    )?;
+    Ok(allowance)
+}
+
+// ── Spender index ─────────────────────────────────────────────────────────────
+
+/// Spenders `from` has granted a live, non-zero allowance to, in the order
+/// they were first approved.
+pub fn read_spenders(e: &Env, from: &Address) -> Vec<Address> {
+    let mut spenders = Vec::new(e);
+    for spender in read_spender_index(e, from).iter() {
+        if read_allowance(e, from.clone(), spender.clone()) > 0 {
+            spenders.push_back(spender);
+        }
+    }
+    spenders
+}
+
+fn read_spender_index(e: &Env, from: &Address) -> Vec<Address> {
+    e.storage()
+        .persistent()
+        .get(&DataKey::Spenders(from.clone()))
+        .unwrap_or_else(|| Vec::new(e))
+}
+
+/// Adds `spender` to (or drops it from) `from`'s spender index. Spenders
+/// whose allowance has since expired or been used up are pruned on the way,
+/// so the index does not grow past the number of live approvals.
+fn index_spender(e: &Env, from: &Address, spender: &Address, active: bool) {
+    let mut found = false;
+    let mut spenders = Vec::new(e);
+    for existing in read_spender_index(e, from).iter() {
+        if existing == *spender {
+            found = true;
+            if active {
+                spenders.push_back(existing);
+            }
+        } else if read_allowance(e, from.clone(), existing.clone()) > 0 {
+            spenders.push_back(existing);
+        }
+    }
+    if active && !found {
+        spenders.push_back(spender.clone());
+    }
+
+    let key = DataKey::Spenders(from.clone());
+    if spenders.is_empty() {
+        e.storage().persistent().remove(&key);
+        return;
+    }
+    e.storage().persistent().set(&key, &spenders);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+/// Deducts `amount` from the allowance, which must have been granted for
+/// `purpose` (or for any purpose). The remaining allowance keeps its
+/// expiration and purpose.
 pub fn spend_allowance(
     e: &Env,
     from: Address,
     spender: Address,
     amount: i128,
+    purpose: AllowancePurpose,
 ) -> Result<(), Error> {
-    let allowance = read_allowance(e, from.clone(), spender.clone());
-    if allowance < amount {
+    let allowance = match read_allowance_value(e, from.clone(), spender.clone()) {
+        Some(allowance) => allowance,
+        None if amount > 0 => return Err(Error::InsufficientAllowance),
+        None => return Ok(()),
+    };
+    if allowance.purpose != AllowancePurpose::Any && allowance.purpose != purpose {
+        return Err(Error::AllowancePurposeMismatch);
+    }
+    if allowance.amount < amount {
         return Err(Error::InsufficientAllowance);
     }
     if amount > 0 {
-        write_allowance(e, from, spender, allowance - amount, e.ledger().sequence())?;
+        write_allowance(
+            e,
+            from,
+            spender,
+            allowance.amount - amount,
+            allowance.expiration_ledger,
+            allowance.purpose,
+        )?;
     }
     Ok(())
 }
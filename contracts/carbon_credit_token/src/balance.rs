@@ -28,7 +28,23 @@ pub fn receive_balance(e: &Env, addr: Address, amount: i128) {
     write_balance(e, addr, balance + amount);
 }
 
-pub fn spend_balance(e: &Env, addr: Address, amount: i128) -> Result<(), Error> {
+/// Debits `amount` from `addr`, which may only spend what is not `frozen`.
+/// The compliance module keeps freezes; see `compliance::require_compliant`.
+pub fn spend_balance(e: &Env, addr: Address, amount: i128, frozen: i128) -> Result<(), Error> {
+    let balance = read_balance(e, addr.clone());
+    if balance < amount {
+        return Err(Error::InsufficientBalance);
+    }
+    if balance - frozen < amount {
+        return Err(Error::BalanceFrozen);
+    }
+    write_balance(e, addr, balance - amount);
+    Ok(())
+}
+
+/// Debits `amount` from `addr` regardless of any freeze, for SuperAdmin
+/// corrections such as reversing an issuance or a forced transfer.
+pub fn seize_balance(e: &Env, addr: Address, amount: i128) -> Result<(), Error> {
     let balance = read_balance(e, addr.clone());
     if balance < amount {
         return Err(Error::InsufficientBalance);
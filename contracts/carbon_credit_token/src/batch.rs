@@ -0,0 +1,271 @@
+use soroban_sdk::{contracttype, Address, Env, String, Vec};
+
+use crate::error::Error;
+use crate::storage::{DataKey, BALANCE_BUMP_AMOUNT, BALANCE_LIFETIME_THRESHOLD};
+
+/// Batch created from the project metadata passed to `initialize`.
+/// Plain `mint` issues into this batch.
+pub const DEFAULT_BATCH_ID: u32 = 1;
+
+/// An issuance batch: every credit belongs to exactly one project/vintage pair.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct CreditBatch {
+    pub id: u32,
+    pub project_name: String,
+    pub vintage: String,
+    pub location: String,
+    pub metadata_url: String,
+    pub issued: i128,
+    pub retired: i128,
+    /// Credits clawed back after their issuance was reversed.
+    pub reversed: i128,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct BatchKey {
+    pub project_name: String,
+    pub vintage: String,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct BatchBalanceKey {
+    pub batch_id: u32,
+    pub holder: Address,
+}
+
+/// A quantity of credits drawn from (or credited to) a single batch.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct BatchAmount {
+    pub batch_id: u32,
+    pub amount: i128,
+}
+
+// ── Batch registry ────────────────────────────────────────────────────────────
+
+pub fn read_batch(e: &Env, batch_id: u32) -> Option<CreditBatch> {
+    e.storage().persistent().get(&DataKey::Batch(batch_id))
+}
+
+pub fn write_batch(e: &Env, batch: &CreditBatch) {
+    let key = DataKey::Batch(batch.id);
+    e.storage().persistent().set(&key, batch);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+pub fn read_batch_id(e: &Env, project_name: String, vintage: String) -> Option<u32> {
+    e.storage()
+        .persistent()
+        .get(&DataKey::BatchIndex(BatchKey {
+            project_name,
+            vintage,
+        }))
+}
+
+/// Ids of every vintage batch registered under `project_name`.
+pub fn read_project_batches(e: &Env, project_name: &String) -> Vec<u32> {
+    e.storage()
+        .persistent()
+        .get(&DataKey::ProjectBatches(project_name.clone()))
+        .unwrap_or_else(|| Vec::new(e))
+}
+
+/// Registers a new project/vintage batch and returns its id.
+pub fn create_batch(
+    e: &Env,
+    project_name: String,
+    vintage: String,
+    location: String,
+    metadata_url: String,
+) -> Result<u32, Error> {
+    let index_key = DataKey::BatchIndex(BatchKey {
+        project_name: project_name.clone(),
+        vintage: vintage.clone(),
+    });
+    if e.storage().persistent().has(&index_key) {
+        return Err(Error::BatchAlreadyExists);
+    }
+
+    let id = e
+        .storage()
+        .instance()
+        .get::<DataKey, u32>(&DataKey::NextBatchId)
+        .unwrap_or(0)
+        + 1;
+    e.storage().instance().set(&DataKey::NextBatchId, &id);
+
+    let project_key = DataKey::ProjectBatches(project_name.clone());
+    let mut project_batches = read_project_batches(e, &project_name);
+    project_batches.push_back(id);
+    e.storage().persistent().set(&project_key, &project_batches);
+    e.storage()
+        .persistent()
+        .extend_ttl(&project_key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+
+    write_batch(
+        e,
+        &CreditBatch {
+            id,
+            project_name,
+            vintage,
+            location,
+            metadata_url,
+            issued: 0,
+            retired: 0,
+            reversed: 0,
+        },
+    );
+    e.storage().persistent().set(&index_key, &id);
+    e.storage()
+        .persistent()
+        .extend_ttl(&index_key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+
+    Ok(id)
+}
+
+pub fn record_issued(e: &Env, batch_id: u32, amount: i128) -> Result<(), Error> {
+    let mut batch = read_batch(e, batch_id).ok_or(Error::BatchNotFound)?;
+    batch.issued += amount;
+    write_batch(e, &batch);
+    Ok(())
+}
+
+pub fn record_reversed(e: &Env, batch_id: u32, amount: i128) {
+    if let Some(mut batch) = read_batch(e, batch_id) {
+        batch.reversed += amount;
+        write_batch(e, &batch);
+    }
+}
+
+pub fn record_retired(e: &Env, lots: &Vec<BatchAmount>) {
+    for lot in lots.iter() {
+        if let Some(mut batch) = read_batch(e, lot.batch_id) {
+            batch.retired += lot.amount;
+            write_batch(e, &batch);
+        }
+    }
+}
+
+// ── Per-batch balances ────────────────────────────────────────────────────────
+
+/// Batches an address currently holds, oldest first: ids are assigned in
+/// creation order and the list is kept sorted by id.
+pub fn read_holder_batches(e: &Env, holder: &Address) -> Vec<u32> {
+    e.storage()
+        .persistent()
+        .get(&DataKey::HolderBatches(holder.clone()))
+        .unwrap_or_else(|| Vec::new(e))
+}
+
+fn write_holder_batches(e: &Env, holder: &Address, batches: &Vec<u32>) {
+    let key = DataKey::HolderBatches(holder.clone());
+    if batches.is_empty() {
+        e.storage().persistent().remove(&key);
+        return;
+    }
+    e.storage().persistent().set(&key, batches);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+pub fn read_batch_balance(e: &Env, batch_id: u32, holder: Address) -> i128 {
+    let key = DataKey::BatchBalance(BatchBalanceKey { batch_id, holder });
+    if let Some(balance) = e.storage().persistent().get::<DataKey, i128>(&key) {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+        balance
+    } else {
+        0
+    }
+}
+
+fn write_batch_balance(e: &Env, batch_id: u32, holder: Address, amount: i128) {
+    let key = DataKey::BatchBalance(BatchBalanceKey { batch_id, holder });
+    if amount == 0 {
+        e.storage().persistent().remove(&key);
+        return;
+    }
+    e.storage().persistent().set(&key, &amount);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+pub fn receive_batch_balance(e: &Env, batch_id: u32, holder: &Address, amount: i128) {
+    if amount == 0 {
+        return;
+    }
+    let balance = read_batch_balance(e, batch_id, holder.clone());
+    if balance == 0 {
+        let mut batches = read_holder_batches(e, holder);
+        if !batches.contains(batch_id) {
+            let pos = batches
+                .iter()
+                .position(|held| held > batch_id)
+                .unwrap_or(batches.len() as usize);
+            batches.insert(pos as u32, batch_id);
+            write_holder_batches(e, holder, &batches);
+        }
+    }
+    write_batch_balance(e, batch_id, holder.clone(), balance + amount);
+}
+
+pub fn spend_batch_balance(
+    e: &Env,
+    batch_id: u32,
+    holder: &Address,
+    amount: i128,
+) -> Result<(), Error> {
+    let balance = read_batch_balance(e, batch_id, holder.clone());
+    if balance < amount {
+        return Err(Error::InsufficientBalance);
+    }
+    write_batch_balance(e, batch_id, holder.clone(), balance - amount);
+    if balance == amount && amount > 0 {
+        let mut batches = read_holder_batches(e, holder);
+        if let Some(pos) = batches.first_index_of(batch_id) {
+            batches.remove(pos);
+            write_holder_batches(e, holder, &batches);
+        }
+    }
+    Ok(())
+}
+
+/// Draws `amount` from the holder's batches, lowest (oldest) batch id first,
+/// and returns the lots consumed. Used by the aggregate (SEP-41) entrypoints,
+/// which do not name a batch.
+pub fn spend_batches(e: &Env, holder: &Address, amount: i128) -> Vec<BatchAmount> {
+    let mut lots = Vec::new(e);
+    let mut remaining = amount;
+    for batch_id in read_holder_batches(e, holder).iter() {
+        if remaining == 0 {
+            break;
+        }
+        let available = read_batch_balance(e, batch_id, holder.clone());
+        let take = available.min(remaining);
+        if take == 0 {
+            continue;
+        }
+        // `take` never exceeds the batch balance, so this cannot fail.
+        let _ = spend_batch_balance(e, batch_id, holder, take);
+        lots.push_back(BatchAmount {
+            batch_id,
+            amount: take,
+        });
+        remaining -= take;
+    }
+    lots
+}
+
+pub fn receive_batches(e: &Env, holder: &Address, lots: &Vec<BatchAmount>) {
+    for lot in lots.iter() {
+        receive_batch_balance(e, lot.batch_id, holder, lot.amount);
+    }
+}
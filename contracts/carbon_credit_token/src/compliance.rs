@@ -0,0 +1,49 @@
+use soroban_sdk::{contractclient, Address, Env};
+
+use crate::error::{forward, Error};
+use crate::modules::read_modules;
+
+/// Cross-contract client interface for the compliance module, which keeps
+/// the blacklist, partial freezes, permissioned-holder mode and the external
+/// compliance hook.
+#[contractclient(name = "ComplianceClient")]
+#[allow(dead_code)]
+pub trait ComplianceInterface {
+    /// Checks a movement of `amount` from `from` to `to`, made by `spender`
+    /// when an allowance is used, and returns the amount frozen on `from`.
+    /// Mints are reported with the token contract as `from`, burns and
+    /// retirements with the token contract as `to`.
+    fn check_transfer(
+        env: Env,
+        spender: Option<Address>,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<i128, Error>;
+
+    fn is_blacklisted(env: Env, addr: Address) -> bool;
+}
+
+fn client(e: &Env) -> ComplianceClient<'_> {
+    ComplianceClient::new(e, &read_modules(e).compliance)
+}
+
+/// Has the compliance module check a movement. Returns the amount frozen on
+/// `from`, which the caller must leave unspent.
+pub fn require_compliant(
+    e: &Env,
+    spender: Option<&Address>,
+    from: &Address,
+    to: &Address,
+    amount: i128,
+) -> Result<i128, Error> {
+    forward(client(e).try_check_transfer(&spender.cloned(), from, to, &amount))
+}
+
+pub fn require_not_blacklisted(e: &Env, addr: &Address) -> Result<(), Error> {
+    if client(e).is_blacklisted(addr) {
+        Err(Error::Blacklisted)
+    } else {
+        Ok(())
+    }
+}
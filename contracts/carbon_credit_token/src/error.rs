@@ -1,5 +1,10 @@
 use soroban_sdk::contracterror;
 
+/// Codes are shared with the module contracts (issuance, retirement,
+/// compliance, permits, reversals, ex-ante and mint approvals): each
+/// contract lists the codes it can return, under the same number
+/// everywhere, so an error raised in a cross-contract call reads the same
+/// from the caller. Gaps in the numbering are codes only the modules return.
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
@@ -22,10 +27,36 @@ pub enum Error {
     ZeroRetirementAmount = 8,
     /// The successor address for super admin is invalid.
     InvalidSuccessor = 9,
-    /// Only the SuperAdmin can blacklist/unblacklist themselves.
-    CannotBlacklistSelf = 10,
-    /// The report hash has already been used.
-    ReportHashUsed = 11,
     /// The contract is paused.
     ContractPaused = 12,
+    /// No batch exists with the given id.
+    BatchNotFound = 13,
+    /// A batch for this project and vintage already exists.
+    BatchAlreadyExists = 14,
+    /// The allowance was granted for a different purpose.
+    AllowancePurposeMismatch = 17,
+    /// The amount exceeds the account's balance that is not frozen.
+    BalanceFrozen = 40,
+    /// In permissioned-holder mode, a counterparty holds no Trader, Verifier
+    /// or Admin role.
+    HolderNotPermitted = 43,
+    /// The registered compliance hook rejected the movement.
+    TransferNotCompliant = 44,
+    /// No super admin handover is pending.
+    NoPendingSuperAdmin = 45,
+    /// The pending super admin handover has expired.
+    SuperAdminTransferExpired = 46,
+    /// The deployment is at a schema version `migrate` cannot upgrade.
+    SchemaNotMigratable = 49,
+}
+
+/// Passes on the error of a failed `try_` call to a module contract. Modules
+/// reuse the token's codes, so the callee's error keeps its meaning here.
+/// Anything else, such as a trap in the callee, aborts the call.
+pub fn forward<T, C, I>(result: Result<Result<T, C>, Result<Error, I>>) -> Result<T, Error> {
+    match result {
+        Ok(Ok(value)) => Ok(value),
+        Err(Ok(error)) => Err(error),
+        _ => panic!("module call failed"),
+    }
 }
@@ -1,4 +1,6 @@
-use soroban_sdk::{contracttype, symbol_short, Address, Bytes, Env, String};
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, String};
+
+use crate::storage::AllowancePurpose;
 
 #[derive(Clone, Debug)]
 #[contracttype]
@@ -50,92 +52,173 @@ pub struct ApproveEvent {
     pub spender: Address,
     pub amount: i128,
     pub expiration_ledger: u32,
+    pub purpose: AllowancePurpose,
 }
 
 impl ApproveEvent {
     pub fn publish(self, env: &Env) {
         env.events().publish(
             (symbol_short!("approve"), self.from, self.spender),
-            (self.amount, self.expiration_ledger),
+            (self.amount, self.expiration_ledger, self.purpose),
         );
     }
 }
 
 #[derive(Clone, Debug)]
 #[contracttype]
-pub struct RetirementData {
-    pub amount: i128,
-    pub timestamp: u64,
-    pub report_hash: Bytes,
-    pub methodology: String,
+pub struct PauseEvent {
+    pub admin: Address,
 }
 
+impl PauseEvent {
+    pub fn publish(self, env: &Env) {
+        env.events()
+            .publish((symbol_short!("paused"), self.admin), true);
+    }
+}
 
 #[derive(Clone, Debug)]
 #[contracttype]
-pub struct RetirementEvent {
-    pub from: Address,
-    pub amount: i128,
-    pub timestamp: u64,
-    pub report_hash: Bytes,
-    pub methodology: String,
+pub struct UnpauseEvent {
+    pub admin: Address,
 }
 
-
-impl RetirementEvent {
+impl UnpauseEvent {
     pub fn publish(self, env: &Env) {
-        let data = RetirementData {
-            amount: self.amount,
-            timestamp: self.timestamp,
-            report_hash: self.report_hash,
-            methodology: self.methodology,
-        };
         env.events()
-            .publish((symbol_short!("retire"), self.from), data);
+            .publish((symbol_short!("unpaused"), self.admin), false);
     }
 }
 
 #[derive(Clone, Debug)]
 #[contracttype]
-pub struct CertificateGeneratedEvent {
-    pub certificate_id: u64,
-    pub corporate: Address,
-    pub amount: i128,
-    pub timestamp: u64,
+pub struct SuperAdminProposedEvent {
+    pub super_admin: Address,
+    pub successor: Address,
+    pub expiration_ledger: u32,
 }
 
-impl CertificateGeneratedEvent {
+impl SuperAdminProposedEvent {
     pub fn publish(self, env: &Env) {
         env.events().publish(
-            (symbol_short!("cert"), self.corporate, self.certificate_id),
-            (self.amount, self.timestamp),
+            (symbol_short!("sa_prop"), self.super_admin, self.successor),
+            self.expiration_ledger,
         );
     }
 }
 
 #[derive(Clone, Debug)]
 #[contracttype]
-pub struct PauseEvent {
-    pub admin: Address,
+pub struct SuperAdminAcceptedEvent {
+    pub previous: Address,
+    pub super_admin: Address,
 }
 
-impl PauseEvent {
+impl SuperAdminAcceptedEvent {
     pub fn publish(self, env: &Env) {
-        env.events()
-            .publish((symbol_short!("paused"), self.admin), true);
+        env.events().publish(
+            (symbol_short!("sa_accept"), self.previous, self.super_admin),
+            (),
+        );
     }
 }
 
 #[derive(Clone, Debug)]
 #[contracttype]
-pub struct UnpauseEvent {
-    pub admin: Address,
+pub struct SuperAdminCancelledEvent {
+    pub super_admin: Address,
+    pub successor: Address,
 }
 
-impl UnpauseEvent {
+impl SuperAdminCancelledEvent {
+    pub fn publish(self, env: &Env) {
+        env.events().publish(
+            (symbol_short!("sa_cncl"), self.super_admin, self.successor),
+            (),
+        );
+    }
+}
+
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct UpgradeEvent {
+    pub new_wasm_hash: BytesN<32>,
+}
+
+impl UpgradeEvent {
     pub fn publish(self, env: &Env) {
         env.events()
-            .publish((symbol_short!("unpaused"), self.admin), false);
+            .publish((symbol_short!("upgraded"),), self.new_wasm_hash);
+    }
+}
+
+#[cfg(any(test, feature = "migrator"))]
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct MigrateEvent {
+    pub from_version: u32,
+    pub to_version: u32,
+    pub moved: u32,
+}
+
+#[cfg(any(test, feature = "migrator"))]
+impl MigrateEvent {
+    pub fn publish(self, env: &Env) {
+        env.events().publish(
+            (symbol_short!("migrated"), self.to_version),
+            (self.from_version, self.moved),
+        );
+    }
+}
+
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct BatchCreatedEvent {
+    pub batch_id: u32,
+    pub project_name: String,
+    pub vintage: String,
+}
+
+impl BatchCreatedEvent {
+    pub fn publish(self, env: &Env) {
+        env.events().publish(
+            (symbol_short!("batch"), self.batch_id),
+            (self.project_name, self.vintage),
+        );
+    }
+}
+
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct BatchTransferEvent {
+    pub batch_id: u32,
+    pub from: Address,
+    pub to: Address,
+    pub amount: i128,
+}
+
+impl BatchTransferEvent {
+    pub fn publish(self, env: &Env) {
+        env.events().publish(
+            (symbol_short!("batch_xfr"), self.from, self.to),
+            (self.batch_id, self.amount),
+        );
     }
 }
 
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct BatchRetirementEvent {
+    pub batch_id: u32,
+    pub from: Address,
+    pub amount: i128,
+}
+
+impl BatchRetirementEvent {
+    pub fn publish(self, env: &Env) {
+        env.events().publish(
+            (symbol_short!("batch_ret"), self.from),
+            (self.batch_id, self.amount),
+        );
+    }
+}
@@ -1,29 +1,37 @@
-/// Integration tests: real RbacContract + CarbonCreditToken in the same env.
+/// Integration tests: real RbacContract + CarbonCreditToken and its module
+/// contracts in the same env.
 ///
 /// These tests complement the mock-based unit tests in `test.rs` by exercising
 /// the actual cross-contract call path that `require_verifier` takes at runtime.
 /// Any serialisation mismatch, storage-key collision, or interface drift between
-/// the two contracts will surface here rather than silently passing with a mock.
+/// the contracts will surface here rather than silently passing with a mock.
 #[cfg(test)]
 mod integration_tests {
+    use crate::modules::Modules;
+    use crate::test::{MockVerifierRegistry, MockVerifierRegistryClient};
     use crate::{CarbonCreditToken, CarbonCreditTokenClient};
+    use credit_compliance::{CreditCompliance, CreditComplianceClient};
+    use credit_issuance::{CreditIssuance, CreditIssuanceClient, Error};
+    use ex_ante_credits::{ExAnteCredits, ExAnteCreditsClient};
     use rbac::{RbacContract, RbacContractClient};
-    use soroban_sdk::{testutils::Address as _, Address, Bytes, Env};
+    use soroban_sdk::{testutils::Address as _, Address, Bytes, Env, String};
 
     // ── Helpers ───────────────────────────────────────────────────────────────
 
-    /// Deploys and initialises both contracts, returning a ready-to-use tuple.
+    /// Deploys and initialises the RBAC contract, the token and its
+    /// compliance, issuance and ex-ante modules, returning a ready-to-use
+    /// tuple.
     ///
     /// The RBAC contract is initialised with `super_admin` as its SuperAdmin.
     /// The token contract is wired to the real RBAC contract address.
     fn setup<'a>() -> (
         Env,
         CarbonCreditTokenClient<'a>,
+        CreditIssuanceClient<'a>,
+        ExAnteCreditsClient<'a>,
         RbacContractClient<'a>,
         Address, // super_admin
     ) {
-        use soroban_sdk::String;
-
         let env = Env::default();
         env.mock_all_auths();
 
@@ -35,12 +43,24 @@ mod integration_tests {
         let admins = soroban_sdk::vec![&env, super_admin.clone()];
         rbac.initialize(&admins, &1u32, &0u64);
 
-        // Deploy the token contract wired to the real RBAC contract.
+        // Deploy the token contract wired to the real RBAC contract, then
+        // bind the modules to it.
+        let registry_id = env.register_contract(None, MockVerifierRegistry);
+        let compliance_id = env.register_contract(None, CreditCompliance);
+        let issuance_id = env.register_contract(None, CreditIssuance);
+        let ex_ante_id = env.register_contract(None, ExAnteCredits);
         let token_id = env.register_contract(None, CarbonCreditToken);
         let token = CarbonCreditTokenClient::new(&env, &token_id);
         token.initialize(
             &super_admin,
             &rbac_id,
+            &Modules {
+                issuance: issuance_id.clone(),
+                retirement: Address::generate(&env),
+                compliance: compliance_id.clone(),
+                permits: Address::generate(&env),
+                reversals: Address::generate(&env),
+            },
             &String::from_str(&env, "Carbon Credit Token"),
             &String::from_str(&env, "CCT"),
             &0u32,
@@ -50,25 +70,50 @@ mod integration_tests {
             &String::from_str(&env, "https://farmcredit.xyz/amazon-1"),
         );
 
-        (env, token, rbac, super_admin)
+        CreditComplianceClient::new(&env, &compliance_id).initialize(&token_id);
+        let issuance = CreditIssuanceClient::new(&env, &issuance_id);
+        let mint_approvals = Address::generate(&env);
+        issuance.initialize(&token_id, &registry_id, &ex_ante_id, &mint_approvals);
+        let ex_ante = ExAnteCreditsClient::new(&env, &ex_ante_id);
+        ex_ante.initialize(&token_id);
+
+        (env, token, issuance, ex_ante, rbac, super_admin)
     }
 
     fn hash(env: &Env, tag: &[u8]) -> Bytes {
         Bytes::from_slice(env, tag)
     }
 
+    /// Files a report with `hash` from `verifier` for `farmer` in the
+    /// issuance module's registry, so a mint with that hash passes the
+    /// registry check.
+    fn submit_report(
+        issuance: &CreditIssuanceClient,
+        verifier: &Address,
+        farmer: &Address,
+        hash: &Bytes,
+    ) {
+        extern crate std;
+
+        let env = &issuance.env;
+        let registry = MockVerifierRegistryClient::new(env, &issuance.verifier_registry());
+        let hash: std::vec::Vec<u8> = hash.iter().collect();
+        registry.submit_report_hash(verifier, farmer, &String::from_bytes(env, &hash));
+    }
+
     // ── Tests ─────────────────────────────────────────────────────────────────
 
     /// A verifier granted through the real RBAC contract can mint tokens.
     #[test]
     fn test_verifier_can_mint() {
-        let (env, token, rbac, super_admin) = setup();
+        let (env, token, issuance, _ex_ante, rbac, super_admin) = setup();
         let verifier = Address::generate(&env);
         let recipient = Address::generate(&env);
 
         rbac.grant_verifier(&super_admin, &verifier);
 
-        token.mint(&verifier, &recipient, &500, &hash(&env, b"hash-001"));
+        submit_report(&issuance, &verifier, &recipient, &hash(&env, b"hash-001"));
+        issuance.mint(&verifier, &recipient, &500, &hash(&env, b"hash-001"));
 
         assert_eq!(token.balance(&recipient), 500);
         assert_eq!(token.total_supply(), 500);
@@ -76,64 +121,70 @@ mod integration_tests {
 
     /// An address with no role must not be able to mint.
     #[test]
-    #[should_panic]
     fn test_non_verifier_cannot_mint() {
-        let (env, token, _rbac, _super_admin) = setup();
+        let (env, _token, issuance, _ex_ante, _rbac, _super_admin) = setup();
         let stranger = Address::generate(&env);
         let recipient = Address::generate(&env);
 
-        // No role granted — `has_role` returns false, `require_verifier` panics.
-        token.mint(&stranger, &recipient, &100, &hash(&env, b"hash-002"));
+        // No role granted — `has_role` returns false, `require_verifier` rejects.
+        submit_report(&issuance, &stranger, &recipient, &hash(&env, b"hash-002"));
+        let result = issuance.try_mint(&stranger, &recipient, &100, &hash(&env, b"hash-002"));
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
     }
 
     /// Revoking the Verifier role through RBAC immediately prevents minting.
     #[test]
-    #[should_panic]
     fn test_revoked_verifier_cannot_mint() {
-        let (env, token, rbac, super_admin) = setup();
+        let (env, token, issuance, _ex_ante, rbac, super_admin) = setup();
         let verifier = Address::generate(&env);
         let recipient = Address::generate(&env);
 
         // Grant, mint once successfully, then revoke.
         rbac.grant_verifier(&super_admin, &verifier);
-        token.mint(&verifier, &recipient, &200, &hash(&env, b"hash-003"));
+        submit_report(&issuance, &verifier, &recipient, &hash(&env, b"hash-003"));
+        issuance.mint(&verifier, &recipient, &200, &hash(&env, b"hash-003"));
         assert_eq!(token.balance(&recipient), 200);
 
         rbac.revoke_role(&super_admin, &verifier);
 
-        // Second mint must panic — role has been revoked.
-        token.mint(&verifier, &recipient, &100, &hash(&env, b"hash-004"));
+        // Second mint must fail — role has been revoked.
+        submit_report(&issuance, &verifier, &recipient, &hash(&env, b"hash-004"));
+        let result = issuance.try_mint(&verifier, &recipient, &100, &hash(&env, b"hash-004"));
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        assert_eq!(token.balance(&recipient), 200);
     }
 
     /// An Admin in RBAC does not automatically gain Verifier minting rights.
     #[test]
-    #[should_panic]
     fn test_admin_role_does_not_grant_mint() {
-        let (env, token, rbac, super_admin) = setup();
+        let (env, _token, issuance, _ex_ante, rbac, super_admin) = setup();
         let admin = Address::generate(&env);
         let recipient = Address::generate(&env);
 
         rbac.grant_admin(&super_admin, &admin);
 
         // Admin ≠ Verifier — minting must be rejected.
-        token.mint(&admin, &recipient, &100, &hash(&env, b"hash-005"));
+        submit_report(&issuance, &admin, &recipient, &hash(&env, b"hash-005"));
+        let result = issuance.try_mint(&admin, &recipient, &100, &hash(&env, b"hash-005"));
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
     }
 
     /// The SuperAdmin of RBAC does not automatically gain Verifier minting rights.
     #[test]
-    #[should_panic]
     fn test_super_admin_cannot_mint_without_verifier_role() {
-        let (env, token, _rbac, super_admin) = setup();
+        let (env, _token, issuance, _ex_ante, _rbac, super_admin) = setup();
         let recipient = Address::generate(&env);
 
         // SuperAdmin has no Verifier role — minting must be rejected.
-        token.mint(&super_admin, &recipient, &100, &hash(&env, b"hash-006"));
+        submit_report(&issuance, &super_admin, &recipient, &hash(&env, b"hash-006"));
+        let result = issuance.try_mint(&super_admin, &recipient, &100, &hash(&env, b"hash-006"));
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
     }
 
     /// Multiple distinct verifiers can each mint independently.
     #[test]
     fn test_multiple_verifiers_can_mint_independently() {
-        let (env, token, rbac, super_admin) = setup();
+        let (env, token, issuance, _ex_ante, rbac, super_admin) = setup();
         let verifier_a = Address::generate(&env);
         let verifier_b = Address::generate(&env);
         let recipient = Address::generate(&env);
@@ -141,8 +192,10 @@ mod integration_tests {
         rbac.grant_verifier(&super_admin, &verifier_a);
         rbac.grant_verifier(&super_admin, &verifier_b);
 
-        token.mint(&verifier_a, &recipient, &300, &hash(&env, b"hash-007"));
-        token.mint(&verifier_b, &recipient, &700, &hash(&env, b"hash-008"));
+        submit_report(&issuance, &verifier_a, &recipient, &hash(&env, b"hash-007"));
+        issuance.mint(&verifier_a, &recipient, &300, &hash(&env, b"hash-007"));
+        submit_report(&issuance, &verifier_b, &recipient, &hash(&env, b"hash-008"));
+        issuance.mint(&verifier_b, &recipient, &700, &hash(&env, b"hash-008"));
 
         assert_eq!(token.balance(&recipient), 1000);
         assert_eq!(token.total_supply(), 1000);
@@ -151,7 +204,7 @@ mod integration_tests {
     /// Revoking one verifier does not affect another verifier's ability to mint.
     #[test]
     fn test_revoking_one_verifier_does_not_affect_another() {
-        let (env, token, rbac, super_admin) = setup();
+        let (env, token, issuance, _ex_ante, rbac, super_admin) = setup();
         let verifier_a = Address::generate(&env);
         let verifier_b = Address::generate(&env);
         let recipient = Address::generate(&env);
@@ -162,7 +215,8 @@ mod integration_tests {
         rbac.revoke_role(&super_admin, &verifier_a);
 
         // verifier_b must still be able to mint.
-        token.mint(&verifier_b, &recipient, &400, &hash(&env, b"hash-009"));
+        submit_report(&issuance, &verifier_b, &recipient, &hash(&env, b"hash-009"));
+        issuance.mint(&verifier_b, &recipient, &400, &hash(&env, b"hash-009"));
         assert_eq!(token.balance(&recipient), 400);
     }
 
@@ -170,23 +224,109 @@ mod integration_tests {
     #[test]
     #[should_panic]
     fn test_duplicate_report_hash_rejected() {
-        let (env, token, rbac, super_admin) = setup();
+        let (env, _token, issuance, _ex_ante, rbac, super_admin) = setup();
         let verifier = Address::generate(&env);
         let recipient = Address::generate(&env);
 
         rbac.grant_verifier(&super_admin, &verifier);
 
         let h = hash(&env, b"hash-010");
-        token.mint(&verifier, &recipient, &100, &h);
+        submit_report(&issuance, &verifier, &recipient, &h);
+        issuance.mint(&verifier, &recipient, &100, &h);
 
         // Second mint with the same hash must panic.
-        token.mint(&verifier, &recipient, &100, &h);
+        submit_report(&issuance, &verifier, &recipient, &h);
+        issuance.mint(&verifier, &recipient, &100, &h);
+    }
+
+    /// Only the issuing verifier converts a tranche; an Admin, who holds no
+    /// Verifier role, may still cancel it.
+    #[test]
+    fn test_tranche_settlement_with_one_role_per_account() {
+        use ex_ante_credits::Error;
+
+        let (env, token, issuance, ex_ante, rbac, super_admin) = setup();
+        let verifier = Address::generate(&env);
+        let other_verifier = Address::generate(&env);
+        let admin = Address::generate(&env);
+        let farmer = Address::generate(&env);
+
+        rbac.grant_verifier(&super_admin, &verifier);
+        rbac.grant_verifier(&super_admin, &other_verifier);
+        rbac.grant_admin(&super_admin, &admin);
+
+        let tranche_id = ex_ante.mint_ex_ante(&verifier, &farmer, &1, &1000);
+        let h = hash(&env, b"mrv-001");
+        submit_report(&issuance, &verifier, &farmer, &h);
+        let result = ex_ante.try_convert_ex_ante(&other_verifier, &tranche_id, &100, &h);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        let result = ex_ante.try_convert_ex_ante(&admin, &tranche_id, &100, &h);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        let result = ex_ante.try_cancel_ex_ante(&other_verifier, &tranche_id);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+        ex_ante.convert_ex_ante(&verifier, &tranche_id, &400, &h);
+        assert_eq!(token.balance(&farmer), 400);
+        assert_eq!(ex_ante.cancel_ex_ante(&admin, &tranche_id), 600);
+    }
+
+    /// In permissioned-holder mode each party's role is read with one
+    /// `get_role` call; the codes must match the real RBAC contract.
+    #[test]
+    fn test_permissioned_mode_with_real_rbac_roles() {
+        let (env, token, issuance, _ex_ante, rbac, super_admin) = setup();
+        let compliance = CreditComplianceClient::new(&env, &token.modules().compliance);
+        let verifier = Address::generate(&env);
+        let trader = Address::generate(&env);
+        let admin = Address::generate(&env);
+        let stranger = Address::generate(&env);
+
+        rbac.grant_verifier(&super_admin, &verifier);
+        rbac.grant_trader(&super_admin, &trader);
+        rbac.grant_admin(&super_admin, &admin);
+
+        let h = hash(&env, b"hash-010");
+        submit_report(&issuance, &verifier, &trader, &h);
+        issuance.mint(&verifier, &trader, &500, &h);
+        compliance.set_permissioned_mode(&true);
+
+        token.transfer(&trader, &verifier, &100);
+        token.transfer(&trader, &admin, &100);
+        let result = token.try_transfer(&trader, &stranger, &100);
+        assert_eq!(result, Err(Ok(crate::Error::HolderNotPermitted)));
+        let result = token.try_transfer(&trader, &super_admin, &100);
+        assert_eq!(result, Err(Ok(crate::Error::HolderNotPermitted)));
     }
 
     /// `rbac_contract()` on the token returns the address of the real RBAC contract.
     #[test]
     fn test_rbac_contract_address_stored_correctly() {
-        let (_env, token, rbac, _super_admin) = setup();
-        assert_eq!(token.rbac_contract(), *rbac.address);
+        let (_env, token, _issuance, _ex_ante, rbac, _super_admin) = setup();
+        assert_eq!(token.rbac_contract(), rbac.address);
+    }
+
+    #[test]
+    fn test_governance_mode_routes_admin_calls_through_rbac_proposals() {
+        use rbac::{ContractCall, ProposalAction};
+        use soroban_sdk::{IntoVal, Symbol};
+
+        let (env, token, _issuance, _ex_ante, rbac, super_admin) = setup();
+        let compliance = CreditComplianceClient::new(&env, &token.modules().compliance);
+        let target = Address::generate(&env);
+        token.set_governance_mode(&true);
+
+        // The SuperAdmin key alone is no longer the authority, for the
+        // modules' admin calls too.
+        compliance.blacklist(&target);
+        assert_eq!(env.auths()[0].0, rbac.address);
+        compliance.unblacklist(&target);
+
+        let action = ProposalAction::InvokeContract(ContractCall {
+            contract: compliance.address.clone(),
+            function: Symbol::new(&env, "blacklist"),
+            args: soroban_sdk::vec![&env, target.into_val(&env)],
+        });
+        rbac.propose_action(&super_admin, &action);
+        assert!(compliance.is_blacklisted(&target));
     }
 }
@@ -1,39 +1,62 @@
 #![no_std]
+// The migrator build only keeps the migration entrypoints.
+#![cfg_attr(feature = "migrator", allow(dead_code, unused_imports))]
 
 mod admin;
 mod allowance;
 mod balance;
+mod batch;
+mod compliance;
 mod error;
 mod events;
 mod metadata;
-mod rbac;
+#[cfg(any(test, feature = "migrator"))]
+mod migration;
+mod modules;
 mod storage;
 #[cfg(test)]
+mod integration_test;
+#[cfg(test)]
 mod test;
 
-use soroban_sdk::{contract, contractimpl, Address, Bytes, Env, String};
+use soroban_sdk::{contract, contractimpl, vec, Address, BytesN, Env, String, Vec};
+
+pub use crate::admin::PendingSuperAdmin;
+pub use crate::batch::{BatchAmount, CreditBatch, DEFAULT_BATCH_ID};
+pub use crate::error::Error;
+#[cfg(any(test, feature = "migrator"))]
+pub use crate::migration::{TokenMigrator, TokenMigratorClient};
+pub use crate::modules::Modules;
+pub use crate::storage::{AllowanceDataKey, AllowancePurpose};
 
 use crate::admin::{
-    blacklist_address, grant_verifier, is_blacklisted, is_verifier, read_administrator,
-    read_super_admin, revoke_verifier, unblacklist_address, write_administrator, write_super_admin,
+    read_administrator, read_pending_super_admin, read_super_admin, remove_pending_super_admin,
+    write_administrator, write_pending_super_admin, write_super_admin,
+};
+use crate::allowance::{
+    decrease_allowance, increase_allowance, read_allowance, read_allowance_purpose,
+    read_spenders, spend_allowance, write_allowance,
+};
+use crate::balance::{read_balance, receive_balance, seize_balance, spend_balance};
+use crate::batch::{
+    create_batch, read_batch, read_batch_balance, read_batch_id, read_holder_batches,
+    receive_batch_balance, receive_batches, record_issued, record_retired, record_reversed,
+    spend_batch_balance, spend_batches,
 };
-use crate::allowance::{read_allowance, spend_allowance, write_allowance};
-use crate::balance::{read_balance, receive_balance, spend_balance};
-use crate::error::Error;
+use crate::compliance::{require_compliant, require_not_blacklisted};
 use crate::events::{
-    ApproveEvent, BurnEvent, CertificateGeneratedEvent, MintEvent, RetirementEvent, TransferEvent,
-    PauseEvent, UnpauseEvent,
+    ApproveEvent, BatchCreatedEvent, BatchRetirementEvent, BatchTransferEvent, BurnEvent,
+    MintEvent, PauseEvent, SuperAdminAcceptedEvent, SuperAdminCancelledEvent,
+    SuperAdminProposedEvent, TransferEvent, UnpauseEvent, UpgradeEvent,
 };
-
 use crate::metadata::{read_decimals, read_name, read_symbol, write_metadata};
-use crate::rbac::require_verifier;
+use crate::modules::{read_modules, write_modules};
 use crate::storage::{
-    increment_certificate_count, is_initialized, is_report_hash_used, mark_report_hash_used, read_total_retired,
-    read_total_supply, set_initialized, write_rbac_contract, write_total_retired,
-    write_total_supply, INSTANCE_BUMP_AMOUNT, INSTANCE_LIFETIME_THRESHOLD, OffsetCertificate,
-    is_paused, set_paused,
+    is_governance_mode, is_initialized, is_paused, read_rbac_contract, read_schema_version,
+    read_total_retired, read_total_supply, set_governance_mode, set_initialized, set_paused,
+    write_rbac_contract, write_schema_version, write_total_retired, write_total_supply,
+    CURRENT_SCHEMA_VERSION, INSTANCE_BUMP_AMOUNT, INSTANCE_LIFETIME_THRESHOLD,
 };
-use crate::storage::{read_certificates, read_certificate_count, write_certificate};
 
 fn check_nonnegative_amount(amount: i128) -> Result<(), Error> {
     if amount < 0 {
@@ -43,11 +66,14 @@ fn check_nonnegative_amount(amount: i128) -> Result<(), Error> {
     }
 }
 
-fn require_not_blacklisted(env: &Env, addr: &Address) -> Result<(), Error> {
-    if is_blacklisted(env, addr) {
-        Err(Error::Blacklisted)
+/// The address SuperAdmin-only entrypoints require auth from: the SuperAdmin,
+/// or, in governance mode, the RBAC contract, so that those calls have to go
+/// through an executed RBAC proposal.
+fn read_admin_authority(env: &Env) -> Address {
+    if is_governance_mode(env) {
+        read_rbac_contract(env)
     } else {
-        Ok(())
+        read_super_admin(env)
     }
 }
 
@@ -59,116 +85,300 @@ fn require_not_paused(env: &Env) -> Result<(), Error> {
     }
 }
 
+/// Moves `amount` from `from` to `to`, carrying the underlying batches along
+/// (oldest batch first) so per-batch balances stay in step with the aggregate.
+/// `frozen` is what the compliance module reported frozen on `from`.
+fn move_credits(
+    env: &Env,
+    from: &Address,
+    to: &Address,
+    amount: i128,
+    frozen: i128,
+) -> Result<(), Error> {
+    spend_balance(env, from.clone(), amount, frozen)?;
+    receive_balance(env, to.clone(), amount);
+    carry_batches(env, from, to, amount);
+    Ok(())
+}
+
+fn carry_batches(env: &Env, from: &Address, to: &Address, amount: i128) {
+    let lots = spend_batches(env, from, amount);
+    receive_batches(env, to, &lots);
+    for lot in lots.iter() {
+        BatchTransferEvent {
+            batch_id: lot.batch_id,
+            from: from.clone(),
+            to: to.clone(),
+            amount: lot.amount,
+        }
+        .publish(env);
+    }
+}
+
+/// Removes `amount` from circulation, returning the batches it was drawn from.
+fn burn_credits(
+    env: &Env,
+    from: &Address,
+    amount: i128,
+    frozen: i128,
+) -> Result<Vec<BatchAmount>, Error> {
+    spend_balance(env, from.clone(), amount, frozen)?;
+    let lots = spend_batches(env, from, amount);
+    let new_supply = read_total_supply(env) - amount;
+    write_total_supply(env, new_supply);
+    Ok(lots)
+}
+
+/// Writes an allowance once `from` has authorized it, directly or by permit.
+fn approve_allowance(
+    env: &Env,
+    from: Address,
+    spender: Address,
+    amount: i128,
+    expiration_ledger: u32,
+    purpose: AllowancePurpose,
+) -> Result<(), Error> {
+    check_nonnegative_amount(amount)?;
+    require_not_paused(env)?;
+    require_not_blacklisted(env, &from)?;
+
+    env.storage()
+        .instance()
+        .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+    write_allowance(
+        env,
+        from.clone(),
+        spender.clone(),
+        amount,
+        expiration_ledger,
+        purpose,
+    )?;
+
+    ApproveEvent {
+        from,
+        spender,
+        amount,
+        expiration_ledger,
+        purpose,
+    }
+    .publish(env);
+    Ok(())
+}
+
 #[contract]
 pub struct CarbonCreditToken;
 
+#[cfg(any(test, not(feature = "migrator")))]
 #[contractimpl]
 impl CarbonCreditToken {
-    /// Initializes the contract with admin/super-admin, RBAC and metadata.
+    /// Initializes the contract with admin/super-admin, RBAC, the module
+    /// contracts it delegates issuance, retirement and compliance to, and
+    /// metadata. The project metadata becomes the default issuance batch.
     /// Can only be called once.
+    #[allow(clippy::too_many_arguments)]
     pub fn initialize(
         env: Env,
         admin: Address,
         rbac_contract: Address,
+        modules: Modules,
         name: String,
         symbol: String,
         decimals: u32,
+        project_name: String,
+        vintage: String,
+        location: String,
+        metadata_url: String,
     ) -> Result<(), Error> {
         if is_initialized(&env) {
             return Err(Error::AlreadyInitialized);
         }
 
         set_initialized(&env);
+        write_schema_version(&env, CURRENT_SCHEMA_VERSION);
         write_administrator(&env, &admin);
         write_super_admin(&env, &admin);
         write_rbac_contract(&env, &rbac_contract);
+        write_modules(&env, &modules);
         write_metadata(&env, name, symbol, decimals);
         write_total_supply(&env, 0);
         write_total_retired(&env, 0);
+
+        let batch_id = create_batch(&env, project_name.clone(), vintage.clone(), location, metadata_url)?;
+        BatchCreatedEvent {
+            batch_id,
+            project_name,
+            vintage,
+        }
+        .publish(&env);
         Ok(())
     }
 
-    // ── RBAC management (SuperAdmin only) ────────────────────────────────────
+    // ── Upgrades (SuperAdmin only) ────────────────────────────────────────────
 
-    pub fn add_verifier(env: Env, verifier: Address) -> Result<(), Error> {
-        let super_admin = read_super_admin(&env);
-        super_admin.require_auth();
-        require_not_paused(&env)?;
+    /// Replaces the contract code with the uploaded wasm `new_wasm_hash`.
+    /// Storage is kept as is; call `migrate` afterwards if the new code
+    /// bumps the schema version.
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        let authority = read_admin_authority(&env);
+        authority.require_auth();
 
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 
-        grant_verifier(&env, &verifier);
+        env.deployer()
+            .update_current_contract_wasm(new_wasm_hash.clone());
+        UpgradeEvent { new_wasm_hash }.publish(&env);
         Ok(())
     }
 
-    pub fn remove_verifier(env: Env, verifier: Address) -> Result<(), Error> {
+    /// Returns the storage layout version; `0` for deployments that have not
+    /// been migrated since versioning was introduced.
+    pub fn schema_version(env: Env) -> u32 {
+        read_schema_version(&env)
+    }
+
+    /// Returns the module contracts the token delegates to.
+    pub fn modules(env: Env) -> Modules {
+        read_modules(&env)
+    }
+
+    /// Starts handing the SuperAdmin role to `successor`, who must call
+    /// `accept_super_admin` by `expiration_ledger`. Until then the current
+    /// SuperAdmin stays in charge; a new proposal replaces a pending one.
+    pub fn transfer_super_admin(
+        env: Env,
+        successor: Address,
+        expiration_ledger: u32,
+    ) -> Result<(), Error> {
+        let authority = read_admin_authority(&env);
+        authority.require_auth();
+
         let super_admin = read_super_admin(&env);
-        super_admin.require_auth();
-        require_not_paused(&env)?;
+        if successor == super_admin {
+            return Err(Error::InvalidSuccessor);
+        }
+        if expiration_ledger < env.ledger().sequence() {
+            return Err(Error::InvalidExpirationLedger);
+        }
 
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 
-        revoke_verifier(&env, &verifier);
+        write_pending_super_admin(
+            &env,
+            &PendingSuperAdmin {
+                successor: successor.clone(),
+                expiration_ledger,
+            },
+        );
+
+        SuperAdminProposedEvent {
+            super_admin,
+            successor,
+            expiration_ledger,
+        }
+        .publish(&env);
         Ok(())
     }
 
-    pub fn blacklist(env: Env, target: Address) -> Result<(), Error> {
-        let super_admin = read_super_admin(&env);
-        super_admin.require_auth();
+    /// Completes a pending handover. Must be signed by the proposed successor.
+    pub fn accept_super_admin(env: Env) -> Result<(), Error> {
+        let pending = read_pending_super_admin(&env).ok_or(Error::NoPendingSuperAdmin)?;
+        pending.successor.require_auth();
 
-        if target == super_admin {
-            return Err(Error::CannotBlacklistSelf);
+        if pending.expiration_ledger < env.ledger().sequence() {
+            return Err(Error::SuperAdminTransferExpired);
         }
 
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 
-        blacklist_address(&env, &target);
+        let previous = read_super_admin(&env);
+        write_super_admin(&env, &pending.successor);
+        remove_pending_super_admin(&env);
+
+        SuperAdminAcceptedEvent {
+            previous,
+            super_admin: pending.successor,
+        }
+        .publish(&env);
         Ok(())
     }
 
-    pub fn unblacklist(env: Env, target: Address) -> Result<(), Error> {
+    /// Withdraws a pending handover, e.g. one sent to a mistyped address.
+    pub fn cancel_super_admin_transfer(env: Env) -> Result<(), Error> {
+        let authority = read_admin_authority(&env);
+        authority.require_auth();
+
+        let pending = read_pending_super_admin(&env).ok_or(Error::NoPendingSuperAdmin)?;
         let super_admin = read_super_admin(&env);
-        super_admin.require_auth();
 
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 
-        unblacklist_address(&env, &target);
+        remove_pending_super_admin(&env);
+
+        SuperAdminCancelledEvent {
+            super_admin,
+            successor: pending.successor,
+        }
+        .publish(&env);
         Ok(())
     }
 
-    pub fn transfer_super_admin(env: Env, successor: Address) -> Result<(), Error> {
-        let super_admin = read_super_admin(&env);
-        super_admin.require_auth();
+    /// Returns the pending super admin handover, if any. It may have expired.
+    pub fn pending_super_admin(env: Env) -> Option<PendingSuperAdmin> {
+        read_pending_super_admin(&env)
+    }
 
-        if successor == super_admin {
-            return Err(Error::InvalidSuccessor);
-        }
+    // ── Project/vintage batches (SuperAdmin only) ─────────────────────────────
+
+    /// Registers a new issuance batch for a project and vintage.
+    pub fn create_batch(
+        env: Env,
+        project_name: String,
+        vintage: String,
+        location: String,
+        metadata_url: String,
+    ) -> Result<u32, Error> {
+        let authority = read_admin_authority(&env);
+        authority.require_auth();
+        require_not_paused(&env)?;
 
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 
-        write_super_admin(&env, &successor);
-        Ok(())
+        let batch_id = create_batch(
+            &env,
+            project_name.clone(),
+            vintage.clone(),
+            location,
+            metadata_url,
+        )?;
+        BatchCreatedEvent {
+            batch_id,
+            project_name,
+            vintage,
+        }
+        .publish(&env);
+        Ok(batch_id)
     }
 
     // ── Pause / emergency stop (SuperAdmin only) ──────────────────────────────
 
-    /// Pauses all state-mutating operations. SuperAdmin only.
+    /// Pauses all state-mutating operations. SuperAdmin only, or the
+    /// issuance module when its circuit breaker trips.
     pub fn admin_pause(env: Env, admin: Address) -> Result<(), Error> {
-        let super_admin = read_super_admin(&env);
-        super_admin.require_auth();
-        if admin != super_admin {
+        if admin != read_admin_authority(&env) && admin != read_modules(&env).issuance {
             return Err(Error::Unauthorized);
         }
+        admin.require_auth();
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -179,9 +389,9 @@ impl CarbonCreditToken {
 
     /// Unpauses the contract. SuperAdmin only.
     pub fn admin_unpause(env: Env, admin: Address) -> Result<(), Error> {
-        let super_admin = read_super_admin(&env);
-        super_admin.require_auth();
-        if admin != super_admin {
+        let authority = read_admin_authority(&env);
+        authority.require_auth();
+        if admin != authority {
             return Err(Error::Unauthorized);
         }
         env.storage()
@@ -197,156 +407,261 @@ impl CarbonCreditToken {
         is_paused(&env)
     }
 
-    // ── Token operations ──────────────────────────────────────────────────────
+    /// Turns governance mode on or off. While on, every SuperAdmin-only
+    /// entrypoint (including this one, and those of the module contracts)
+    /// accepts only the RBAC contract as caller, i.e. an `InvokeContract`
+    /// proposal that passed the RBAC threshold and timelock. The SuperAdmin
+    /// key alone can switch it on but not back off.
+    pub fn set_governance_mode(env: Env, enabled: bool) -> Result<(), Error> {
+        let authority = read_admin_authority(&env);
+        authority.require_auth();
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        set_governance_mode(&env, enabled);
+        Ok(())
+    }
+
+    pub fn governance_mode(env: Env) -> bool {
+        is_governance_mode(&env)
+    }
+
+    // ── Module entrypoints ────────────────────────────────────────────────────
+
+    /// Credits `amount` newly issued credits of `batch_id` to `to`. Issuance
+    /// module only; it checks the verifier, report, caps and limits first.
+    pub fn issue(env: Env, to: Address, batch_id: u32, amount: i128) -> Result<(), Error> {
+        read_modules(&env).issuance.require_auth();
+        check_nonnegative_amount(amount)?;
+        require_not_paused(&env)?;
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        require_compliant(&env, None, &env.current_contract_address(), &to, amount)?;
+        record_issued(&env, batch_id, amount)?;
+        receive_balance(&env, to.clone(), amount);
+        receive_batch_balance(&env, batch_id, &to, amount);
+        write_total_supply(&env, read_total_supply(&env) + amount);
+
+        MintEvent { to, amount }.publish(&env);
+        Ok(())
+    }
+
+    /// Takes `amount` of `batch_id` back from `holder` after its issuance
+    /// was reversed, ignoring any freeze and the compliance checks. Reversals
+    /// module only.
+    pub fn claw_back(env: Env, holder: Address, batch_id: u32, amount: i128) -> Result<(), Error> {
+        read_modules(&env).reversals.require_auth();
+        check_nonnegative_amount(amount)?;
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        spend_batch_balance(&env, batch_id, &holder, amount)?;
+        seize_balance(&env, holder, amount)?;
+        record_reversed(&env, batch_id, amount);
+        write_total_supply(&env, read_total_supply(&env) - amount);
+        Ok(())
+    }
 
-    pub fn mint(env: Env, verifier: Address, to: Address, amount: i128, report_hash: Bytes) -> Result<(), Error> {
+    /// Burns `amount` of `from`'s credits as a retirement, drawing on
+    /// `batch_id` or, if `None`, on the oldest batches first, and returns
+    /// the batches retired. When `spender` is given, the burn spends its
+    /// retire allowance from `from`. Retirement module only; it authorizes
+    /// `from` or `spender` and records the retirement.
+    pub fn retire_credits(
+        env: Env,
+        spender: Option<Address>,
+        from: Address,
+        batch_id: Option<u32>,
+        amount: i128,
+    ) -> Result<Vec<BatchAmount>, Error> {
+        read_modules(&env).retirement.require_auth();
         check_nonnegative_amount(amount)?;
         require_not_paused(&env)?;
-        require_not_blacklisted(&env, &verifier)?;
-        require_not_blacklisted(&env, &to)?;
 
-        require_verifier(&env, &verifier);
+        let frozen = require_compliant(
+            &env,
+            spender.as_ref(),
+            &from,
+            &env.current_contract_address(),
+            amount,
+        )?;
+        if amount == 0 {
+            return Err(Error::ZeroRetirementAmount);
+        }
 
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 
-        if is_report_hash_used(&env, &report_hash) {
-            return Err(Error::ReportHashUsed);
+        if let Some(spender) = spender {
+            spend_allowance(&env, from.clone(), spender, amount, AllowancePurpose::Retire)?;
         }
-        mark_report_hash_used(&env, &report_hash);
+        let lots = match batch_id {
+            Some(batch_id) => {
+                spend_batch_balance(&env, batch_id, &from, amount)?;
+                spend_balance(&env, from.clone(), amount, frozen)?;
+                let new_supply = read_total_supply(&env) - amount;
+                write_total_supply(&env, new_supply);
+                vec![&env, BatchAmount { batch_id, amount }]
+            }
+            None => burn_credits(&env, &from, amount, frozen)?,
+        };
 
-        receive_balance(&env, to.clone(), amount);
+        record_retired(&env, &lots);
+        for lot in lots.iter() {
+            BatchRetirementEvent {
+                batch_id: lot.batch_id,
+                from: from.clone(),
+                amount: lot.amount,
+            }
+            .publish(&env);
+        }
+        let new_retired = read_total_retired(&env) + amount;
+        write_total_retired(&env, new_retired);
 
-        let new_supply = read_total_supply(&env) + amount;
-        write_total_supply(&env, new_supply);
+        BurnEvent { from, amount }.publish(&env);
+        Ok(lots)
+    }
 
-        MintEvent { to, amount }.publish(&env);
+    /// Moves `amount` from `from` to `to` regardless of freezes and the
+    /// compliance checks. Compliance module only, for `force_transfer`.
+    pub fn force_transfer(env: Env, from: Address, to: Address, amount: i128) -> Result<(), Error> {
+        read_modules(&env).compliance.require_auth();
+        check_nonnegative_amount(amount)?;
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        seize_balance(&env, from.clone(), amount)?;
+        receive_balance(&env, to.clone(), amount);
+        carry_batches(&env, &from, &to, amount);
         Ok(())
     }
 
+    // ── Token operations ──────────────────────────────────────────────────────
 
     /// Transfers tokens between addresses.
     pub fn transfer(env: Env, from: Address, to: Address, amount: i128) -> Result<(), Error> {
         from.require_auth();
         check_nonnegative_amount(amount)?;
         require_not_paused(&env)?;
-        require_not_blacklisted(&env, &from)?;
-        require_not_blacklisted(&env, &to)?;
 
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 
-        spend_balance(&env, from.clone(), amount)?;
-        receive_balance(&env, to.clone(), amount);
+        let frozen = require_compliant(&env, None, &from, &to, amount)?;
+        move_credits(&env, &from, &to, amount, frozen)?;
 
         TransferEvent { from, to, amount }.publish(&env);
         Ok(())
     }
 
-    pub fn transfer_from(
+    /// Transfers to many recipients at once, each entry being `(to, amount)`.
+    /// `from` authorizes once; any failure reverts the whole call.
+    pub fn transfer_batch(
         env: Env,
-        spender: Address,
         from: Address,
-        to: Address,
-        amount: i128,
+        transfers: Vec<(Address, i128)>,
     ) -> Result<(), Error> {
-        spender.require_auth();
-        check_nonnegative_amount(amount)?;
+        from.require_auth();
         require_not_paused(&env)?;
-        require_not_blacklisted(&env, &spender)?;
-        require_not_blacklisted(&env, &from)?;
-        require_not_blacklisted(&env, &to)?;
 
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 
-        spend_allowance(&env, from.clone(), spender, amount)?;
-        spend_balance(&env, from.clone(), amount)?;
-        receive_balance(&env, to.clone(), amount);
-
-        TransferEvent { from, to, amount }.publish(&env);
+        for (to, amount) in transfers.iter() {
+            check_nonnegative_amount(amount)?;
+
+            let frozen = require_compliant(&env, None, &from, &to, amount)?;
+            move_credits(&env, &from, &to, amount, frozen)?;
+            TransferEvent {
+                from: from.clone(),
+                to,
+                amount,
+            }
+            .publish(&env);
+        }
         Ok(())
     }
 
-    pub fn retire(
+    /// Transfers credits of a single batch.
+    pub fn transfer_in_batch(
         env: Env,
         from: Address,
+        to: Address,
+        batch_id: u32,
         amount: i128,
-        report_hash: Bytes,
-        methodology: String,
     ) -> Result<(), Error> {
         from.require_auth();
         check_nonnegative_amount(amount)?;
         require_not_paused(&env)?;
-        require_not_blacklisted(&env, &from)?;
-
-        if amount == 0 {
-            return Err(Error::ZeroRetirementAmount);
-        }
 
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 
-        spend_balance(&env, from.clone(), amount)?;
-
-        let new_supply = read_total_supply(&env) - amount;
-        write_total_supply(&env, new_supply);
-
-        let new_retired = read_total_retired(&env) + amount;
-        write_total_retired(&env, new_retired);
-
-        let timestamp = env.ledger().timestamp();
-
-        let cert_id = increment_certificate_count(&env);
-        let certificate = OffsetCertificate {
-            id: cert_id,
-            amount,
-            timestamp,
-        };
-        write_certificate(&env, from.clone(), certificate);
+        let frozen = require_compliant(&env, None, &from, &to, amount)?;
+        spend_batch_balance(&env, batch_id, &from, amount)?;
+        spend_balance(&env, from.clone(), amount, frozen)?;
+        receive_balance(&env, to.clone(), amount);
+        receive_batch_balance(&env, batch_id, &to, amount);
 
-        RetirementEvent {
+        BatchTransferEvent {
+            batch_id,
             from: from.clone(),
+            to: to.clone(),
             amount,
-            timestamp,
-            report_hash,
-            methodology,
         }
         .publish(&env);
+        TransferEvent { from, to, amount }.publish(&env);
+        Ok(())
+    }
 
-        CertificateGeneratedEvent {
-            certificate_id: cert_id,
-            corporate: from.clone(),
-            amount,
-            timestamp,
-        }
-        .publish(&env);
+    pub fn transfer_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        spender.require_auth();
+        check_nonnegative_amount(amount)?;
+        require_not_paused(&env)?;
 
-        BurnEvent { from, amount }.publish(&env);
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 
+        let frozen = require_compliant(&env, Some(&spender), &from, &to, amount)?;
+        spend_allowance(&env, from.clone(), spender, amount, AllowancePurpose::Transfer)?;
+        move_credits(&env, &from, &to, amount, frozen)?;
+
+        TransferEvent { from, to, amount }.publish(&env);
         Ok(())
     }
 
-
     /// Burns tokens (SEP-41 standard).
     pub fn burn(env: Env, from: Address, amount: i128) -> Result<(), Error> {
         from.require_auth();
         check_nonnegative_amount(amount)?;
         require_not_paused(&env)?;
-        require_not_blacklisted(&env, &from)?;
 
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 
-        spend_balance(&env, from.clone(), amount)?;
-
-        let new_supply = read_total_supply(&env) - amount;
-        write_total_supply(&env, new_supply);
+        let frozen = require_compliant(&env, None, &from, &env.current_contract_address(), amount)?;
+        burn_credits(&env, &from, amount, frozen)?;
 
         BurnEvent { from, amount }.publish(&env);
         Ok(())
@@ -361,18 +676,20 @@ impl CarbonCreditToken {
         spender.require_auth();
         check_nonnegative_amount(amount)?;
         require_not_paused(&env)?;
-        require_not_blacklisted(&env, &spender)?;
-        require_not_blacklisted(&env, &from)?;
 
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 
-        spend_allowance(&env, from.clone(), spender, amount)?;
-        spend_balance(&env, from.clone(), amount)?;
-
-        let new_supply = read_total_supply(&env) - amount;
-        write_total_supply(&env, new_supply);
+        let frozen = require_compliant(
+            &env,
+            Some(&spender),
+            &from,
+            &env.current_contract_address(),
+            amount,
+        )?;
+        spend_allowance(&env, from.clone(), spender, amount, AllowancePurpose::Burn)?;
+        burn_credits(&env, &from, amount, frozen)?;
 
         BurnEvent { from, amount }.publish(&env);
         Ok(())
@@ -384,6 +701,56 @@ impl CarbonCreditToken {
         spender: Address,
         amount: i128,
         expiration_ledger: u32,
+    ) -> Result<(), Error> {
+        Self::approve_with_purpose(
+            env,
+            from,
+            spender,
+            amount,
+            expiration_ledger,
+            AllowancePurpose::Any,
+        )
+    }
+
+    /// Approves an allowance that can only be used for `purpose`, e.g. a
+    /// retire-only allowance that cannot be used with `transfer_from`.
+    pub fn approve_with_purpose(
+        env: Env,
+        from: Address,
+        spender: Address,
+        amount: i128,
+        expiration_ledger: u32,
+        purpose: AllowancePurpose,
+    ) -> Result<(), Error> {
+        from.require_auth();
+        approve_allowance(&env, from, spender, amount, expiration_ledger, purpose)
+    }
+
+    /// Approves an allowance for `from` whose signature the permits module
+    /// has verified. Accepts the permits module only.
+    pub fn approve_by_permit(
+        env: Env,
+        from: Address,
+        spender: Address,
+        amount: i128,
+        expiration_ledger: u32,
+        purpose: AllowancePurpose,
+    ) -> Result<(), Error> {
+        read_modules(&env).permits.require_auth();
+        approve_allowance(&env, from, spender, amount, expiration_ledger, purpose)
+    }
+
+    /// Raises `spender`'s allowance by `amount` instead of overwriting it, so
+    /// a pending `transfer_from` cannot spend both the old and new approval.
+    /// The allowance moves to `expiration_ledger`. `purpose` must match the
+    /// live allowance, if any, and is the purpose of a new one.
+    pub fn increase_allowance(
+        env: Env,
+        from: Address,
+        spender: Address,
+        amount: i128,
+        expiration_ledger: u32,
+        purpose: AllowancePurpose,
     ) -> Result<(), Error> {
         from.require_auth();
         check_nonnegative_amount(amount)?;
@@ -394,30 +761,52 @@ impl CarbonCreditToken {
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 
-        write_allowance(
+        let allowance = increase_allowance(
             &env,
             from.clone(),
             spender.clone(),
             amount,
             expiration_ledger,
+            purpose,
         )?;
 
         ApproveEvent {
             from,
             spender,
-            amount,
-            expiration_ledger,
+            amount: allowance.amount,
+            expiration_ledger: allowance.expiration_ledger,
+            purpose: allowance.purpose,
         }
         .publish(&env);
         Ok(())
     }
 
-    pub fn is_verifier(env: Env, addr: Address) -> bool {
-        is_verifier(&env, &addr)
-    }
+    /// Lowers `spender`'s allowance by `amount`, keeping its expiration and
+    /// purpose. Fails with `InsufficientAllowance` if less is left.
+    pub fn decrease_allowance(
+        env: Env,
+        from: Address,
+        spender: Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        from.require_auth();
+        check_nonnegative_amount(amount)?;
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        let allowance = decrease_allowance(&env, from.clone(), spender.clone(), amount)?;
 
-    pub fn is_blacklisted(env: Env, addr: Address) -> bool {
-        is_blacklisted(&env, &addr)
+        ApproveEvent {
+            from,
+            spender,
+            amount: allowance.amount,
+            expiration_ledger: allowance.expiration_ledger,
+            purpose: allowance.purpose,
+        }
+        .publish(&env);
+        Ok(())
     }
 
     pub fn balance(env: Env, id: Address) -> i128 {
@@ -427,6 +816,35 @@ impl CarbonCreditToken {
         read_balance(&env, id)
     }
 
+    /// Returns the balance `id` holds in a single batch.
+    pub fn batch_balance(env: Env, id: Address, batch_id: u32) -> i128 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        read_batch_balance(&env, batch_id, id)
+    }
+
+    /// Returns the per-batch breakdown of `id`'s balance, oldest batch first.
+    pub fn batches_of(env: Env, id: Address) -> Vec<BatchAmount> {
+        let mut holdings = Vec::new(&env);
+        for batch_id in read_holder_batches(&env, &id).iter() {
+            holdings.push_back(BatchAmount {
+                batch_id,
+                amount: read_batch_balance(&env, batch_id, id.clone()),
+            });
+        }
+        holdings
+    }
+
+    pub fn get_batch(env: Env, batch_id: u32) -> Option<CreditBatch> {
+        read_batch(&env, batch_id)
+    }
+
+    /// Looks up the batch id registered for a project and vintage.
+    pub fn find_batch(env: Env, project_name: String, vintage: String) -> Option<u32> {
+        read_batch_id(&env, project_name, vintage)
+    }
+
     pub fn allowance(env: Env, from: Address, spender: Address) -> i128 {
         env.storage()
             .instance()
@@ -434,6 +852,16 @@ impl CarbonCreditToken {
         read_allowance(&env, from, spender)
     }
 
+    /// Returns the spenders `owner` has a live, non-zero allowance with.
+    pub fn spenders_of(env: Env, owner: Address) -> Vec<Address> {
+        read_spenders(&env, &owner)
+    }
+
+    /// Returns the purpose the current allowance was granted for.
+    pub fn allowance_purpose(env: Env, from: Address, spender: Address) -> AllowancePurpose {
+        read_allowance_purpose(&env, from, spender)
+    }
+
     pub fn total_supply(env: Env) -> i128 {
         read_total_supply(&env)
     }
@@ -443,7 +871,7 @@ impl CarbonCreditToken {
     }
 
     pub fn rbac_contract(env: Env) -> Address {
-        crate::storage::read_rbac_contract(&env)
+        read_rbac_contract(&env)
     }
 
     pub fn name(env: Env) -> String {
@@ -462,13 +890,7 @@ impl CarbonCreditToken {
         read_administrator(&env)
     }
 
-    /// Returns the list of certificates for a corporate address.
-    pub fn get_certificates(env: Env, corporate: Address) -> soroban_sdk::Vec<OffsetCertificate> {
-        read_certificates(&env, corporate)
+    pub fn super_admin(env: Env) -> Address {
+        read_super_admin(&env)
     }
-
-    /// Returns the total number of certificates issued globally.
-    pub fn get_certificate_count(env: Env) -> u64 {
-        read_certificate_count(&env)
-    }
-}
\ No newline at end of file
+}
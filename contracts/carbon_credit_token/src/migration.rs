@@ -0,0 +1,354 @@
+use soroban_sdk::{
+    contract, contractclient, contractimpl, contracttype, vec, Address, Bytes, BytesN, Env,
+    String, Vec,
+};
+
+use crate::admin::read_super_admin;
+use crate::allowance::write_allowance;
+use crate::balance::read_balance;
+use crate::batch::{
+    create_batch, read_batch, read_batch_balance, read_holder_batches, receive_batch_balance,
+    record_issued, record_retired, BatchAmount, CreditBatch, DEFAULT_BATCH_ID,
+};
+use crate::error::{forward, Error};
+use crate::events::{BatchCreatedEvent, MigrateEvent, UpgradeEvent};
+use crate::modules::{write_modules, Modules};
+use crate::read_admin_authority;
+use crate::storage::{
+    is_governance_mode, read_rbac_contract, read_schema_version, read_total_retired,
+    read_total_supply, write_schema_version, AllowanceDataKey, AllowancePurpose,
+    CURRENT_SCHEMA_VERSION, INSTANCE_BUMP_AMOUNT, INSTANCE_LIFETIME_THRESHOLD,
+};
+
+/// Keys written by unversioned deployments that the current code no longer
+/// reads. They encode the same as the `DataKey` variants of that release.
+#[derive(Clone)]
+#[contracttype]
+enum LegacyDataKey {
+    Verifier(Address),
+    Blacklisted(Address),
+    UsedReportHash(Bytes),
+    Allowance(AllowanceDataKey),
+    CertificateCount,
+    Certificates(Address),
+}
+
+#[derive(Clone)]
+#[contracttype]
+struct LegacyAllowanceValue {
+    amount: i128,
+    expiration_ledger: u32,
+}
+
+#[derive(Clone)]
+#[contracttype]
+struct LegacyCertificate {
+    id: u64,
+    amount: i128,
+    timestamp: u64,
+}
+
+/// The module entrypoints `migrate` hands legacy entries to. Each accepts
+/// calls from the token only.
+#[contractclient(name = "ComplianceImportClient")]
+#[allow(dead_code)]
+trait ComplianceImport {
+    fn import_blacklist(env: Env, accounts: Vec<Address>) -> Result<(), Error>;
+}
+
+#[contractclient(name = "IssuanceImportClient")]
+#[allow(dead_code)]
+trait IssuanceImport {
+    fn import_report_hashes(env: Env, report_hashes: Vec<Bytes>) -> Result<(), Error>;
+}
+
+#[contractclient(name = "RetirementImportClient")]
+#[allow(dead_code)]
+trait RetirementImport {
+    fn import_certificates(
+        env: Env,
+        batch: CreditBatch,
+        next_id: u32,
+        certificates: Vec<(Address, u64, i128, u64)>,
+    ) -> Result<(), Error>;
+}
+
+/// Legacy entries taken out of the token's storage that now belong to a
+/// module contract, collected so each module is called once.
+struct LegacyEntries {
+    /// Number of entries migrated so far, including those below.
+    moved: u32,
+    blacklisted: Vec<Address>,
+    report_hashes: Vec<Bytes>,
+    certificates: Vec<(Address, u64, i128, u64)>,
+    certificate_count: Option<u32>,
+}
+
+impl LegacyEntries {
+    fn new(e: &Env) -> Self {
+        LegacyEntries {
+            moved: 0,
+            blacklisted: Vec::new(e),
+            report_hashes: Vec::new(e),
+            certificates: Vec::new(e),
+            certificate_count: None,
+        }
+    }
+}
+
+/// Removes a legacy instance flag. Returns `false` if there was none, so
+/// repeated runs are no-ops.
+fn take_flag(e: &Env, key: &LegacyDataKey) -> bool {
+    if !e.storage().instance().has(key) {
+        return false;
+    }
+    e.storage().instance().remove(key);
+    true
+}
+
+/// Files the part of `addr`'s balance that is not held in any batch under
+/// `default_batch`, which every mint went to before batches existed.
+fn migrate_unbatched_balance(e: &Env, addr: &Address, default_batch: &CreditBatch) -> bool {
+    let mut batched = 0;
+    for batch_id in read_holder_batches(e, addr).iter() {
+        batched += read_batch_balance(e, batch_id, addr.clone());
+    }
+    let unbatched = read_balance(e, addr.clone()) - batched;
+    if unbatched <= 0 {
+        return false;
+    }
+    receive_batch_balance(e, default_batch.id, addr, unbatched);
+    true
+}
+
+/// Migrates `addr`'s legacy entries: the unbatched balance is filed under
+/// `default_batch`, the blacklist flag and certificates are collected for
+/// their modules, and the verifier flag is dropped, as verifier roles live
+/// in the RBAC contract.
+fn migrate_account(
+    e: &Env,
+    addr: &Address,
+    default_batch: &CreditBatch,
+    legacy: &mut LegacyEntries,
+) {
+    if migrate_unbatched_balance(e, addr, default_batch) {
+        legacy.moved += 1;
+    }
+    if take_flag(e, &LegacyDataKey::Verifier(addr.clone())) {
+        legacy.moved += 1;
+    }
+    if take_flag(e, &LegacyDataKey::Blacklisted(addr.clone())) {
+        legacy.blacklisted.push_back(addr.clone());
+        legacy.moved += 1;
+    }
+
+    let key = LegacyDataKey::Certificates(addr.clone());
+    let certificates: Option<Vec<LegacyCertificate>> = e.storage().persistent().get(&key);
+    if let Some(certificates) = certificates {
+        for cert in certificates.iter() {
+            legacy
+                .certificates
+                .push_back((addr.clone(), cert.id, cert.amount, cert.timestamp));
+        }
+        legacy.moved += certificates.len();
+        e.storage().persistent().remove(&key);
+    }
+}
+
+/// Collects a legacy used report hash for the issuance module.
+fn migrate_report_hash(e: &Env, hash: &Bytes, legacy: &mut LegacyEntries) {
+    if take_flag(e, &LegacyDataKey::UsedReportHash(hash.clone())) {
+        legacy.report_hashes.push_back(hash.clone());
+        legacy.moved += 1;
+    }
+}
+
+/// Collects the legacy certificate counter, so the retirement module does
+/// not reuse migrated certificate ids.
+fn migrate_certificate_count(e: &Env, legacy: &mut LegacyEntries) {
+    let key = LegacyDataKey::CertificateCount;
+    let count: Option<u64> = e.storage().instance().get(&key);
+    if let Some(count) = count {
+        legacy.certificate_count = Some(count as u32);
+        legacy.moved += 1;
+        e.storage().instance().remove(&key);
+    }
+}
+
+/// Rewrites a legacy allowance with purpose `Any`, the only kind unversioned
+/// deployments granted. Expired or spent allowances are dropped. Returns the
+/// number of entries moved.
+fn migrate_allowance(e: &Env, key: &AllowanceDataKey) -> Result<u32, Error> {
+    let legacy_key = LegacyDataKey::Allowance(key.clone());
+    let legacy: Option<LegacyAllowanceValue> = e.storage().temporary().get(&legacy_key);
+    let Some(legacy) = legacy else {
+        return Ok(0);
+    };
+    e.storage().temporary().remove(&legacy_key);
+    if legacy.amount > 0 && legacy.expiration_ledger >= e.ledger().sequence() {
+        write_allowance(
+            e,
+            key.from.clone(),
+            key.spender.clone(),
+            legacy.amount,
+            legacy.expiration_ledger,
+            AllowancePurpose::Any,
+        )?;
+    }
+    Ok(1)
+}
+
+/// Hands the collected entries to their modules: blacklist flags to the
+/// compliance module, report hashes to the issuance module and certificates,
+/// attributed to `default_batch`, to the retirement module. Modules with
+/// nothing to import are not called.
+fn import_legacy_entries(
+    e: &Env,
+    modules: &Modules,
+    default_batch: &CreditBatch,
+    legacy: &LegacyEntries,
+) -> Result<(), Error> {
+    if !legacy.blacklisted.is_empty() {
+        let client = ComplianceImportClient::new(e, &modules.compliance);
+        forward(client.try_import_blacklist(&legacy.blacklisted))?;
+    }
+    if !legacy.report_hashes.is_empty() {
+        let client = IssuanceImportClient::new(e, &modules.issuance);
+        forward(client.try_import_report_hashes(&legacy.report_hashes))?;
+    }
+    if !legacy.certificates.is_empty() || legacy.certificate_count.is_some() {
+        let client = RetirementImportClient::new(e, &modules.retirement);
+        forward(client.try_import_certificates(
+            default_batch,
+            &legacy.certificate_count.unwrap_or(0),
+            &legacy.certificates,
+        ))?;
+    }
+    Ok(())
+}
+
+/// Migration build of the token, deployed with the `migrator` feature. The
+/// regular build leaves the legacy layout out to stay within the wasm size
+/// limit, so a first-release deployment is upgraded to this code, migrated,
+/// and then upgraded to the regular build.
+#[contract]
+pub struct TokenMigrator;
+
+#[contractimpl]
+impl TokenMigrator {
+    /// Replaces the contract code with the uploaded wasm `new_wasm_hash`,
+    /// normally the regular token build once `migrate` is done.
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        let authority = read_admin_authority(&env);
+        authority.require_auth();
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        env.deployer()
+            .update_current_contract_wasm(new_wasm_hash.clone());
+        UpgradeEvent { new_wasm_hash }.publish(&env);
+        Ok(())
+    }
+
+    /// Brings a first-release deployment to the current layout: registers
+    /// the module contracts, creates the default batch from the project
+    /// metadata if there is none, and moves the legacy entries of `accounts`
+    /// (unbatched balance, blacklist flags, certificates), `report_hashes`
+    /// and `allowances`. Entries the modules now keep are handed to them;
+    /// legacy verifier flags are dropped, as verifier roles live in the RBAC
+    /// contract. Legacy keys cannot be enumerated, so the caller supplies
+    /// them; the call is idempotent and can be repeated in chunks. Returns
+    /// the number of entries moved.
+    #[allow(clippy::too_many_arguments)]
+    pub fn migrate(
+        env: Env,
+        modules: Modules,
+        project_name: String,
+        vintage: String,
+        location: String,
+        metadata_url: String,
+        accounts: Vec<Address>,
+        report_hashes: Vec<Bytes>,
+        allowances: Vec<AllowanceDataKey>,
+    ) -> Result<u32, Error> {
+        let authority = read_admin_authority(&env);
+        authority.require_auth();
+
+        let from_version = read_schema_version(&env);
+        if from_version != 0 && from_version != CURRENT_SCHEMA_VERSION {
+            return Err(Error::SchemaNotMigratable);
+        }
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        write_modules(&env, &modules);
+
+        let default_batch = match read_batch(&env, DEFAULT_BATCH_ID) {
+            Some(batch) => batch,
+            None => {
+                let batch_id = create_batch(&env, project_name.clone(), vintage.clone(), location, metadata_url)?;
+                // Everything issued before batches existed came from this project.
+                let retired = read_total_retired(&env);
+                record_issued(&env, batch_id, read_total_supply(&env) + retired)?;
+                record_retired(&env, &vec![&env, BatchAmount { batch_id, amount: retired }]);
+                BatchCreatedEvent {
+                    batch_id,
+                    project_name,
+                    vintage,
+                }
+                .publish(&env);
+                read_batch(&env, batch_id).ok_or(Error::BatchNotFound)?
+            }
+        };
+
+        let mut legacy = LegacyEntries::new(&env);
+        migrate_certificate_count(&env, &mut legacy);
+        for account in accounts.iter() {
+            migrate_account(&env, &account, &default_batch, &mut legacy);
+        }
+        for hash in report_hashes.iter() {
+            migrate_report_hash(&env, &hash, &mut legacy);
+        }
+        import_legacy_entries(&env, &modules, &default_batch, &legacy)?;
+
+        let mut moved = legacy.moved;
+        for key in allowances.iter() {
+            moved += migrate_allowance(&env, &key)?;
+        }
+
+        write_schema_version(&env, CURRENT_SCHEMA_VERSION);
+        MigrateEvent {
+            from_version,
+            to_version: CURRENT_SCHEMA_VERSION,
+            moved,
+        }
+        .publish(&env);
+        Ok(moved)
+    }
+
+    /// Returns the storage layout version; `0` for deployments that have not
+    /// been migrated since versioning was introduced.
+    pub fn schema_version(env: Env) -> u32 {
+        read_schema_version(&env)
+    }
+
+    // The module contracts are initialized against this code, before
+    // `migrate` hands them the legacy entries; their `initialize` reads the
+    // admin authority through these.
+
+    pub fn super_admin(env: Env) -> Address {
+        read_super_admin(&env)
+    }
+
+    pub fn rbac_contract(env: Env) -> Address {
+        read_rbac_contract(&env)
+    }
+
+    pub fn governance_mode(env: Env) -> bool {
+        is_governance_mode(&env)
+    }
+}
@@ -0,0 +1,35 @@
+use soroban_sdk::{contracttype, Address, Env};
+
+use crate::storage::DataKey;
+
+/// The contracts the token delegates to. Each is deployed on its own and
+/// initialized with the token's address; only these may call the module
+/// entrypoints (`issue`, `claw_back`, `retire_credits`, `force_transfer`,
+/// `approve_by_permit`).
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Modules {
+    /// Mints against verifier reports. It may also pause the token when its
+    /// circuit breaker trips.
+    pub issuance: Address,
+    /// Retires credits and keeps the retirement ledger and certificates.
+    pub retirement: Address,
+    /// Checks every movement of credits and moves them out of restricted
+    /// accounts.
+    pub compliance: Address,
+    /// Verifies signed approvals and writes them with `approve_by_permit`.
+    pub permits: Address,
+    /// Claws back credits of reversed issuances.
+    pub reversals: Address,
+}
+
+pub fn read_modules(e: &Env) -> Modules {
+    e.storage()
+        .instance()
+        .get(&DataKey::Modules)
+        .expect("modules not set: was initialize() called?")
+}
+
+pub fn write_modules(e: &Env, modules: &Modules) {
+    e.storage().instance().set(&DataKey::Modules, modules);
+}
@@ -1,13 +1,6 @@
-use soroban_sdk::{contracttype, Address, Bytes, Env, Vec};
-
-#[derive(Clone, Debug, PartialEq)]
-#[contracttype]
-pub struct OffsetCertificate {
-    pub id: u64,
-    pub amount: i128,
-    pub timestamp: u64,
-}
+use soroban_sdk::{contracttype, Address, Env, String};
 
+use crate::batch::{BatchBalanceKey, BatchKey};
 
 // ── TTL Constants ──────────────────────────────────────────────────────────────
 pub const INSTANCE_LIFETIME_THRESHOLD: u32 = 17280; // ~1 day
@@ -24,13 +17,41 @@ pub struct AllowanceDataKey {
     pub spender: Address,
 }
 
+/// What an allowance may be used for. `Any` is what plain SEP-41 `approve`
+/// grants; the others restrict the spender to a single entrypoint family.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+#[repr(u32)]
+pub enum AllowancePurpose {
+    Any = 0,
+    Transfer = 1,
+    Burn = 2,
+    Retire = 3,
+}
+
 #[derive(Clone)]
 #[contracttype]
 pub struct AllowanceValue {
     pub amount: i128,
     pub expiration_ledger: u32,
+    pub purpose: AllowancePurpose,
 }
 
+// ── Storage Layout ─────────────────────────────────────────────────────────────
+/// Storage layout written by this code.
+///
+/// * `0`: unversioned deployments, which kept verifier and blacklist flags
+///   and used report hashes in instance storage, held balances outside of
+///   any batch, and stored allowances without a purpose and certificates as
+///   one list per retiree.
+/// * `1`, `2`: single-contract layouts that kept issuance, retirement,
+///   compliance and permit state in the token. `migrate` cannot carry that
+///   state over to the module contracts.
+/// * `3`: every balance is held in a batch and allowances carry a purpose;
+///   issuance, retirement, compliance and permit state lives in the module
+///   contracts.
+pub const CURRENT_SCHEMA_VERSION: u32 = 3;
+
 // ── Storage Keys ───────────────────────────────────────────────────────────────
 #[derive(Clone)]
 #[contracttype]
@@ -39,12 +60,14 @@ pub enum DataKey {
     RbacContract,
     Admin,
     SuperAdmin,
-    Verifier(Address),
-    Blacklisted(Address),
+    PendingSuperAdmin,
 
     // Ledger/accounting
     Balance(Address),
-    Allowance(AllowanceDataKey),
+    // Allowances of unversioned deployments lived under `Allowance` without
+    // a purpose; see `migration::migrate_allowance`.
+    Approval(AllowanceDataKey),
+    Spenders(Address),
     TotalSupply,
     TotalRetired,
 
@@ -53,19 +76,27 @@ pub enum DataKey {
     Symbol,
     Decimals,
 
-    // Init flag
+    // Project/vintage batches
+    NextBatchId,
+    Batch(u32),
+    BatchIndex(BatchKey),
+    ProjectBatches(String),
+    BatchBalance(BatchBalanceKey),
+    HolderBatches(Address),
+
+    // Init flag and storage layout version
     Initialized,
-    VerifierRegistry,
-    UsedReportHash(Bytes),
+    SchemaVersion,
 
     // Pause flag
     Paused,
 
-    // Offset Certificates
-    CertificateCount,
-    Certificates(Address),
-}
+    // Module contracts the token delegates to
+    Modules,
 
+    // Governance mode: admin calls must come from the RBAC contract
+    GovernanceMode,
+}
 
 // ── Initialization ─────────────────────────────────────────────────────────────
 pub fn is_initialized(e: &Env) -> bool {
@@ -78,8 +109,22 @@ pub fn set_initialized(e: &Env) {
         .set(&DataKey::Initialized, &true);
 }
 
+pub fn read_schema_version(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&DataKey::SchemaVersion)
+        .unwrap_or(0)
+}
+
+pub fn write_schema_version(e: &Env, version: u32) {
+    e.storage()
+        .instance()
+        .set(&DataKey::SchemaVersion, &version);
+}
+
 // ── RBAC Contract ──────────────────────────────────────────────────────────────
-/// Persists the external RBAC contract address used for role-based minting checks.
+/// Persists the external RBAC contract address. The module contracts check
+/// roles against it, and governance mode routes admin calls through it.
 pub fn write_rbac_contract(e: &Env, rbac_id: &Address) {
     e.storage()
         .instance()
@@ -96,68 +141,6 @@ pub fn read_rbac_contract(e: &Env) -> Address {
         .expect("rbac contract address not set: was initialize() called?")
 }
 
-// ── Administrator ──────────────────────────────────────────────────────────────
-pub fn read_administrator(e: &Env) -> Address {
-    e.storage()
-        .instance()
-        .get(&DataKey::Admin)
-        .expect("administrator not set")
-}
-
-pub fn write_administrator(e: &Env, admin: &Address) {
-    e.storage().instance().set(&DataKey::Admin, admin);
-}
-
-pub fn read_super_admin(e: &Env) -> Address {
-    e.storage()
-        .instance()
-        .get(&DataKey::SuperAdmin)
-        .expect("super admin not set")
-}
-
-pub fn write_super_admin(e: &Env, admin: &Address) {
-    e.storage().instance().set(&DataKey::SuperAdmin, admin);
-}
-
-// ── Verifier / Blacklist (inline RBAC) ────────────────────────────────────────
-pub fn grant_verifier(e: &Env, verifier: &Address) {
-    e.storage()
-        .instance()
-        .set(&DataKey::Verifier(verifier.clone()), &true);
-}
-
-pub fn revoke_verifier(e: &Env, verifier: &Address) {
-    e.storage()
-        .instance()
-        .remove(&DataKey::Verifier(verifier.clone()));
-}
-
-pub fn is_verifier(e: &Env, addr: &Address) -> bool {
-    e.storage()
-        .instance()
-        .get::<DataKey, bool>(&DataKey::Verifier(addr.clone()))
-        .unwrap_or(false)
-}
-
-pub fn blacklist_address(e: &Env, addr: &Address) {
-    e.storage()
-        .instance()
-        .set(&DataKey::Blacklisted(addr.clone()), &true);
-}
-
-pub fn unblacklist_address(e: &Env, addr: &Address) {
-    e.storage()
-        .instance()
-        .remove(&DataKey::Blacklisted(addr.clone()));
-}
-
-pub fn is_blacklisted(e: &Env, addr: &Address) -> bool {
-    e.storage()
-        .instance()
-        .get::<DataKey, bool>(&DataKey::Blacklisted(addr.clone()))
-        .unwrap_or(false)
-}
-
 // ── Supply Accounting ──────────────────────────────────────────────────────────
 pub fn read_total_supply(e: &Env) -> i128 {
     e.storage()
@@ -185,76 +168,29 @@ pub fn write_total_retired(e: &Env, amount: i128) {
         .set(&DataKey::TotalRetired, &amount);
 }
 
-pub fn read_verifier_registry(e: &Env) -> Address {
-    e.storage()
-        .instance()
-        .get(&DataKey::VerifierRegistry)
-        .expect("verifier registry address not set")
-}
-
-pub fn write_verifier_registry(e: &Env, registry: &Address) {
-    e.storage()
-        .instance()
-        .set(&DataKey::VerifierRegistry, registry);
-}
-
-pub fn is_report_hash_used(e: &Env, hash: &Bytes) -> bool {
+// ── Pause ──────────────────────────────────────────────────────────────────────
+pub fn is_paused(e: &Env) -> bool {
     e.storage()
         .instance()
-        .has(&DataKey::UsedReportHash(hash.clone()))
+        .get::<DataKey, bool>(&DataKey::Paused)
+        .unwrap_or(false)
 }
 
-pub fn mark_report_hash_used(e: &Env, hash: &Bytes) {
-    e.storage()
-        .instance()
-        .set(&DataKey::UsedReportHash(hash.clone()), &true);
-}
-// ── Offset Certificates ────────────────────────────────────────────────────────
-pub fn read_certificate_count(e: &Env) -> u64 {
-    e.storage()
-        .instance()
-        .get(&DataKey::CertificateCount)
-        .unwrap_or(0)
+pub fn set_paused(e: &Env, paused: bool) {
+    e.storage().instance().set(&DataKey::Paused, &paused);
 }
 
-pub fn increment_certificate_count(e: &Env) -> u64 {
-    let count = read_certificate_count(e) + 1;
+// ── Governance mode ───────────────────────────────────────────────────────────
+pub fn is_governance_mode(e: &Env) -> bool {
     e.storage()
         .instance()
-        .set(&DataKey::CertificateCount, &count);
-    count
-}
-
-pub fn read_certificates(e: &Env, corporate: Address) -> Vec<OffsetCertificate> {
-    e.storage()
-        .persistent()
-        .get(&DataKey::Certificates(corporate))
-        .unwrap_or_else(|| Vec::new(e))
-}
-
-pub fn write_certificate(e: &Env, corporate: Address, cert: OffsetCertificate) {
-    let mut certs = read_certificates(e, corporate.clone());
-    certs.push_back(cert);
-    e.storage()
-        .persistent()
-        .set(&DataKey::Certificates(corporate.clone()), &certs);
-
-
-    // Bump TTL for persistent storage
-    e.storage()
-        .persistent()
-        .extend_ttl(&DataKey::Certificates(corporate.clone()), 17280, 518400);
+        .get::<DataKey, bool>(&DataKey::GovernanceMode)
+        .unwrap_or(false)
 }
 
-// ── Pause ──────────────────────────────────────────────────────────────────────
-pub fn is_paused(e: &Env) -> bool {
+pub fn set_governance_mode(e: &Env, enabled: bool) {
     e.storage()
         .instance()
-        .get::<DataKey, bool>(&DataKey::Paused)
-        .unwrap_or(false)
-}
-
-pub fn set_paused(e: &Env, paused: bool) {
-    e.storage().instance().set(&DataKey::Paused, &paused);
+        .set(&DataKey::GovernanceMode, &enabled);
 }
 
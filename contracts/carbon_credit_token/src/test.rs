@@ -1,8 +1,18 @@
 #![cfg(test)]
 
-use crate::{CarbonCreditToken, CarbonCreditTokenClient};
+use crate::admin::PendingSuperAdmin;
+use crate::batch::DEFAULT_BATCH_ID;
+use crate::error::Error;
+use crate::modules::Modules;
+use crate::storage::{AllowanceDataKey, AllowancePurpose};
+use crate::{CarbonCreditToken, CarbonCreditTokenClient, TokenMigratorClient};
+use credit_compliance::{CreditCompliance, CreditComplianceClient};
+use credit_issuance::{CreditIssuance, CreditIssuanceClient};
+use credit_retirement::{CreditRetirement, CreditRetirementClient};
 use soroban_sdk::{
-    contract, contractimpl, testutils::Address as _, Address, Bytes, Env, String,
+    contract, contractimpl,
+    testutils::{Address as _, Events as _, Ledger},
+    vec, Address, Bytes, Env, IntoVal, String, Symbol,
 };
 
 #[contract]
@@ -10,23 +20,65 @@ pub struct MockRbacContract;
 
 #[contractimpl]
 impl MockRbacContract {
-    pub fn has_role(_env: Env, _address: Address, _role: String) -> bool {
-        true // Simplest mock: everyone has every role
+    pub fn has_role(env: Env, address: Address, role: String) -> bool {
+        // Simplest mock: everyone has every role unless denied
+        !env.storage().instance().has(&address)
+            && !env.storage().instance().has(&(address, role))
+    }
+
+    pub fn get_role(env: Env, address: Address) -> u32 {
+        // Everyone is a Trader unless denied
+        if env.storage().instance().has(&address) {
+            255
+        } else {
+            2
+        }
     }
 }
 
-fn setup_env<'a>() -> (Env, CarbonCreditTokenClient<'a>, Address, Address, Address) {
+#[contract]
+pub struct MockVerifierRegistry;
+
+#[contractimpl]
+impl MockVerifierRegistry {
+    pub fn submit_report_hash(env: Env, verifier: Address, farmer: Address, metric_hash: String) {
+        let report = (verifier, farmer, env.ledger().sequence());
+        env.storage().instance().set(&metric_hash, &report);
+    }
+
+    pub fn is_verifier_active(_env: Env, _verifier: Address) -> bool {
+        true
+    }
+
+    pub fn get_report(env: Env, metric_hash: String) -> Option<(Address, Address, u32)> {
+        env.storage().instance().get(&metric_hash)
+    }
+}
+
+/// Registers a token wired to a real compliance module, initializes both and
+/// returns `(env, token, admin, user)`. The other modules are bare
+/// addresses: with every auth mocked, credits are issued by calling the
+/// module entrypoints directly, see `issue`.
+fn setup_env<'a>() -> (Env, CarbonCreditTokenClient<'a>, Address, Address) {
     let env = Env::default();
     env.mock_all_auths();
 
     let admin = Address::generate(&env);
     let rbac_id = env.register_contract(None, MockRbacContract);
+    let compliance_id = env.register_contract(None, CreditCompliance);
     let token_id = env.register_contract(None, CarbonCreditToken);
     let client = CarbonCreditTokenClient::new(&env, &token_id);
 
     client.initialize(
         &admin,
         &rbac_id,
+        &Modules {
+            issuance: Address::generate(&env),
+            retirement: Address::generate(&env),
+            compliance: compliance_id.clone(),
+            permits: Address::generate(&env),
+            reversals: Address::generate(&env),
+        },
         &String::from_str(&env, "Carbon Credit Token"),
         &String::from_str(&env, "CCT"),
         &0u32,
@@ -35,166 +87,618 @@ fn setup_env<'a>() -> (Env, CarbonCreditTokenClient<'a>, Address, Address, Addre
         &String::from_str(&env, "Brazil"),
         &String::from_str(&env, "https://farmcredit.xyz/amazon-1"),
     );
+    CreditComplianceClient::new(&env, &compliance_id).initialize(&token_id);
 
-    let verifier = Address::generate(&env);
     let user = Address::generate(&env);
 
-    (env, client, admin, verifier, user)
+    (env, client, admin, user)
+}
+
+fn compliance_of<'a>(token: &CarbonCreditTokenClient) -> CreditComplianceClient<'a> {
+    CreditComplianceClient::new(&token.env, &token.modules().compliance)
+}
+
+/// Issues `amount` credits of `batch_id` to `to` as the issuance module.
+fn issue(token: &CarbonCreditTokenClient, to: &Address, batch_id: u32, amount: i128) {
+    token.issue(to, &batch_id, &amount);
 }
 
 #[test]
 fn test_initialize() {
-    let (_, token, _, _, _) = setup_env();
+    let (_, token, admin, _) = setup_env();
 
     assert_eq!(token.name(), String::from_str(&token.env, "Carbon Credit Token"));
     assert_eq!(token.symbol(), String::from_str(&token.env, "CCT"));
     assert_eq!(token.decimals(), 0u32);
     assert_eq!(token.total_supply(), 0i128);
     assert_eq!(token.total_retired(), 0i128);
+    assert_eq!(token.super_admin(), admin);
 }
 
 #[test]
-fn test_retire_and_certificate_issuance() {
-    let (env, token, _, verifier, user) = setup_env();
+fn test_initialize_twice_rejected() {
+    let (env, token, admin, _) = setup_env();
 
-    let hash1 = Bytes::from_slice(&env, b"report_hash_1");
-    let hash2 = Bytes::from_slice(&env, b"report_hash_2");
-    let methodology = String::from_str(&env, "VCS");
+    let result = token.try_initialize(
+        &admin,
+        &token.rbac_contract(),
+        &token.modules(),
+        &String::from_str(&env, "Carbon Credit Token"),
+        &String::from_str(&env, "CCT"),
+        &0u32,
+        &String::from_str(&env, "Amazon Reforestation"),
+        &String::from_str(&env, "2023"),
+        &String::from_str(&env, "Brazil"),
+        &String::from_str(&env, "https://farmcredit.xyz/amazon-1"),
+    );
+    assert_eq!(result, Err(Ok(Error::AlreadyInitialized)));
+}
 
-    // Mint some tokens
-    token.mint(&verifier, &user, &1000, &hash1);
-    assert_eq!(token.balance(&user), 1000);
+// ── Project/vintage batches ───────────────────────────────────────────────────
 
-    // Retire some tokens
-    token.retire(&user, &300, &hash2, &methodology);
+fn create_agroforestry_batch(env: &Env, token: &CarbonCreditTokenClient) -> u32 {
+    token.create_batch(
+        &String::from_str(env, "Kenya Agroforestry"),
+        &String::from_str(env, "2024"),
+        &String::from_str(env, "Kenya"),
+        &String::from_str(env, "https://farmcredit.xyz/kenya-1"),
+    )
+}
 
-    assert_eq!(token.balance(&user), 700);
-    assert_eq!(token.total_retired(), 300);
+#[test]
+fn test_initialize_creates_default_batch() {
+    let (env, token, _, _) = setup_env();
+
+    let batch = token.get_batch(&1).unwrap();
+    assert_eq!(batch.project_name, String::from_str(&env, "Amazon Reforestation"));
+    assert_eq!(batch.vintage, String::from_str(&env, "2023"));
+    assert_eq!(batch.issued, 0);
+    assert_eq!(
+        token.find_batch(
+            &String::from_str(&env, "Amazon Reforestation"),
+            &String::from_str(&env, "2023")
+        ),
+        Some(1)
+    );
+}
 
-    // Verify NFT creation
-    assert_eq!(token.certificate_count(), 1);
-    let cert = token.get_certificate(&1).unwrap();
-    assert_eq!(cert.owner, user);
-    assert_eq!(cert.amount, 300);
-    assert_eq!(cert.project_name, String::from_str(&env, "Amazon Reforestation"));
-    assert_eq!(cert.vintage, String::from_str(&env, "2023"));
-    assert_eq!(cert.location, String::from_str(&env, "Brazil"));
-    assert_eq!(cert.metadata_url, String::from_str(&env, "https://farmcredit.xyz/amazon-1"));
+#[test]
+fn test_create_batch_rejects_duplicate_project_vintage() {
+    let (env, token, _, _) = setup_env();
+
+    assert_eq!(create_agroforestry_batch(&env, &token), 2);
+    let result = token.try_create_batch(
+        &String::from_str(&env, "Kenya Agroforestry"),
+        &String::from_str(&env, "2024"),
+        &String::from_str(&env, "Kenya"),
+        &String::from_str(&env, "https://farmcredit.xyz/kenya-1"),
+    );
+    assert_eq!(result, Err(Ok(Error::BatchAlreadyExists)));
 }
 
 #[test]
-fn test_multiple_retirements() {
-    let (env, token, _, verifier, user) = setup_env();
+fn test_issue_tracks_per_batch_balance() {
+    let (env, token, _, user) = setup_env();
+    let batch_id = create_agroforestry_batch(&env, &token);
 
-    let hash1 = Bytes::from_slice(&env, b"h1");
-    let hash2 = Bytes::from_slice(&env, b"h2");
-    let hash3 = Bytes::from_slice(&env, b"h3");
-    let methodology = String::from_str(&env, "VCS");
+    issue(&token, &user, DEFAULT_BATCH_ID, 100);
+    issue(&token, &user, batch_id, 250);
 
-    token.mint(&verifier, &user, &1000, &hash1);
-    
-    token.retire(&user, &100, &hash2, &methodology);
-    token.retire(&user, &200, &hash3, &methodology);
+    assert_eq!(token.balance(&user), 350);
+    assert_eq!(token.total_supply(), 350);
+    assert_eq!(token.batch_balance(&user, &1), 100);
+    assert_eq!(token.batch_balance(&user, &batch_id), 250);
+    assert_eq!(token.get_batch(&batch_id).unwrap().issued, 250);
 
-    assert_eq!(token.certificate_count(), 2);
-    assert_eq!(token.get_certificate(&1).unwrap().amount, 100);
-    assert_eq!(token.get_certificate(&2).unwrap().amount, 200);
-    assert_eq!(token.total_retired(), 300);
+    let result = token.try_issue(&user, &99, &10);
+    assert_eq!(result, Err(Ok(Error::BatchNotFound)));
 }
 
-// ── Pause / unpause ───────────────────────────────────────────────────────────
+#[test]
+fn test_transfer_draws_oldest_batch_first() {
+    let (env, token, _, user) = setup_env();
+    let other = Address::generate(&env);
+    let batch_id = create_agroforestry_batch(&env, &token);
+
+    issue(&token, &user, DEFAULT_BATCH_ID, 100);
+    issue(&token, &user, batch_id, 100);
+
+    token.transfer(&user, &other, &150);
+
+    assert_eq!(token.batch_balance(&user, &1), 0);
+    assert_eq!(token.batch_balance(&user, &batch_id), 50);
+    assert_eq!(token.batch_balance(&other, &1), 100);
+    assert_eq!(token.batch_balance(&other, &batch_id), 50);
+    assert_eq!(token.batches_of(&user).len(), 1);
+    assert_eq!(token.batches_of(&other).len(), 2);
+}
 
 #[test]
-fn test_paused_initially_false() {
-    let (_env, token, _, _, _) = setup();
-    assert!(!token.paused());
+fn test_transfer_draws_oldest_batch_first_whatever_order_received() {
+    let (env, token, _, user) = setup_env();
+    let other = Address::generate(&env);
+    let batch_id = create_agroforestry_batch(&env, &token);
+
+    // The newer batch arrives first.
+    issue(&token, &user, batch_id, 100);
+    issue(&token, &user, DEFAULT_BATCH_ID, 100);
+
+    let holdings = token.batches_of(&user);
+    assert_eq!(holdings.get(0).unwrap().batch_id, 1);
+    assert_eq!(holdings.get(1).unwrap().batch_id, batch_id);
+
+    token.transfer(&user, &other, &150);
+    assert_eq!(token.batch_balance(&user, &1), 0);
+    assert_eq!(token.batch_balance(&user, &batch_id), 50);
 }
 
 #[test]
-fn test_admin_pause_and_unpause() {
-    let (_env, token, admin, _, _) = setup();
-    token.admin_pause(&admin);
-    assert!(token.paused());
-    token.admin_unpause(&admin);
-    assert!(!token.paused());
+fn test_transfer_in_batch() {
+    let (env, token, _, user) = setup_env();
+    let other = Address::generate(&env);
+    let batch_id = create_agroforestry_batch(&env, &token);
+
+    issue(&token, &user, DEFAULT_BATCH_ID, 100);
+    issue(&token, &user, batch_id, 100);
+
+    token.transfer_in_batch(&user, &other, &batch_id, &40);
+    assert_eq!(token.batch_balance(&user, &1), 100);
+    assert_eq!(token.batch_balance(&user, &batch_id), 60);
+    assert_eq!(token.batch_balance(&other, &batch_id), 40);
+    assert_eq!(token.balance(&other), 40);
+
+    let result = token.try_transfer_in_batch(&other, &user, &1, &1);
+    assert_eq!(result, Err(Ok(Error::InsufficientBalance)));
 }
 
 #[test]
-fn test_pause_emits_event() {
-    let (env, token, admin, _, _) = setup();
-    let before = env.events().all().len();
-    token.admin_pause(&admin);
-    assert!(env.events().all().len() > before);
+fn test_transfer_batch() {
+    let (env, token, _, sender) = setup_env();
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+
+    issue(&token, &sender, DEFAULT_BATCH_ID, 1000);
+    token.transfer_batch(&sender, &vec![&env, (a.clone(), 300i128), (b.clone(), 200i128)]);
+    assert_eq!(token.balance(&sender), 500);
+    assert_eq!(token.balance(&a), 300);
+    assert_eq!(token.balance(&b), 200);
+
+    let transfers = vec![&env, (a.clone(), 300i128), (b.clone(), 300i128)];
+    let result = token.try_transfer_batch(&sender, &transfers);
+    assert_eq!(result, Err(Ok(Error::InsufficientBalance)));
+    assert_eq!(token.balance(&sender), 500);
+    assert_eq!(token.balance(&a), 300);
 }
 
 #[test]
-fn test_unpause_emits_event() {
-    let (env, token, admin, _, _) = setup();
-    token.admin_pause(&admin);
-    let before = env.events().all().len();
-    token.admin_unpause(&admin);
-    assert!(env.events().all().len() > before);
+fn test_transfer_checked_by_compliance_module() {
+    let (env, token, _, user) = setup_env();
+    let other = Address::generate(&env);
+    let compliance = compliance_of(&token);
+
+    issue(&token, &user, DEFAULT_BATCH_ID, 1000);
+    compliance.blacklist(&other);
+    let result = token.try_transfer(&user, &other, &100);
+    assert_eq!(result, Err(Ok(Error::Blacklisted)));
+    let result = token.try_approve(&other, &user, &100, &1000);
+    assert_eq!(result, Err(Ok(Error::Blacklisted)));
+
+    compliance.unblacklist(&other);
+    compliance.freeze_amount(&user, &600, &String::from_str(&env, "court order 42"));
+    let result = token.try_transfer(&user, &other, &401);
+    assert_eq!(result, Err(Ok(Error::BalanceFrozen)));
+    let result = token.try_burn(&user, &401);
+    assert_eq!(result, Err(Ok(Error::BalanceFrozen)));
+    token.transfer(&user, &other, &400);
+    assert_eq!(token.balance(&other), 400);
 }
 
+// ── Module entrypoints ────────────────────────────────────────────────────────
+
 #[test]
-fn test_pause_by_non_admin_returns_error() {
-    let (env, token, _, _, _) = setup();
-    let rando = Address::generate(&env);
-    let result = token.try_admin_pause(&rando);
-    assert!(result.is_err());
+fn test_module_entrypoints_require_their_module() {
+    let (env, token, _, user) = setup_env();
+    let spender = Address::generate(&env);
+    let modules = token.modules();
+
+    token.issue(&user, &DEFAULT_BATCH_ID, &1000);
+    assert_eq!(env.auths()[0].0, modules.issuance);
+
+    token.claw_back(&user, &DEFAULT_BATCH_ID, &100);
+    assert_eq!(env.auths()[0].0, modules.reversals);
+    assert_eq!(token.balance(&user), 900);
+    assert_eq!(token.total_supply(), 900);
+    assert_eq!(token.get_batch(&DEFAULT_BATCH_ID).unwrap().reversed, 100);
+
+    token.approve_by_permit(&user, &spender, &300, &1000, &AllowancePurpose::Retire);
+    assert_eq!(env.auths()[0].0, modules.permits);
+    assert_eq!(token.allowance_purpose(&user, &spender), AllowancePurpose::Retire);
+
+    let lots = token.retire_credits(&Some(spender.clone()), &user, &None, &200);
+    assert_eq!(env.auths()[0].0, modules.retirement);
+    assert_eq!(lots.get(0).unwrap().amount, 200);
+    assert_eq!(token.allowance(&user, &spender), 100);
+    assert_eq!(token.total_retired(), 200);
+    assert_eq!(token.get_batch(&DEFAULT_BATCH_ID).unwrap().retired, 200);
+
+    token.force_transfer(&user, &spender, &100);
+    assert_eq!(env.auths()[0].0, modules.compliance);
+    assert_eq!(token.balance(&spender), 100);
 }
 
 #[test]
-fn test_mint_when_paused_returns_error() {
-    let (_env, token, admin, verifier, user) = setup();
-    token.admin_pause(&admin);
-    let result = token.try_mint(&verifier, &user, &1000);
-    assert_eq!(result, Err(Ok(Error::ContractPaused)));
+fn test_claw_back_ignores_freezes() {
+    let (env, token, _, user) = setup_env();
+
+    issue(&token, &user, DEFAULT_BATCH_ID, 1000);
+    compliance_of(&token).freeze_amount(&user, &1000, &String::from_str(&env, "aml review"));
+    token.claw_back(&user, &DEFAULT_BATCH_ID, &400);
+    assert_eq!(token.balance(&user), 600);
+
+    let result = token.try_claw_back(&user, &DEFAULT_BATCH_ID, &601);
+    assert_eq!(result, Err(Ok(Error::InsufficientBalance)));
 }
 
+// ── Purpose-restricted allowances ─────────────────────────────────────────────
+
 #[test]
-fn test_transfer_when_paused_returns_error() {
-    let (env, token, admin, verifier, user) = setup();
-    token.mint(&verifier, &user, &500);
-    token.admin_pause(&admin);
-    let other = Address::generate(&env);
-    let result = token.try_transfer(&user, &other, &100);
-    assert_eq!(result, Err(Ok(Error::ContractPaused)));
+fn test_retire_only_allowance_cannot_transfer_or_burn() {
+    let (env, token, _, treasury) = setup_env();
+    let custodian = Address::generate(&env);
+
+    issue(&token, &treasury, DEFAULT_BATCH_ID, 1000);
+    token.approve_with_purpose(&treasury, &custodian, &300, &1000, &AllowancePurpose::Retire);
+    assert_eq!(token.allowance_purpose(&treasury, &custodian), AllowancePurpose::Retire);
+
+    let result = token.try_transfer_from(&custodian, &treasury, &custodian, &100);
+    assert_eq!(result, Err(Ok(Error::AllowancePurposeMismatch)));
+    let result = token.try_burn_from(&custodian, &treasury, &100);
+    assert_eq!(result, Err(Ok(Error::AllowancePurposeMismatch)));
+    assert_eq!(token.allowance(&treasury, &custodian), 300);
 }
 
 #[test]
-fn test_burn_when_paused_returns_error() {
-    let (_env, token, admin, verifier, user) = setup();
-    token.mint(&verifier, &user, &500);
-    token.admin_pause(&admin);
-    let result = token.try_burn(&user, &100);
-    assert_eq!(result, Err(Ok(Error::ContractPaused)));
+fn test_unrestricted_approve_allows_every_purpose() {
+    let (env, token, _, treasury) = setup_env();
+    let spender = Address::generate(&env);
+
+    issue(&token, &treasury, DEFAULT_BATCH_ID, 1000);
+    token.approve(&treasury, &spender, &300, &1000);
+    assert_eq!(token.allowance_purpose(&treasury, &spender), AllowancePurpose::Any);
+
+    token.transfer_from(&spender, &treasury, &spender, &100);
+    token.burn_from(&spender, &treasury, &100);
+    assert_eq!(token.allowance(&treasury, &spender), 100);
 }
 
+// ── Incremental allowances ────────────────────────────────────────────────────
+
 #[test]
-fn test_operations_resume_after_unpause() {
-    let (env, token, admin, verifier, user) = setup();
-    token.admin_pause(&admin);
+fn test_increase_and_decrease_allowance() {
+    let (env, token, _, treasury) = setup_env();
+    let custodian = Address::generate(&env);
+
+    issue(&token, &treasury, DEFAULT_BATCH_ID, 1000);
+    token.increase_allowance(&treasury, &custodian, &200, &1000, &AllowancePurpose::Any);
+    assert_eq!(token.allowance(&treasury, &custodian), 200);
+    assert_eq!(token.allowance_purpose(&treasury, &custodian), AllowancePurpose::Any);
+
+    token.approve_with_purpose(&treasury, &custodian, &100, &1000, &AllowancePurpose::Retire);
+    let result =
+        token.try_increase_allowance(&treasury, &custodian, &50, &2000, &AllowancePurpose::Any);
+    assert_eq!(result, Err(Ok(Error::AllowancePurposeMismatch)));
+    token.increase_allowance(&treasury, &custodian, &50, &2000, &AllowancePurpose::Retire);
+    assert_eq!(token.allowance(&treasury, &custodian), 150);
+    assert_eq!(token.allowance_purpose(&treasury, &custodian), AllowancePurpose::Retire);
+
+    token.decrease_allowance(&treasury, &custodian, &120);
+    assert_eq!(token.allowance(&treasury, &custodian), 30);
+    assert_eq!(token.allowance_purpose(&treasury, &custodian), AllowancePurpose::Retire);
+
+    let result = token.try_decrease_allowance(&treasury, &custodian, &31);
+    assert_eq!(result, Err(Ok(Error::InsufficientAllowance)));
+    assert_eq!(token.allowance(&treasury, &custodian), 30);
+}
+
+#[test]
+fn test_increase_allowance_after_expiry_uses_given_purpose() {
+    let (env, token, _, treasury) = setup_env();
+    let custodian = Address::generate(&env);
+
+    issue(&token, &treasury, DEFAULT_BATCH_ID, 1000);
+    let sequence = env.ledger().sequence();
+    token.approve_with_purpose(
+        &treasury,
+        &custodian,
+        &100,
+        &(sequence + 5),
+        &AllowancePurpose::Retire,
+    );
+
+    // Topping up a lapsed allowance never falls back to `Any`.
+    env.ledger().with_mut(|li| li.sequence_number = sequence + 6);
+    assert_eq!(token.allowance(&treasury, &custodian), 0);
+
+    let expiration = sequence + 1000;
+    token.increase_allowance(&treasury, &custodian, &200, &expiration, &AllowancePurpose::Retire);
+    assert_eq!(token.allowance(&treasury, &custodian), 200);
+    assert_eq!(token.allowance_purpose(&treasury, &custodian), AllowancePurpose::Retire);
+    let result = token.try_transfer_from(&custodian, &treasury, &custodian, &200);
+    assert_eq!(result, Err(Ok(Error::AllowancePurposeMismatch)));
+}
+
+#[test]
+fn test_spenders_of_lists_live_allowances() {
+    let (env, token, _, treasury) = setup_env();
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let third = Address::generate(&env);
+
+    issue(&token, &treasury, DEFAULT_BATCH_ID, 1000);
+    token.approve(&treasury, &first, &100, &1000);
+    token.increase_allowance(&treasury, &second, &100, &1000, &AllowancePurpose::Any);
+    token.approve(&treasury, &third, &100, &1000);
+    assert_eq!(
+        token.spenders_of(&treasury),
+        vec![&env, first.clone(), second.clone(), third.clone()]
+    );
+
+    token.transfer_from(&first, &treasury, &first, &100);
+    token.decrease_allowance(&treasury, &second, &100);
+    assert_eq!(token.spenders_of(&treasury), vec![&env, third.clone()]);
+
+    token.approve(&treasury, &third, &0, &0);
+    assert_eq!(token.spenders_of(&treasury).len(), 0);
+}
+
+// ── Schema migration ──────────────────────────────────────────────────────────
+
+#[test]
+fn test_initialize_records_current_schema_version() {
+    let (_, token, _, _) = setup_env();
+    assert_eq!(token.schema_version(), crate::storage::CURRENT_SCHEMA_VERSION);
+}
+
+/// The token as first released; see `testdata/README.md`.
+const TOKEN_V0_WASM: &[u8] = include_bytes!("../testdata/carbon_credit_token_v0.wasm");
+
+#[test]
+fn test_migrate_upgrades_first_release_deployment() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let verifier = Address::generate(&env);
+    let holder = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let rbac_id = env.register_contract(None, MockRbacContract);
+    let registry_id = env.register_contract(None, MockVerifierRegistry);
+
+    let token_id = env.register_contract_wasm(None, TOKEN_V0_WASM);
+    env.invoke_contract::<()>(
+        &token_id,
+        &Symbol::new(&env, "initialize"),
+        vec![
+            &env,
+            admin.into_val(&env),
+            rbac_id.into_val(&env),
+            String::from_str(&env, "Carbon Credit Token").into_val(&env),
+            String::from_str(&env, "CCT").into_val(&env),
+            0u32.into_val(&env),
+        ],
+    );
+
+    // Activity under the first release.
+    let legacy_hash = Bytes::from_slice(&env, b"legacy-0001");
+    let expiration_ledger = env.ledger().sequence() + 1000;
+    env.invoke_contract::<()>(
+        &token_id,
+        &Symbol::new(&env, "mint"),
+        vec![
+            &env,
+            verifier.into_val(&env),
+            holder.into_val(&env),
+            1000i128.into_val(&env),
+            legacy_hash.into_val(&env),
+        ],
+    );
+    env.invoke_contract::<()>(
+        &token_id,
+        &Symbol::new(&env, "approve"),
+        vec![
+            &env,
+            holder.into_val(&env),
+            spender.into_val(&env),
+            300i128.into_val(&env),
+            expiration_ledger.into_val(&env),
+        ],
+    );
+    env.invoke_contract::<()>(
+        &token_id,
+        &Symbol::new(&env, "retire"),
+        vec![
+            &env,
+            holder.into_val(&env),
+            100i128.into_val(&env),
+            legacy_hash.into_val(&env),
+            String::from_str(&env, "VM0007").into_val(&env),
+        ],
+    );
+
+    // The first release has no `upgrade` entrypoint; swap the code in place.
+    env.register_contract(Some(&token_id), crate::TokenMigrator);
+    let migrator = TokenMigratorClient::new(&env, &token_id);
+    assert_eq!(migrator.schema_version(), 0);
+
+    // The modules are initialized against the migration code.
+    let compliance_id = env.register_contract(None, CreditCompliance);
+    let issuance_id = env.register_contract(None, CreditIssuance);
+    let retirement_id = env.register_contract(None, CreditRetirement);
+    let modules = Modules {
+        issuance: issuance_id.clone(),
+        retirement: retirement_id.clone(),
+        compliance: compliance_id.clone(),
+        permits: Address::generate(&env),
+        reversals: Address::generate(&env),
+    };
+    CreditComplianceClient::new(&env, &compliance_id).initialize(&token_id);
+    let issuance = CreditIssuanceClient::new(&env, &issuance_id);
+    let ex_ante = Address::generate(&env);
+    let mint_approvals = Address::generate(&env);
+    issuance.initialize(&token_id, &registry_id, &ex_ante, &mint_approvals);
+    let retirement = CreditRetirementClient::new(&env, &retirement_id);
+    retirement.initialize(&token_id);
+
+    let project_name = String::from_str(&env, "Amazon Reforestation");
+    let vintage = String::from_str(&env, "2023");
+    let location = String::from_str(&env, "Brazil");
+    let metadata_url = String::from_str(&env, "https://farmcredit.xyz/amazon-1");
+    let accounts = vec![&env, holder.clone(), spender.clone()];
+    let hashes = vec![&env, legacy_hash.clone()];
+    let allowances = vec![
+        &env,
+        AllowanceDataKey {
+            from: holder.clone(),
+            spender: spender.clone(),
+        },
+    ];
+    let migrate = || {
+        migrator.migrate(
+            &modules,
+            &project_name,
+            &vintage,
+            &location,
+            &metadata_url,
+            &accounts,
+            &hashes,
+            &allowances,
+        )
+    };
+    // Certificate counter, holder balance and certificate, report hash, allowance.
+    assert_eq!(migrate(), 5);
+    assert_eq!(migrate(), 0);
+    assert_eq!(migrator.schema_version(), crate::storage::CURRENT_SCHEMA_VERSION);
+
+    env.register_contract(Some(&token_id), CarbonCreditToken);
+    let token = CarbonCreditTokenClient::new(&env, &token_id);
+    assert_eq!(token.modules(), modules);
+
+    let batch = token.get_batch(&DEFAULT_BATCH_ID).unwrap();
+    assert_eq!(batch.project_name, project_name);
+    assert_eq!(batch.issued, 1000);
+    assert_eq!(batch.retired, 100);
+    assert_eq!(token.batch_balance(&holder, &DEFAULT_BATCH_ID), 900);
+    assert_eq!(token.allowance(&holder, &spender), 300);
+    assert_eq!(token.allowance_purpose(&holder, &spender), AllowancePurpose::Any);
+    let cert = retirement.get_certificate(&1).unwrap();
+    assert_eq!(cert.owner, holder);
+    assert_eq!(cert.amount, 100);
+    assert_eq!(cert.batch_id, DEFAULT_BATCH_ID);
+    assert!(issuance.is_report_hash_used(&legacy_hash));
+
+    // The upgraded deployment works like a fresh one.
+    let hash = Bytes::from_slice(&env, b"mrv-0002");
+    let registry = MockVerifierRegistryClient::new(&env, &registry_id);
+    registry.submit_report_hash(&verifier, &holder, &String::from_str(&env, "mrv-0002"));
+    issuance.mint(&verifier, &holder, &500, &hash);
+    token.transfer(&holder, &buyer, &1000);
+    token.transfer_from(&spender, &holder, &buyer, &200);
+    assert_eq!(token.balance(&holder), 200);
+    assert_eq!(token.batch_balance(&buyer, &DEFAULT_BATCH_ID), 1200);
+    assert_eq!(token.allowance(&holder, &spender), 100);
+
+    retirement.retire(&buyer, &50, &hash, &String::from_str(&env, "VM0007"));
+    assert_eq!(retirement.certificate_count(), 2);
+    assert_eq!(retirement.get_certificate(&2).unwrap().owner, buyer);
+    assert_eq!(token.total_supply(), 1350);
+    assert_eq!(token.get_batch(&DEFAULT_BATCH_ID).unwrap().retired, 150);
+}
+
+// ── Super admin handover ──────────────────────────────────────────────────────
+
+#[test]
+fn test_super_admin_handover_requires_acceptance() {
+    let (env, token, admin, _) = setup_env();
+    let successor = Address::generate(&env);
+
+    token.transfer_super_admin(&successor, &100);
+    assert_eq!(token.super_admin(), admin);
+    assert_eq!(
+        token.pending_super_admin(),
+        Some(PendingSuperAdmin {
+            successor: successor.clone(),
+            expiration_ledger: 100,
+        })
+    );
+
+    token.accept_super_admin();
+    assert_eq!(token.super_admin(), successor);
+    assert_eq!(token.pending_super_admin(), None);
+    let result = token.try_accept_super_admin();
+    assert_eq!(result, Err(Ok(Error::NoPendingSuperAdmin)));
+}
+
+#[test]
+fn test_super_admin_handover_can_be_cancelled_or_expire() {
+    let (env, token, admin, _) = setup_env();
+    let mistyped = Address::generate(&env);
+
+    token.transfer_super_admin(&mistyped, &100);
+    token.cancel_super_admin_transfer();
+    let result = token.try_accept_super_admin();
+    assert_eq!(result, Err(Ok(Error::NoPendingSuperAdmin)));
+    let result = token.try_cancel_super_admin_transfer();
+    assert_eq!(result, Err(Ok(Error::NoPendingSuperAdmin)));
+
+    token.transfer_super_admin(&mistyped, &100);
+    env.ledger().with_mut(|li| li.sequence_number = 101);
+    let result = token.try_accept_super_admin();
+    assert_eq!(result, Err(Ok(Error::SuperAdminTransferExpired)));
+    assert_eq!(token.super_admin(), admin);
+
+    let result = token.try_transfer_super_admin(&mistyped, &100);
+    assert_eq!(result, Err(Ok(Error::InvalidExpirationLedger)));
+}
+
+// ── Governance mode ───────────────────────────────────────────────────────────
+
+#[test]
+fn test_governance_mode_moves_admin_authority_to_rbac() {
+    let (env, token, admin, user) = setup_env();
+    let rbac = token.rbac_contract();
+    let compliance = compliance_of(&token);
+
+    compliance.blacklist(&user);
+    assert_eq!(env.auths()[0].0, admin);
+
+    token.set_governance_mode(&true);
+    assert!(token.governance_mode());
+    compliance.unblacklist(&user);
+    assert_eq!(env.auths()[0].0, rbac);
+
+    let result = token.try_admin_pause(&admin);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    token.admin_pause(&rbac);
+    assert!(token.paused());
+
+    token.set_governance_mode(&false);
+    assert_eq!(env.auths()[0].0, rbac);
     token.admin_unpause(&admin);
-    // should succeed after unpause
-    token.mint(&verifier, &user, &500);
-    assert_eq!(token.balance(&user), 500);
-    let other = Address::generate(&env);
-    token.transfer(&user, &other, &200);
-    assert_eq!(token.balance(&other), 200);
+    assert!(!token.paused());
 }
 
 // ── Pause / unpause ───────────────────────────────────────────────────────────
 
 #[test]
 fn test_paused_initially_false() {
-    let (_env, token, _, _, _) = setup();
+    let (_env, token, _, _) = setup_env();
     assert!(!token.paused());
 }
 
 #[test]
 fn test_admin_pause_and_unpause() {
-    let (_env, token, admin, _, _) = setup();
+    let (_env, token, admin, _) = setup_env();
     token.admin_pause(&admin);
     assert!(token.paused());
     token.admin_unpause(&admin);
@@ -203,7 +707,7 @@ fn test_admin_pause_and_unpause() {
 
 #[test]
 fn test_pause_emits_event() {
-    let (env, token, admin, _, _) = setup();
+    let (env, token, admin, _) = setup_env();
     let before = env.events().all().len();
     token.admin_pause(&admin);
     assert!(env.events().all().len() > before);
@@ -211,7 +715,7 @@ fn test_pause_emits_event() {
 
 #[test]
 fn test_unpause_emits_event() {
-    let (env, token, admin, _, _) = setup();
+    let (env, token, admin, _) = setup_env();
     token.admin_pause(&admin);
     let before = env.events().all().len();
     token.admin_unpause(&admin);
@@ -220,24 +724,36 @@ fn test_unpause_emits_event() {
 
 #[test]
 fn test_pause_by_non_admin_returns_error() {
-    let (env, token, _, _, _) = setup();
+    let (env, token, _, _) = setup_env();
     let rando = Address::generate(&env);
     let result = token.try_admin_pause(&rando);
     assert!(result.is_err());
 }
 
 #[test]
-fn test_mint_when_paused_returns_error() {
-    let (_env, token, admin, verifier, user) = setup();
+fn test_issuance_module_may_pause() {
+    let (_env, token, admin, _) = setup_env();
+    token.admin_pause(&token.modules().issuance);
+    assert!(token.paused());
+
+    let result = token.try_admin_unpause(&token.modules().issuance);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    token.admin_unpause(&admin);
+    assert!(!token.paused());
+}
+
+#[test]
+fn test_issue_when_paused_returns_error() {
+    let (_env, token, admin, user) = setup_env();
     token.admin_pause(&admin);
-    let result = token.try_mint(&verifier, &user, &1000);
+    let result = token.try_issue(&user, &DEFAULT_BATCH_ID, &1000);
     assert_eq!(result, Err(Ok(Error::ContractPaused)));
 }
 
 #[test]
 fn test_transfer_when_paused_returns_error() {
-    let (env, token, admin, verifier, user) = setup();
-    token.mint(&verifier, &user, &500);
+    let (env, token, admin, user) = setup_env();
+    issue(&token, &user, DEFAULT_BATCH_ID, 500);
     token.admin_pause(&admin);
     let other = Address::generate(&env);
     let result = token.try_transfer(&user, &other, &100);
@@ -246,8 +762,8 @@ fn test_transfer_when_paused_returns_error() {
 
 #[test]
 fn test_burn_when_paused_returns_error() {
-    let (_env, token, admin, verifier, user) = setup();
-    token.mint(&verifier, &user, &500);
+    let (_env, token, admin, user) = setup_env();
+    issue(&token, &user, DEFAULT_BATCH_ID, 500);
     token.admin_pause(&admin);
     let result = token.try_burn(&user, &100);
     assert_eq!(result, Err(Ok(Error::ContractPaused)));
@@ -255,11 +771,11 @@ fn test_burn_when_paused_returns_error() {
 
 #[test]
 fn test_operations_resume_after_unpause() {
-    let (env, token, admin, verifier, user) = setup();
+    let (env, token, admin, user) = setup_env();
     token.admin_pause(&admin);
     token.admin_unpause(&admin);
     // should succeed after unpause
-    token.mint(&verifier, &user, &500);
+    issue(&token, &user, DEFAULT_BATCH_ID, 500);
     assert_eq!(token.balance(&user), 500);
     let other = Address::generate(&env);
     token.transfer(&user, &other, &200);
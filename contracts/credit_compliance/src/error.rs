@@ -0,0 +1,38 @@
+use soroban_sdk::contracterror;
+
+/// Codes match the token's, so an error reads the same whichever contract
+/// raised it.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    /// The provided amount is negative.
+    NegativeAmount = 1,
+    /// The account does not have enough balance.
+    InsufficientBalance = 2,
+    /// The contract is already initialized.
+    AlreadyInitialized = 4,
+    /// The address is blacklisted.
+    Blacklisted = 6,
+    /// Only the SuperAdmin can blacklist/unblacklist themselves.
+    CannotBlacklistSelf = 10,
+    /// The amount exceeds what is frozen on the account.
+    ExceedsFrozenAmount = 41,
+    /// A forced transfer needs a source account that is blacklisted or frozen.
+    AccountNotRestricted = 42,
+    /// In permissioned-holder mode, a counterparty holds no Trader, Verifier
+    /// or Admin role.
+    HolderNotPermitted = 43,
+    /// The registered compliance hook rejected the movement.
+    TransferNotCompliant = 44,
+}
+
+/// Passes on the error of a failed `try_` call to the token. Anything else,
+/// such as a trap in the token, aborts the call.
+pub fn forward<T, C, I>(result: Result<Result<T, C>, Result<Error, I>>) -> Result<T, Error> {
+    match result {
+        Ok(Ok(value)) => Ok(value),
+        Err(Ok(error)) => Err(error),
+        _ => panic!("token call failed"),
+    }
+}
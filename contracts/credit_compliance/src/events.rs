@@ -0,0 +1,57 @@
+use soroban_sdk::{contracttype, symbol_short, Address, Env, String};
+
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct FreezeEvent {
+    pub target: Address,
+    pub amount: i128,
+    pub frozen: i128,
+    pub reason: String,
+}
+
+impl FreezeEvent {
+    pub fn publish(self, env: &Env) {
+        env.events().publish(
+            (symbol_short!("frozen"), self.target),
+            (self.amount, self.frozen, self.reason),
+        );
+    }
+}
+
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct UnfreezeEvent {
+    pub target: Address,
+    pub amount: i128,
+    pub frozen: i128,
+    pub reason: String,
+}
+
+impl UnfreezeEvent {
+    pub fn publish(self, env: &Env) {
+        env.events().publish(
+            (symbol_short!("unfrozen"), self.target),
+            (self.amount, self.frozen, self.reason),
+        );
+    }
+}
+
+/// Published for a SuperAdmin `force_transfer`; the token publishes only
+/// the batch transfers.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct ForcedTransferEvent {
+    pub from: Address,
+    pub to: Address,
+    pub amount: i128,
+    pub reason_code: u32,
+}
+
+impl ForcedTransferEvent {
+    pub fn publish(self, env: &Env) {
+        env.events().publish(
+            (symbol_short!("forced"), self.from, self.to),
+            (self.amount, self.reason_code),
+        );
+    }
+}
@@ -0,0 +1,29 @@
+use soroban_sdk::{contractclient, Address, Env};
+
+use crate::error::Error;
+use crate::storage::read_compliance_hook;
+
+/// Cross-contract client interface for an external transfer-compliance
+/// contract. Jurisdiction-specific rules (travel-rule limits, sanctioned
+/// geographies, ...) live there and can change without redeploying.
+#[contractclient(name = "ComplianceHookClient")]
+#[allow(dead_code)]
+pub trait ComplianceHookInterface {
+    /// Returns `true` when `amount` may move from `from` to `to`. Mints are
+    /// reported with the token contract as `from`, burns and retirements
+    /// with the token contract as `to`.
+    fn can_transfer(env: Env, from: Address, to: Address, amount: i128) -> bool;
+}
+
+/// Asks the registered compliance hook whether the movement is allowed.
+/// A no-op until the SuperAdmin sets a hook with `set_compliance_hook`.
+pub fn require_hook_approval(e: &Env, from: &Address, to: &Address, amount: i128) -> Result<(), Error> {
+    let Some(hook) = read_compliance_hook(e) else {
+        return Ok(());
+    };
+    if ComplianceHookClient::new(e, &hook).can_transfer(from, to, &amount) {
+        Ok(())
+    } else {
+        Err(Error::TransferNotCompliant)
+    }
+}
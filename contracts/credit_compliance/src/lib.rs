@@ -0,0 +1,313 @@
+#![no_std]
+
+mod error;
+mod events;
+mod hook;
+mod rbac;
+mod storage;
+mod token;
+#[cfg(test)]
+mod test;
+
+pub use error::Error;
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, String, Vec};
+
+use crate::error::forward;
+use crate::events::{ForcedTransferEvent, FreezeEvent, UnfreezeEvent};
+use crate::hook::require_hook_approval;
+use crate::rbac::is_permitted_holder;
+use crate::storage::{
+    blacklist_address, is_blacklisted, is_initialized, is_permissioned_mode,
+    read_compliance_hook, read_frozen, read_token, set_permissioned_mode, unblacklist_address,
+    write_compliance_hook, write_frozen, write_rbac_contract, write_token, INSTANCE_BUMP_AMOUNT,
+    INSTANCE_LIFETIME_THRESHOLD,
+};
+use crate::token::{read_admin_authority, token_client};
+
+fn check_nonnegative_amount(amount: i128) -> Result<(), Error> {
+    if amount < 0 {
+        Err(Error::NegativeAmount)
+    } else {
+        Ok(())
+    }
+}
+
+fn require_not_blacklisted(env: &Env, addr: &Address) -> Result<(), Error> {
+    if is_blacklisted(env, addr) {
+        Err(Error::Blacklisted)
+    } else {
+        Ok(())
+    }
+}
+
+/// In permissioned-holder mode, both sides of a transfer must hold a Trader,
+/// Verifier or Admin role in the RBAC contract.
+fn require_permitted_holders(env: &Env, from: &Address, to: &Address) -> Result<(), Error> {
+    if !is_permissioned_mode(env) {
+        return Ok(());
+    }
+    if !is_permitted_holder(env, from) || !is_permitted_holder(env, to) {
+        return Err(Error::HolderNotPermitted);
+    }
+    Ok(())
+}
+
+/// Compliance module of the carbon credit token: the blacklist, partial
+/// freezes, permissioned-holder mode and the external compliance hook. The
+/// token asks it to check every movement of credits.
+#[contract]
+pub struct CreditCompliance;
+
+#[contractimpl]
+impl CreditCompliance {
+    /// Binds the module to `token`. Must be signed by the token's SuperAdmin
+    /// (or RBAC contract in governance mode). Can only be called once.
+    pub fn initialize(env: Env, token: Address) -> Result<(), Error> {
+        if is_initialized(&env) {
+            return Err(Error::AlreadyInitialized);
+        }
+
+        write_token(&env, &token);
+        read_admin_authority(&env).require_auth();
+        write_rbac_contract(&env, &token_client(&env).rbac_contract());
+        Ok(())
+    }
+
+    /// Replaces the module code with the uploaded wasm `new_wasm_hash`.
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+        read_admin_authority(&env).require_auth();
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+    }
+
+    // ── Blacklist (SuperAdmin only) ───────────────────────────────────────────
+
+    pub fn blacklist(env: Env, target: Address) -> Result<(), Error> {
+        let authority = read_admin_authority(&env);
+        authority.require_auth();
+
+        if target == token_client(&env).super_admin() {
+            return Err(Error::CannotBlacklistSelf);
+        }
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        blacklist_address(&env, &target);
+        Ok(())
+    }
+
+    pub fn unblacklist(env: Env, target: Address) -> Result<(), Error> {
+        let authority = read_admin_authority(&env);
+        authority.require_auth();
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        unblacklist_address(&env, &target);
+        Ok(())
+    }
+
+    pub fn is_blacklisted(env: Env, addr: Address) -> bool {
+        is_blacklisted(&env, &addr)
+    }
+
+    /// Blacklists the accounts a first-release token had flagged. Called by
+    /// the token's `migrate` only.
+    pub fn import_blacklist(env: Env, accounts: Vec<Address>) -> Result<(), Error> {
+        read_token(&env).require_auth();
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        for account in accounts.iter() {
+            blacklist_address(&env, &account);
+        }
+        Ok(())
+    }
+
+    // ── Partial freezes (SuperAdmin only) ─────────────────────────────────────
+
+    /// Freezes `amount` of `target`'s balance on top of anything already
+    /// frozen. Unlike `blacklist`, the account keeps operating with the rest.
+    pub fn freeze_amount(
+        env: Env,
+        target: Address,
+        amount: i128,
+        reason: String,
+    ) -> Result<(), Error> {
+        let authority = read_admin_authority(&env);
+        authority.require_auth();
+        check_nonnegative_amount(amount)?;
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        let frozen = read_frozen(&env, &target) + amount;
+        write_frozen(&env, &target, frozen);
+
+        FreezeEvent {
+            target,
+            amount,
+            frozen,
+            reason,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Releases `amount` of the freeze on `target`.
+    pub fn unfreeze(env: Env, target: Address, amount: i128, reason: String) -> Result<(), Error> {
+        let authority = read_admin_authority(&env);
+        authority.require_auth();
+        check_nonnegative_amount(amount)?;
+
+        let frozen = read_frozen(&env, &target);
+        if amount > frozen {
+            return Err(Error::ExceedsFrozenAmount);
+        }
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        let frozen = frozen - amount;
+        write_frozen(&env, &target, frozen);
+
+        UnfreezeEvent {
+            target,
+            amount,
+            frozen,
+            reason,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Returns the amount of `id`'s balance that is frozen.
+    pub fn frozen_balance(env: Env, id: Address) -> i128 {
+        read_frozen(&env, &id)
+    }
+
+    /// Returns the part of `id`'s token balance that is not frozen.
+    pub fn spendable_balance(env: Env, id: Address) -> i128 {
+        (token_client(&env).balance(&id) - read_frozen(&env, &id)).max(0)
+    }
+
+    /// Moves credits out of a blacklisted or frozen account, e.g. under a
+    /// court order or after key loss. Unless the account is blacklisted, at
+    /// most the frozen amount can be moved. Seized credits come off the
+    /// freeze; release any remainder with `unfreeze` if it no longer applies.
+    /// `reason_code` is recorded in the event for off-chain case references.
+    pub fn force_transfer(
+        env: Env,
+        from: Address,
+        to: Address,
+        amount: i128,
+        reason_code: u32,
+    ) -> Result<(), Error> {
+        let authority = read_admin_authority(&env);
+        authority.require_auth();
+        check_nonnegative_amount(amount)?;
+        let frozen = read_frozen(&env, &from);
+        if !is_blacklisted(&env, &from) {
+            if frozen == 0 {
+                return Err(Error::AccountNotRestricted);
+            }
+            if amount > frozen {
+                return Err(Error::ExceedsFrozenAmount);
+            }
+        }
+        require_not_blacklisted(&env, &to)?;
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        forward(token_client(&env).try_force_transfer(&from, &to, &amount))?;
+        write_frozen(&env, &from, (frozen - amount).max(0));
+
+        ForcedTransferEvent {
+            from,
+            to,
+            amount,
+            reason_code,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    // ── Transfer policy (SuperAdmin only) ─────────────────────────────────────
+
+    /// Turns permissioned-holder mode on or off. While on, transfers require
+    /// both counterparties to hold the Trader, Verifier or Admin role in the
+    /// RBAC contract, so an escrow contract must be granted one of them too.
+    pub fn set_permissioned_mode(env: Env, enabled: bool) -> Result<(), Error> {
+        let authority = read_admin_authority(&env);
+        authority.require_auth();
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        set_permissioned_mode(&env, enabled);
+        Ok(())
+    }
+
+    pub fn permissioned_mode(env: Env) -> bool {
+        is_permissioned_mode(&env)
+    }
+
+    /// Registers the contract whose `can_transfer` is consulted on every
+    /// mint, transfer, burn and retirement. `None` removes the hook.
+    /// SuperAdmin corrections (`force_transfer`, `reverse_issuance`) skip it.
+    pub fn set_compliance_hook(env: Env, hook: Option<Address>) -> Result<(), Error> {
+        let authority = read_admin_authority(&env);
+        authority.require_auth();
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        write_compliance_hook(&env, hook);
+        Ok(())
+    }
+
+    /// Returns the registered compliance hook contract, if any.
+    pub fn compliance_hook(env: Env) -> Option<Address> {
+        read_compliance_hook(&env)
+    }
+
+    // ── Checks ────────────────────────────────────────────────────────────────
+
+    /// Checks a movement of `amount` from `from` to `to`, made by `spender`
+    /// when an allowance is used, and returns the amount frozen on `from`,
+    /// which the caller must leave unspent. No party may be blacklisted;
+    /// in permissioned-holder mode both sides of a transfer need a role, and
+    /// the compliance hook, if any, must approve. Mints are reported with
+    /// the token contract as `from`, burns and retirements with the token
+    /// contract as `to`.
+    pub fn check_transfer(
+        env: Env,
+        spender: Option<Address>,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<i128, Error> {
+        if let Some(spender) = &spender {
+            require_not_blacklisted(&env, spender)?;
+        }
+        require_not_blacklisted(&env, &from)?;
+        require_not_blacklisted(&env, &to)?;
+
+        let token = read_token(&env);
+        if from != token && to != token {
+            require_permitted_holders(&env, &from, &to)?;
+        }
+        require_hook_approval(&env, &from, &to, amount)?;
+        Ok(read_frozen(&env, &from))
+    }
+}
@@ -0,0 +1,27 @@
+use soroban_sdk::{contractclient, Address, Env};
+
+use crate::storage::read_rbac_contract;
+
+/// Role codes returned by the RBAC contract's `get_role`. Traders are
+/// vetted (e.g. KYC'd) holders, checked in permissioned-holder mode.
+const VERIFIER_ROLE_CODE: u32 = 1;
+const TRADER_ROLE_CODE: u32 = 2;
+const ADMIN_ROLE_CODE: u32 = 3;
+
+/// Cross-contract client interface for the external RBAC contract.
+#[contractclient(name = "RbacContractClient")]
+#[allow(dead_code)]
+pub trait RbacContractInterface {
+    /// Returns the code of the single role `address` holds, or `255` for none.
+    fn get_role(env: Env, address: Address) -> u32;
+}
+
+/// Returns `true` when `address` may hold and move credits in
+/// permissioned-holder mode: it must be a Trader, Verifier or Admin.
+pub fn is_permitted_holder(e: &Env, address: &Address) -> bool {
+    let client = RbacContractClient::new(e, &read_rbac_contract(e));
+    matches!(
+        client.get_role(address),
+        TRADER_ROLE_CODE | VERIFIER_ROLE_CODE | ADMIN_ROLE_CODE
+    )
+}
@@ -0,0 +1,131 @@
+use soroban_sdk::{contracttype, Address, Env};
+
+// ── TTL Constants ──────────────────────────────────────────────────────────────
+pub const INSTANCE_LIFETIME_THRESHOLD: u32 = 17280; // ~1 day
+pub const INSTANCE_BUMP_AMOUNT: u32 = 518400; // ~30 days
+
+pub const BALANCE_LIFETIME_THRESHOLD: u32 = 17280; // ~1 day
+pub const BALANCE_BUMP_AMOUNT: u32 = 518400; // ~30 days
+
+// ── Storage Keys ───────────────────────────────────────────────────────────────
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    // The token this module serves, and its RBAC contract
+    Token,
+    RbacContract,
+
+    // Blacklist and partial freezes
+    Blacklisted(Address),
+    Frozen(Address),
+
+    // Permissioned-holder mode and the external hook
+    PermissionedHolders,
+    ComplianceHook,
+}
+
+// ── Token ──────────────────────────────────────────────────────────────────────
+pub fn is_initialized(e: &Env) -> bool {
+    e.storage().instance().has(&DataKey::Token)
+}
+
+pub fn read_token(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::Token)
+        .expect("token not set: was initialize() called?")
+}
+
+pub fn write_token(e: &Env, token: &Address) {
+    e.storage().instance().set(&DataKey::Token, token);
+}
+
+/// The token's RBAC contract, read once at initialization: the token calls
+/// `check_transfer` and cannot be called back while it does.
+pub fn read_rbac_contract(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::RbacContract)
+        .expect("rbac contract address not set: was initialize() called?")
+}
+
+pub fn write_rbac_contract(e: &Env, rbac_id: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::RbacContract, rbac_id);
+}
+
+// ── Blacklist ──────────────────────────────────────────────────────────────────
+pub fn is_blacklisted(e: &Env, addr: &Address) -> bool {
+    e.storage()
+        .persistent()
+        .get::<DataKey, bool>(&DataKey::Blacklisted(addr.clone()))
+        .unwrap_or(false)
+}
+
+pub fn blacklist_address(e: &Env, addr: &Address) {
+    let key = DataKey::Blacklisted(addr.clone());
+    e.storage().persistent().set(&key, &true);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+pub fn unblacklist_address(e: &Env, addr: &Address) {
+    e.storage()
+        .persistent()
+        .remove(&DataKey::Blacklisted(addr.clone()));
+}
+
+// ── Partial freezes ────────────────────────────────────────────────────────────
+/// Amount of `addr`'s balance that is frozen. It may exceed the balance, in
+/// which case credits received later stay frozen up to that amount.
+pub fn read_frozen(e: &Env, addr: &Address) -> i128 {
+    let key = DataKey::Frozen(addr.clone());
+    if let Some(frozen) = e.storage().persistent().get::<DataKey, i128>(&key) {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+        frozen
+    } else {
+        0
+    }
+}
+
+pub fn write_frozen(e: &Env, addr: &Address, amount: i128) {
+    let key = DataKey::Frozen(addr.clone());
+    if amount == 0 {
+        e.storage().persistent().remove(&key);
+        return;
+    }
+    e.storage().persistent().set(&key, &amount);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+// ── Permissioned-holder mode ───────────────────────────────────────────────────
+pub fn is_permissioned_mode(e: &Env) -> bool {
+    e.storage()
+        .instance()
+        .get::<DataKey, bool>(&DataKey::PermissionedHolders)
+        .unwrap_or(false)
+}
+
+pub fn set_permissioned_mode(e: &Env, enabled: bool) {
+    e.storage()
+        .instance()
+        .set(&DataKey::PermissionedHolders, &enabled);
+}
+
+// ── Compliance hook ────────────────────────────────────────────────────────────
+pub fn read_compliance_hook(e: &Env) -> Option<Address> {
+    e.storage().instance().get(&DataKey::ComplianceHook)
+}
+
+pub fn write_compliance_hook(e: &Env, hook: Option<Address>) {
+    match hook {
+        Some(hook) => e.storage().instance().set(&DataKey::ComplianceHook, &hook),
+        None => e.storage().instance().remove(&DataKey::ComplianceHook),
+    }
+}
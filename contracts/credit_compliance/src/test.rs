@@ -0,0 +1,299 @@
+#![cfg(test)]
+
+use crate::{CreditCompliance, CreditComplianceClient, Error};
+use carbon_credit_token::{
+    CarbonCreditToken, CarbonCreditTokenClient, Error as TokenError, Modules, DEFAULT_BATCH_ID,
+};
+use soroban_sdk::{contract, contractimpl, testutils::Address as _, Address, Env, String};
+
+#[contract]
+pub struct MockRbacContract;
+
+#[contractimpl]
+impl MockRbacContract {
+    pub fn get_role(env: Env, address: Address) -> u32 {
+        // Everyone is a Trader unless denied
+        if env.storage().instance().has(&address) {
+            255
+        } else {
+            2
+        }
+    }
+
+    pub fn deny(env: Env, address: Address) {
+        env.storage().instance().set(&address, &true);
+    }
+}
+
+#[contract]
+pub struct MockComplianceHook;
+
+#[contractimpl]
+impl MockComplianceHook {
+    pub fn set_limit(env: Env, limit: i128) {
+        env.storage().instance().set(&(), &limit);
+    }
+
+    pub fn can_transfer(env: Env, _from: Address, _to: Address, amount: i128) -> bool {
+        amount <= env.storage().instance().get(&()).unwrap_or(i128::MAX)
+    }
+}
+
+/// Registers a token bound to this module and returns
+/// `(env, token, compliance, admin, user)`. The other modules are bare
+/// addresses: with every auth mocked, credits are issued by calling the
+/// token's `issue` directly.
+fn setup_env<'a>() -> (
+    Env,
+    CarbonCreditTokenClient<'a>,
+    CreditComplianceClient<'a>,
+    Address,
+    Address,
+) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let rbac_id = env.register_contract(None, MockRbacContract);
+    let compliance_id = env.register_contract(None, CreditCompliance);
+    let token_id = env.register_contract(None, CarbonCreditToken);
+    let token = CarbonCreditTokenClient::new(&env, &token_id);
+
+    token.initialize(
+        &admin,
+        &rbac_id,
+        &Modules {
+            issuance: Address::generate(&env),
+            retirement: Address::generate(&env),
+            compliance: compliance_id.clone(),
+            permits: Address::generate(&env),
+            reversals: Address::generate(&env),
+        },
+        &String::from_str(&env, "Carbon Credit Token"),
+        &String::from_str(&env, "CCT"),
+        &0u32,
+        &String::from_str(&env, "Amazon Reforestation"),
+        &String::from_str(&env, "2023"),
+        &String::from_str(&env, "Brazil"),
+        &String::from_str(&env, "https://farmcredit.xyz/amazon-1"),
+    );
+    let compliance = CreditComplianceClient::new(&env, &compliance_id);
+    compliance.initialize(&token_id);
+
+    let user = Address::generate(&env);
+
+    (env, token, compliance, admin, user)
+}
+
+#[test]
+fn test_initialize_binds_token_once() {
+    let (_, token, compliance, _, _) = setup_env();
+
+    let result = compliance.try_initialize(&token.address);
+    assert_eq!(result, Err(Ok(Error::AlreadyInitialized)));
+}
+
+// ── Blacklist ─────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_blacklisted_account_cannot_move_credits() {
+    let (env, token, compliance, admin, user) = setup_env();
+    let other = Address::generate(&env);
+
+    token.issue(&user, &DEFAULT_BATCH_ID, &1000);
+    compliance.blacklist(&user);
+    assert!(compliance.is_blacklisted(&user));
+
+    let result = token.try_transfer(&user, &other, &100);
+    assert_eq!(result, Err(Ok(TokenError::Blacklisted)));
+    let result = token.try_transfer(&other, &user, &0);
+    assert_eq!(result, Err(Ok(TokenError::Blacklisted)));
+    let result = token.try_issue(&user, &DEFAULT_BATCH_ID, &100);
+    assert_eq!(result, Err(Ok(TokenError::Blacklisted)));
+
+    let result = compliance.try_blacklist(&admin);
+    assert_eq!(result, Err(Ok(Error::CannotBlacklistSelf)));
+
+    compliance.unblacklist(&user);
+    token.transfer(&user, &other, &100);
+    assert_eq!(token.balance(&other), 100);
+}
+
+// ── Partial freezes ───────────────────────────────────────────────────────────
+
+#[test]
+fn test_frozen_amount_cannot_be_spent() {
+    let (env, token, compliance, _, treasury) = setup_env();
+    let buyer = Address::generate(&env);
+    let reason = String::from_str(&env, "court order 42");
+
+    token.issue(&treasury, &DEFAULT_BATCH_ID, &1000);
+    compliance.freeze_amount(&treasury, &600, &reason);
+    assert_eq!(compliance.frozen_balance(&treasury), 600);
+    assert_eq!(compliance.spendable_balance(&treasury), 400);
+
+    token.transfer(&treasury, &buyer, &300);
+    let result = token.try_transfer(&treasury, &buyer, &200);
+    assert_eq!(result, Err(Ok(TokenError::BalanceFrozen)));
+    let result = token.try_burn(&treasury, &200);
+    assert_eq!(result, Err(Ok(TokenError::BalanceFrozen)));
+    assert_eq!(token.balance(&treasury), 700);
+
+    compliance.unfreeze(&treasury, &600, &reason);
+    token.transfer(&treasury, &buyer, &200);
+    assert_eq!(compliance.spendable_balance(&treasury), 500);
+}
+
+#[test]
+fn test_unfreeze_is_limited_to_frozen_amount() {
+    let (env, token, compliance, _, treasury) = setup_env();
+    let reason = String::from_str(&env, "aml review");
+
+    token.issue(&treasury, &DEFAULT_BATCH_ID, &100);
+    compliance.freeze_amount(&treasury, &150, &reason);
+    assert_eq!(compliance.spendable_balance(&treasury), 0);
+
+    let result = compliance.try_unfreeze(&treasury, &151, &reason);
+    assert_eq!(result, Err(Ok(Error::ExceedsFrozenAmount)));
+    compliance.unfreeze(&treasury, &100, &reason);
+    assert_eq!(compliance.frozen_balance(&treasury), 50);
+    assert_eq!(compliance.spendable_balance(&treasury), 50);
+}
+
+#[test]
+fn test_force_transfer_out_of_blacklisted_account() {
+    let (env, token, compliance, _, treasury) = setup_env();
+    let recovery = Address::generate(&env);
+
+    token.issue(&treasury, &DEFAULT_BATCH_ID, &1000);
+    let result = compliance.try_force_transfer(&treasury, &recovery, &400, &7);
+    assert_eq!(result, Err(Ok(Error::AccountNotRestricted)));
+
+    compliance.blacklist(&treasury);
+    compliance.force_transfer(&treasury, &recovery, &400, &7);
+    assert_eq!(token.balance(&treasury), 600);
+    assert_eq!(token.balance(&recovery), 400);
+    assert_eq!(token.batch_balance(&recovery, &1), 400);
+}
+
+#[test]
+fn test_force_transfer_moves_frozen_credits() {
+    let (env, token, compliance, _, treasury) = setup_env();
+    let claimant = Address::generate(&env);
+
+    token.issue(&treasury, &DEFAULT_BATCH_ID, &1000);
+    compliance.freeze_amount(&treasury, &600, &String::from_str(&env, "court order 42"));
+    let result = compliance.try_force_transfer(&treasury, &claimant, &601, &42);
+    assert_eq!(result, Err(Ok(Error::ExceedsFrozenAmount)));
+
+    compliance.force_transfer(&treasury, &claimant, &400, &42);
+    assert_eq!(token.balance(&treasury), 600);
+    assert_eq!(token.balance(&claimant), 400);
+    assert_eq!(compliance.frozen_balance(&treasury), 200);
+    assert_eq!(compliance.spendable_balance(&treasury), 400);
+
+    compliance.force_transfer(&treasury, &claimant, &200, &42);
+    assert_eq!(compliance.frozen_balance(&treasury), 0);
+    let result = compliance.try_force_transfer(&treasury, &claimant, &1, &42);
+    assert_eq!(result, Err(Ok(Error::AccountNotRestricted)));
+
+    compliance.freeze_amount(&treasury, &100, &String::from_str(&env, "court order 43"));
+    compliance.blacklist(&claimant);
+    let result = compliance.try_force_transfer(&treasury, &claimant, &0, &42);
+    assert_eq!(result, Err(Ok(Error::Blacklisted)));
+}
+
+// ── Permissioned-holder mode ──────────────────────────────────────────────────
+
+#[test]
+fn test_permissioned_mode_requires_roles_on_both_sides() {
+    let (env, token, compliance, _, treasury) = setup_env();
+    let trader = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    let rbac = MockRbacContractClient::new(&env, &token.rbac_contract());
+    rbac.deny(&outsider);
+
+    token.issue(&treasury, &DEFAULT_BATCH_ID, &1000);
+    token.transfer(&treasury, &outsider, &100);
+
+    compliance.set_permissioned_mode(&true);
+    assert!(compliance.permissioned_mode());
+    token.transfer(&treasury, &trader, &100);
+    let result = token.try_transfer(&treasury, &outsider, &100);
+    assert_eq!(result, Err(Ok(TokenError::HolderNotPermitted)));
+    let result = token.try_transfer(&outsider, &treasury, &100);
+    assert_eq!(result, Err(Ok(TokenError::HolderNotPermitted)));
+
+    token.approve(&treasury, &outsider, &100, &1000);
+    let result = token.try_transfer_from(&outsider, &treasury, &outsider, &100);
+    assert_eq!(result, Err(Ok(TokenError::HolderNotPermitted)));
+    token.transfer_from(&outsider, &treasury, &trader, &100);
+    assert_eq!(token.balance(&trader), 200);
+
+    // Issuing and burning have the token on one side and are not gated.
+    token.issue(&outsider, &DEFAULT_BATCH_ID, &100);
+    token.burn(&outsider, &100);
+
+    compliance.set_permissioned_mode(&false);
+    token.transfer(&outsider, &treasury, &100);
+}
+
+// ── Compliance hook ───────────────────────────────────────────────────────────
+
+fn setup_compliance_hook<'a>(
+    env: &Env,
+    compliance: &CreditComplianceClient,
+    limit: i128,
+) -> MockComplianceHookClient<'a> {
+    let hook_id = env.register_contract(None, MockComplianceHook);
+    let hook = MockComplianceHookClient::new(env, &hook_id);
+    hook.set_limit(&limit);
+    compliance.set_compliance_hook(&Some(hook_id.clone()));
+    assert_eq!(compliance.compliance_hook(), Some(hook_id));
+    hook
+}
+
+#[test]
+fn test_compliance_hook_gates_transfers_and_burns() {
+    let (env, token, compliance, _, treasury) = setup_env();
+    let buyer = Address::generate(&env);
+
+    token.issue(&treasury, &DEFAULT_BATCH_ID, &1000);
+    setup_compliance_hook(&env, &compliance, 100);
+
+    token.transfer(&treasury, &buyer, &100);
+    let result = token.try_transfer(&treasury, &buyer, &101);
+    assert_eq!(result, Err(Ok(TokenError::TransferNotCompliant)));
+    let result = token.try_transfer_in_batch(&treasury, &buyer, &1, &101);
+    assert_eq!(result, Err(Ok(TokenError::TransferNotCompliant)));
+    let result = token.try_burn(&treasury, &101);
+    assert_eq!(result, Err(Ok(TokenError::TransferNotCompliant)));
+    assert_eq!(token.balance(&treasury), 900);
+
+    compliance.set_compliance_hook(&None);
+    token.transfer(&treasury, &buyer, &500);
+    assert_eq!(token.balance(&buyer), 600);
+}
+
+#[test]
+fn test_compliance_hook_gates_issuance() {
+    let (env, token, compliance, _, treasury) = setup_env();
+    setup_compliance_hook(&env, &compliance, 500);
+
+    let result = token.try_issue(&treasury, &DEFAULT_BATCH_ID, &501);
+    assert_eq!(result, Err(Ok(TokenError::TransferNotCompliant)));
+    token.issue(&treasury, &DEFAULT_BATCH_ID, &500);
+    assert_eq!(token.balance(&treasury), 500);
+}
+
+#[test]
+fn test_force_transfer_skips_compliance_hook() {
+    let (env, token, compliance, _, treasury) = setup_env();
+    let recovery = Address::generate(&env);
+
+    token.issue(&treasury, &DEFAULT_BATCH_ID, &1000);
+    setup_compliance_hook(&env, &compliance, 0);
+    compliance.blacklist(&treasury);
+    compliance.force_transfer(&treasury, &recovery, &1000, &7);
+    assert_eq!(token.balance(&recovery), 1000);
+}
@@ -0,0 +1,32 @@
+use soroban_sdk::{contractclient, Address, Env};
+
+use crate::error::Error;
+use crate::storage::read_token;
+
+/// The parts of the carbon credit token this module calls.
+#[contractclient(name = "TokenClient")]
+#[allow(dead_code)]
+pub trait TokenInterface {
+    fn super_admin(env: Env) -> Address;
+    fn rbac_contract(env: Env) -> Address;
+    fn governance_mode(env: Env) -> bool;
+    fn balance(env: Env, id: Address) -> i128;
+
+    /// Moves credits regardless of freezes. Accepts this module only.
+    fn force_transfer(env: Env, from: Address, to: Address, amount: i128) -> Result<(), Error>;
+}
+
+pub fn token_client(e: &Env) -> TokenClient<'_> {
+    TokenClient::new(e, &read_token(e))
+}
+
+/// The address admin entrypoints require auth from: the token's SuperAdmin,
+/// or, in governance mode, its RBAC contract.
+pub fn read_admin_authority(e: &Env) -> Address {
+    let token = token_client(e);
+    if token.governance_mode() {
+        token.rbac_contract()
+    } else {
+        token.super_admin()
+    }
+}
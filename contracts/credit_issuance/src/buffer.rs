@@ -0,0 +1,52 @@
+use soroban_sdk::{contracttype, Address, Env};
+
+use crate::storage::{PolicyKey, BALANCE_BUMP_AMOUNT, BALANCE_LIFETIME_THRESHOLD};
+
+/// Basis points in 100%.
+pub const MAX_BPS: u32 = 10_000;
+
+/// Where withheld credits go, and the share withheld by default.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct BufferConfig {
+    pub account: Address,
+    pub bps: u32,
+}
+
+pub fn read_buffer_config(e: &Env) -> Option<BufferConfig> {
+    e.storage().instance().get(&PolicyKey::Buffer)
+}
+
+pub fn write_buffer_config(e: &Env, config: &BufferConfig) {
+    e.storage().instance().set(&PolicyKey::Buffer, config);
+}
+
+pub fn read_batch_buffer_bps(e: &Env, batch_id: u32) -> Option<u32> {
+    e.storage()
+        .persistent()
+        .get(&PolicyKey::BatchBufferBps(batch_id))
+}
+
+pub fn write_batch_buffer_bps(e: &Env, batch_id: u32, bps: u32) {
+    let key = PolicyKey::BatchBufferBps(batch_id);
+    e.storage().persistent().set(&key, &bps);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+/// Share withheld from mints into `batch_id`: the batch override if one is
+/// set, otherwise the contract default. Zero when no buffer is configured.
+pub fn effective_buffer_bps(e: &Env, batch_id: u32) -> u32 {
+    match read_buffer_config(e) {
+        Some(config) => read_batch_buffer_bps(e, batch_id).unwrap_or(config.bps),
+        None => 0,
+    }
+}
+
+/// Splits a gross mint into `(net, buffered)`. The buffered share is
+/// rounded down, so the recipient never receives less than its share.
+pub fn split_mint(e: &Env, batch_id: u32, gross: i128) -> (i128, i128) {
+    let buffered = gross * effective_buffer_bps(e, batch_id) as i128 / MAX_BPS as i128;
+    (gross - buffered, buffered)
+}
@@ -0,0 +1,107 @@
+use soroban_sdk::{Address, Env, String};
+
+use crate::error::Error;
+use crate::storage::{PolicyKey, BALANCE_BUMP_AMOUNT, BALANCE_LIFETIME_THRESHOLD};
+use crate::token::{token_client, CreditBatch};
+
+fn read_persistent_cap(e: &Env, key: &PolicyKey) -> Option<i128> {
+    e.storage().persistent().get(key)
+}
+
+fn write_persistent_cap(e: &Env, key: PolicyKey, cap: Option<i128>) {
+    match cap {
+        Some(cap) => {
+            e.storage().persistent().set(&key, &cap);
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+        }
+        None => e.storage().persistent().remove(&key),
+    }
+}
+
+fn read_issued(e: &Env, key: &PolicyKey) -> i128 {
+    e.storage().persistent().get(key).unwrap_or(0)
+}
+
+fn add_issued(e: &Env, key: PolicyKey, delta: i128) {
+    let issued = read_issued(e, &key) + delta;
+    e.storage().persistent().set(&key, &issued);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+pub fn read_global_cap(e: &Env) -> Option<i128> {
+    e.storage().instance().get(&PolicyKey::GlobalCap)
+}
+
+pub fn write_global_cap(e: &Env, cap: Option<i128>) {
+    match cap {
+        Some(cap) => e.storage().instance().set(&PolicyKey::GlobalCap, &cap),
+        None => e.storage().instance().remove(&PolicyKey::GlobalCap),
+    }
+}
+
+pub fn read_verifier_cap(e: &Env, verifier: &Address) -> Option<i128> {
+    read_persistent_cap(e, &PolicyKey::VerifierCap(verifier.clone()))
+}
+
+pub fn write_verifier_cap(e: &Env, verifier: &Address, cap: Option<i128>) {
+    write_persistent_cap(e, PolicyKey::VerifierCap(verifier.clone()), cap);
+}
+
+pub fn read_project_cap(e: &Env, project_name: &String) -> Option<i128> {
+    read_persistent_cap(e, &PolicyKey::ProjectCap(project_name.clone()))
+}
+
+pub fn write_project_cap(e: &Env, project_name: &String, cap: Option<i128>) {
+    write_persistent_cap(e, PolicyKey::ProjectCap(project_name.clone()), cap);
+}
+
+/// Credits issued through this module across all of a project's vintage
+/// batches that have not been reversed.
+pub fn read_project_issued(e: &Env, project_name: &String) -> i128 {
+    read_issued(e, &PolicyKey::ProjectIssued(project_name.clone()))
+}
+
+/// Credits issued by `verifier` that have not been reversed, whether still
+/// in circulation or retired.
+pub fn read_verifier_issued(e: &Env, verifier: &Address) -> i128 {
+    read_issued(e, &PolicyKey::VerifierIssued(verifier.clone()))
+}
+
+/// Adjusts the verifier's and the batch's project issued totals by `delta`:
+/// positive on mint, negative when an issuance is reversed.
+pub fn record_issued(e: &Env, verifier: &Address, batch: &CreditBatch, delta: i128) {
+    add_issued(e, PolicyKey::VerifierIssued(verifier.clone()), delta);
+    add_issued(e, PolicyKey::ProjectIssued(batch.project_name.clone()), delta);
+}
+
+/// Rejects a mint of `amount` that would take total supply plus retired
+/// credits, the verifier's issuance or the batch's project issuance past
+/// its cap.
+pub fn check_mint_caps(
+    e: &Env,
+    verifier: &Address,
+    batch: &CreditBatch,
+    amount: i128,
+) -> Result<(), Error> {
+    if let Some(cap) = read_global_cap(e) {
+        let token = token_client(e);
+        if token.total_supply() + token.total_retired() + amount > cap {
+            return Err(Error::GlobalCapExceeded);
+        }
+    }
+    if let Some(cap) = read_verifier_cap(e, verifier) {
+        if read_verifier_issued(e, verifier) + amount > cap {
+            return Err(Error::VerifierCapExceeded);
+        }
+    }
+    if let Some(cap) = read_project_cap(e, &batch.project_name) {
+        if read_project_issued(e, &batch.project_name) + amount > cap {
+            return Err(Error::ProjectCapExceeded);
+        }
+    }
+    Ok(())
+}
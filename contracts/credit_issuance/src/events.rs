@@ -0,0 +1,22 @@
+use soroban_sdk::{contracttype, symbol_short, Address, Env};
+
+/// Published alongside the SEP-41 `mint` events of one issuance, with the
+/// gross amount and how it was split between `to` and the buffer pool.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct IssuanceEvent {
+    pub to: Address,
+    pub batch_id: u32,
+    pub gross: i128,
+    pub net: i128,
+    pub buffered: i128,
+}
+
+impl IssuanceEvent {
+    pub fn publish(self, env: &Env) {
+        env.events().publish(
+            (symbol_short!("issued"), self.to, self.batch_id),
+            (self.gross, self.net, self.buffered),
+        );
+    }
+}
@@ -0,0 +1,84 @@
+use soroban_sdk::{contracttype, Address, Bytes, Env, Vec};
+
+use crate::index::{push_index, read_index_page, IndexKind};
+use crate::storage::{DataKey, BALANCE_BUMP_AMOUNT, BALANCE_LIFETIME_THRESHOLD};
+
+/// Provenance of a single mint, keyed by the report hash that backed it.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct IssuanceRecord {
+    pub report_hash: Bytes,
+    pub verifier: Address,
+    pub recipient: Address,
+    pub batch_id: u32,
+    /// Gross amount issued, including the share withheld for the buffer pool.
+    pub amount: i128,
+    /// Part of `amount` credited to the buffer pool instead of the recipient.
+    pub buffered: i128,
+    pub ledger: u32,
+    pub timestamp: u64,
+    /// Amount clawed back by `reverse_issuance` or cancelled from the buffer
+    /// pool; never exceeds `amount`.
+    pub reversed: i128,
+    /// Part of `reversed` taken from the buffer pool account.
+    pub buffer_reversed: i128,
+}
+
+/// Overwrites an existing issuance record without touching the indexes.
+pub fn update_issuance(e: &Env, record: &IssuanceRecord) {
+    let key = DataKey::Issuance(record.report_hash.clone());
+    e.storage().persistent().set(&key, record);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+pub fn write_issuance(e: &Env, record: &IssuanceRecord) {
+    update_issuance(e, record);
+
+    push_index(e, IndexKind::VerifierIssuances, &record.verifier, &record.report_hash);
+    push_index(e, IndexKind::RecipientIssuances, &record.recipient, &record.report_hash);
+}
+
+pub fn read_issuance(e: &Env, report_hash: &Bytes) -> Option<IssuanceRecord> {
+    e.storage()
+        .persistent()
+        .get(&DataKey::Issuance(report_hash.clone()))
+}
+
+/// Returns up to `limit` issuance records from `owner`'s index of `kind`,
+/// starting at position `cursor` in mint order.
+fn read_indexed_issuances(
+    e: &Env,
+    kind: IndexKind,
+    owner: &Address,
+    cursor: u32,
+    limit: u32,
+) -> Vec<IssuanceRecord> {
+    let hashes: Vec<Bytes> = read_index_page(e, kind, owner, cursor, limit);
+    let mut records = Vec::new(e);
+    for hash in hashes.iter() {
+        if let Some(record) = read_issuance(e, &hash) {
+            records.push_back(record);
+        }
+    }
+    records
+}
+
+pub fn read_verifier_issuances(
+    e: &Env,
+    verifier: &Address,
+    cursor: u32,
+    limit: u32,
+) -> Vec<IssuanceRecord> {
+    read_indexed_issuances(e, IndexKind::VerifierIssuances, verifier, cursor, limit)
+}
+
+pub fn read_recipient_issuances(
+    e: &Env,
+    recipient: &Address,
+    cursor: u32,
+    limit: u32,
+) -> Vec<IssuanceRecord> {
+    read_indexed_issuances(e, IndexKind::RecipientIssuances, recipient, cursor, limit)
+}
@@ -0,0 +1,658 @@
+#![no_std]
+
+mod buffer;
+mod caps;
+mod error;
+mod events;
+mod index;
+mod issuance;
+mod ratelimit;
+mod rbac;
+mod registry;
+mod storage;
+mod token;
+#[cfg(test)]
+mod test;
+
+pub use buffer::BufferConfig;
+pub use error::Error;
+pub use issuance::IssuanceRecord;
+pub use ratelimit::RateLimits;
+use soroban_sdk::{contract, contractimpl, Address, Bytes, BytesN, Env, String, Vec};
+
+use crate::buffer::{
+    effective_buffer_bps, read_buffer_config, split_mint, write_batch_buffer_bps,
+    write_buffer_config, MAX_BPS,
+};
+use crate::caps::{
+    check_mint_caps, read_global_cap, read_project_cap, read_project_issued, read_verifier_cap,
+    read_verifier_issued, record_issued, write_global_cap, write_project_cap, write_verifier_cap,
+};
+use crate::error::forward;
+use crate::events::IssuanceEvent;
+use crate::issuance::{
+    read_issuance, read_recipient_issuances, read_verifier_issuances, update_issuance,
+    write_issuance,
+};
+use crate::ratelimit::{
+    read_rate_limits, read_verifier_window_minted, read_window_minted, record_window_mint,
+    within_verifier_quota, write_rate_limits,
+};
+use crate::rbac::require_verifier;
+use crate::registry::require_registered_report;
+use crate::storage::{
+    extend_report_hash_ttl, is_initialized, is_report_hash_used, mark_report_hash_used,
+    read_co_approval_threshold, read_ex_ante, read_mint_approvals, read_token,
+    read_verifier_registry, requires_co_approval, write_co_approval_threshold,
+    write_linked_contracts, write_verifier_registry, INSTANCE_BUMP_AMOUNT,
+    INSTANCE_LIFETIME_THRESHOLD,
+};
+use crate::token::{
+    read_admin_authority, read_batch, require_not_blacklisted, require_not_paused, token_client,
+};
+
+/// Batch the token creates from the project metadata passed to its
+/// `initialize`. Plain `mint` issues into this batch.
+const DEFAULT_BATCH_ID: u32 = 1;
+
+fn check_nonnegative_amount(amount: i128) -> Result<(), Error> {
+    if amount < 0 {
+        Err(Error::NegativeAmount)
+    } else {
+        Ok(())
+    }
+}
+
+fn check_cap(cap: Option<i128>) -> Result<(), Error> {
+    match cap {
+        Some(cap) if cap < 0 => Err(Error::InvalidCap),
+        _ => Ok(()),
+    }
+}
+
+/// Books `amount` reversed credits of `issuance` against its verifier and
+/// project. `from_buffer` is the part taken from the buffer pool.
+fn book_reversal(
+    env: &Env,
+    issuance: &mut IssuanceRecord,
+    amount: i128,
+    from_buffer: i128,
+) -> Result<(), Error> {
+    let batch = read_batch(env, issuance.batch_id)?;
+    record_issued(env, &issuance.verifier, &batch, -amount);
+    issuance.reversed += amount;
+    issuance.buffer_reversed += from_buffer;
+    update_issuance(env, issuance);
+    Ok(())
+}
+
+/// Checks and books an issuance of `amount` against `report_hash`: the
+/// registry report, caps and quota, the provenance record, and the verifier
+/// and project totals. The credits themselves are issued by the caller with
+/// `credit_minted`. Returns whether the circuit breaker tripped.
+fn book_issuance(
+    env: &Env,
+    verifier: &Address,
+    recipient: &Address,
+    batch_id: u32,
+    amount: i128,
+    buffered: i128,
+    report_hash: Bytes,
+) -> Result<bool, Error> {
+    if is_report_hash_used(env, &report_hash) {
+        return Err(Error::ReportHashUsed);
+    }
+    require_registered_report(env, verifier, recipient, &report_hash)?;
+    let batch = read_batch(env, batch_id)?;
+    check_mint_caps(env, verifier, &batch, amount)?;
+    if !within_verifier_quota(env, verifier, amount) {
+        return Err(Error::VerifierQuotaExceeded);
+    }
+    mark_report_hash_used(env, &report_hash);
+
+    record_issued(env, verifier, &batch, amount);
+    let tripped = record_window_mint(env, verifier, amount);
+    write_issuance(
+        env,
+        &IssuanceRecord {
+            report_hash,
+            verifier: verifier.clone(),
+            recipient: recipient.clone(),
+            batch_id,
+            amount,
+            buffered,
+            ledger: env.ledger().sequence(),
+            timestamp: env.ledger().timestamp(),
+            reversed: 0,
+            buffer_reversed: 0,
+        },
+    );
+    Ok(tripped)
+}
+
+/// Issues `gross` credits of `batch_id` to `to` through the token, less the
+/// buffer pool share, which is issued to the buffer account. Publishes an
+/// issuance event with the gross, net and buffered amounts.
+fn credit_minted(env: &Env, to: &Address, batch_id: u32, gross: i128) -> Result<(), Error> {
+    let token = token_client(env);
+    let (net, buffered) = split_mint(env, batch_id, gross);
+    forward(token.try_issue(to, &batch_id, &net))?;
+
+    if buffered > 0 {
+        // `split_mint` only withholds when a buffer is configured.
+        let buffer = read_buffer_config(env).ok_or(Error::BufferNotConfigured)?;
+        forward(token.try_issue(&buffer.account, &batch_id, &buffered))?;
+    }
+
+    IssuanceEvent {
+        to: to.clone(),
+        batch_id,
+        gross,
+        net,
+        buffered,
+    }
+    .publish(env);
+    Ok(())
+}
+
+/// Pauses the token after a mint pushed the window volume past the breaker
+/// threshold. The mint itself still completes; returning an error instead
+/// would roll back the pause along with it.
+fn trip_circuit_breaker(env: &Env) -> Result<(), Error> {
+    forward(token_client(env).try_admin_pause(&env.current_contract_address()))
+}
+
+/// Creates `amount` credits of `batch_id` for `to` against `report_hash`.
+/// Callers handle authorization and the pause/blacklist checks.
+fn issue_credits(
+    env: &Env,
+    verifier: Address,
+    to: Address,
+    batch_id: u32,
+    amount: i128,
+    report_hash: Bytes,
+) -> Result<(), Error> {
+    let (_, buffered) = split_mint(env, batch_id, amount);
+    let tripped = book_issuance(env, &verifier, &to, batch_id, amount, buffered, report_hash)?;
+    credit_minted(env, &to, batch_id, amount)?;
+    if tripped {
+        trip_circuit_breaker(env)?;
+    }
+    Ok(())
+}
+
+/// Issuance module of the carbon credit token: mints against verifier
+/// reports, with supply caps, rate limits and the buffer pool, and books
+/// the reversals module's clawbacks. The token credits what this module
+/// issues.
+#[contract]
+pub struct CreditIssuance;
+
+#[contractimpl]
+impl CreditIssuance {
+    /// Binds the module to `token`, the VerifierRegistry that mints are
+    /// checked against, and the ex-ante and mint approvals modules that
+    /// issue through it. Must be signed by the token's SuperAdmin (or RBAC
+    /// contract in governance mode). Can only be called once.
+    pub fn initialize(
+        env: Env,
+        token: Address,
+        verifier_registry: Address,
+        ex_ante: Address,
+        mint_approvals: Address,
+    ) -> Result<(), Error> {
+        if is_initialized(&env) {
+            return Err(Error::AlreadyInitialized);
+        }
+
+        write_linked_contracts(&env, &token, &ex_ante, &mint_approvals);
+        read_admin_authority(&env).require_auth();
+        write_verifier_registry(&env, &verifier_registry);
+        Ok(())
+    }
+
+    /// Replaces the module code with the uploaded wasm `new_wasm_hash`.
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+        read_admin_authority(&env).require_auth();
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+    }
+
+    /// Marks the report hashes a first-release token had used. Called by the
+    /// token's `migrate` only.
+    pub fn import_report_hashes(env: Env, report_hashes: Vec<Bytes>) -> Result<(), Error> {
+        read_token(&env).require_auth();
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        for hash in report_hashes.iter() {
+            mark_report_hash_used(&env, &hash);
+        }
+        Ok(())
+    }
+
+    // ── Verifier registry (SuperAdmin only) ───────────────────────────────────
+
+    /// Replaces the VerifierRegistry contract that mints are checked
+    /// against. Every mint must match a report the calling verifier
+    /// submitted to the registry for the recipient.
+    pub fn set_verifier_registry(env: Env, registry: Address) -> Result<(), Error> {
+        let authority = read_admin_authority(&env);
+        authority.require_auth();
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        write_verifier_registry(&env, &registry);
+        Ok(())
+    }
+
+    /// Returns the VerifierRegistry contract mints are checked against.
+    pub fn verifier_registry(env: Env) -> Address {
+        read_verifier_registry(&env)
+    }
+
+    // ── Issuance reversal (reversals module only) ─────────────────────────────
+
+    /// Books `amount` credits the reversals module clawed back from the
+    /// issuance backed by `report_hash`, `from_buffer` of them from the
+    /// buffer pool account, against the issuance record and the verifier
+    /// and project totals. Reversals module only.
+    pub fn record_reversal(
+        env: Env,
+        report_hash: Bytes,
+        amount: i128,
+        from_buffer: i128,
+    ) -> Result<(), Error> {
+        token_client(&env).modules().reversals.require_auth();
+        check_nonnegative_amount(amount)?;
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        let mut issuance = read_issuance(&env, &report_hash).ok_or(Error::IssuanceNotFound)?;
+        if amount > issuance.amount - issuance.reversed {
+            return Err(Error::ExceedsUnreversedAmount);
+        }
+        book_reversal(&env, &mut issuance, amount, from_buffer)
+    }
+
+    // ── Supply caps (SuperAdmin only) ─────────────────────────────────────────
+
+    /// Caps total supply plus retired credits. `None` removes the cap.
+    pub fn set_global_cap(env: Env, cap: Option<i128>) -> Result<(), Error> {
+        let authority = read_admin_authority(&env);
+        authority.require_auth();
+        check_cap(cap)?;
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        write_global_cap(&env, cap);
+        Ok(())
+    }
+
+    /// Caps the unreversed issuance of a single verifier. `None` removes the cap.
+    pub fn set_verifier_cap(env: Env, verifier: Address, cap: Option<i128>) -> Result<(), Error> {
+        let authority = read_admin_authority(&env);
+        authority.require_auth();
+        check_cap(cap)?;
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        write_verifier_cap(&env, &verifier, cap);
+        Ok(())
+    }
+
+    /// Caps the unreversed issuance of a project, summed across all of its
+    /// vintage batches. `None` removes the cap.
+    pub fn set_project_cap(
+        env: Env,
+        project_name: String,
+        cap: Option<i128>,
+    ) -> Result<(), Error> {
+        let authority = read_admin_authority(&env);
+        authority.require_auth();
+        check_cap(cap)?;
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        write_project_cap(&env, &project_name, cap);
+        Ok(())
+    }
+
+    pub fn global_cap(env: Env) -> Option<i128> {
+        read_global_cap(&env)
+    }
+
+    pub fn verifier_cap(env: Env, verifier: Address) -> Option<i128> {
+        read_verifier_cap(&env, &verifier)
+    }
+
+    pub fn project_cap(env: Env, project_name: String) -> Option<i128> {
+        read_project_cap(&env, &project_name)
+    }
+
+    /// Returns the unreversed issuance of a project across its vintages.
+    pub fn project_issued(env: Env, project_name: String) -> i128 {
+        read_project_issued(&env, &project_name)
+    }
+
+    /// Returns the credits `verifier` has issued that were not reversed.
+    pub fn verifier_issued(env: Env, verifier: Address) -> i128 {
+        read_verifier_issued(&env, &verifier)
+    }
+
+    // ── Rate limits / circuit breaker (SuperAdmin only) ───────────────────────
+
+    /// Limits minting over a rolling window of `window_ledgers` ledgers: each
+    /// verifier may mint at most `verifier_quota`, and once the aggregate
+    /// volume exceeds `breaker_threshold` the token is paused until
+    /// `admin_unpause`. A limit of 0 is disabled; a window of 0 removes
+    /// rate limiting altogether.
+    pub fn set_rate_limits(
+        env: Env,
+        window_ledgers: u32,
+        verifier_quota: i128,
+        breaker_threshold: i128,
+    ) -> Result<(), Error> {
+        let authority = read_admin_authority(&env);
+        authority.require_auth();
+        if verifier_quota < 0 || breaker_threshold < 0 {
+            return Err(Error::InvalidRateLimits);
+        }
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        let limits = if window_ledgers == 0 {
+            None
+        } else {
+            Some(RateLimits {
+                window_ledgers,
+                verifier_quota,
+                breaker_threshold,
+            })
+        };
+        write_rate_limits(&env, limits);
+        Ok(())
+    }
+
+    pub fn rate_limits(env: Env) -> Option<RateLimits> {
+        read_rate_limits(&env)
+    }
+
+    /// Returns what `verifier` has minted within the current window.
+    pub fn verifier_window_minted(env: Env, verifier: Address) -> i128 {
+        read_verifier_window_minted(&env, &verifier)
+    }
+
+    /// Returns the aggregate mint volume within the current window.
+    pub fn window_minted(env: Env) -> i128 {
+        read_window_minted(&env)
+    }
+
+    // ── Buffer pool (SuperAdmin only) ─────────────────────────────────────────
+
+    /// Sets the account that receives withheld credits and the share of
+    /// every mint withheld, in basis points. Batches may override the share
+    /// with `set_batch_buffer_bps`.
+    pub fn set_buffer_config(env: Env, account: Address, bps: u32) -> Result<(), Error> {
+        let authority = read_admin_authority(&env);
+        authority.require_auth();
+        if bps > MAX_BPS {
+            return Err(Error::InvalidBasisPoints);
+        }
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        write_buffer_config(&env, &BufferConfig { account, bps });
+        Ok(())
+    }
+
+    /// Overrides the withheld share for mints into one project/vintage batch.
+    pub fn set_batch_buffer_bps(env: Env, batch_id: u32, bps: u32) -> Result<(), Error> {
+        let authority = read_admin_authority(&env);
+        authority.require_auth();
+        if bps > MAX_BPS {
+            return Err(Error::InvalidBasisPoints);
+        }
+        if read_buffer_config(&env).is_none() {
+            return Err(Error::BufferNotConfigured);
+        }
+        read_batch(&env, batch_id)?;
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        write_batch_buffer_bps(&env, batch_id, bps);
+        Ok(())
+    }
+
+    /// Returns the buffer pool account and default withheld share, if set.
+    pub fn buffer_config(env: Env) -> Option<BufferConfig> {
+        read_buffer_config(&env)
+    }
+
+    /// Returns the share withheld from mints into `batch_id`, in basis points.
+    pub fn buffer_bps(env: Env, batch_id: u32) -> u32 {
+        effective_buffer_bps(&env, batch_id)
+    }
+
+    // ── Four-eyes mints ───────────────────────────────────────────────────────
+
+    /// Sets the mint size above which a second verifier or an RBAC Admin
+    /// must co-approve through the mint approvals module. `None` lets every
+    /// verifier mint alone. SuperAdmin only.
+    pub fn set_co_approval_threshold(env: Env, threshold: Option<i128>) -> Result<(), Error> {
+        let authority = read_admin_authority(&env);
+        authority.require_auth();
+        check_cap(threshold)?;
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        write_co_approval_threshold(&env, threshold);
+        Ok(())
+    }
+
+    pub fn co_approval_threshold(env: Env) -> Option<i128> {
+        read_co_approval_threshold(&env)
+    }
+
+    // ── Minting ───────────────────────────────────────────────────────────────
+
+    /// Mints into the default batch created at initialization.
+    pub fn mint(env: Env, verifier: Address, to: Address, amount: i128, report_hash: Bytes) -> Result<(), Error> {
+        Self::mint_to_batch(env, verifier, to, DEFAULT_BATCH_ID, amount, report_hash)
+    }
+
+    /// Mints credits of a specific project/vintage batch.
+    pub fn mint_to_batch(
+        env: Env,
+        verifier: Address,
+        to: Address,
+        batch_id: u32,
+        amount: i128,
+        report_hash: Bytes,
+    ) -> Result<(), Error> {
+        check_nonnegative_amount(amount)?;
+        require_not_paused(&env)?;
+        require_not_blacklisted(&env, &verifier)?;
+        require_not_blacklisted(&env, &to)?;
+
+        require_verifier(&env, &verifier)?;
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        if requires_co_approval(&env, amount) {
+            return Err(Error::CoApprovalRequired);
+        }
+        issue_credits(&env, verifier, to, batch_id, amount, report_hash)
+    }
+
+    /// Mints into the default batch for many recipients at once, each entry
+    /// being `(to, amount, report_hash)`. The verifier is authorized once;
+    /// every entry is otherwise checked as a separate `mint`, and any failure
+    /// reverts the whole call.
+    pub fn mint_batch(
+        env: Env,
+        verifier: Address,
+        mints: Vec<(Address, i128, Bytes)>,
+    ) -> Result<(), Error> {
+        require_not_paused(&env)?;
+        require_not_blacklisted(&env, &verifier)?;
+
+        require_verifier(&env, &verifier)?;
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        let mut tripped = false;
+        for (to, amount, report_hash) in mints.iter() {
+            check_nonnegative_amount(amount)?;
+            require_not_blacklisted(&env, &to)?;
+            if requires_co_approval(&env, amount) {
+                return Err(Error::CoApprovalRequired);
+            }
+
+            let (_, buffered) = split_mint(&env, DEFAULT_BATCH_ID, amount);
+            tripped |= book_issuance(
+                &env,
+                &verifier,
+                &to,
+                DEFAULT_BATCH_ID,
+                amount,
+                buffered,
+                report_hash,
+            )?;
+            credit_minted(&env, &to, DEFAULT_BATCH_ID, amount)?;
+        }
+
+        if tripped {
+            trip_circuit_breaker(&env)?;
+        }
+        Ok(())
+    }
+
+    /// Mints a co-approved request as if `proposer` had minted it directly,
+    /// without the co-approval threshold. Mint approvals module only; it
+    /// checks the approver and the proposer's role.
+    pub fn issue_approved(
+        env: Env,
+        proposer: Address,
+        to: Address,
+        batch_id: u32,
+        amount: i128,
+        report_hash: Bytes,
+    ) -> Result<(), Error> {
+        read_mint_approvals(&env).require_auth();
+        check_nonnegative_amount(amount)?;
+        require_not_paused(&env)?;
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        issue_credits(&env, proposer, to, batch_id, amount, report_hash)
+    }
+
+    /// Issues the conversion of ex-ante credits: one issuance against
+    /// `report_hash` for `farmer`, shared out as `(holder, amount)` entries.
+    /// Ex-ante module only; it checks the verifier and the tranche.
+    pub fn issue_ex_ante(
+        env: Env,
+        verifier: Address,
+        farmer: Address,
+        batch_id: u32,
+        shares: Vec<(Address, i128)>,
+        report_hash: Bytes,
+    ) -> Result<(), Error> {
+        read_ex_ante(&env).require_auth();
+        require_not_paused(&env)?;
+
+        let mut amount = 0;
+        let mut buffered = 0;
+        for (_, share) in shares.iter() {
+            check_nonnegative_amount(share)?;
+            amount += share;
+            buffered += split_mint(&env, batch_id, share).1;
+        }
+        if requires_co_approval(&env, amount) {
+            return Err(Error::CoApprovalRequired);
+        }
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        let tripped =
+            book_issuance(&env, &verifier, &farmer, batch_id, amount, buffered, report_hash)?;
+        for (holder, share) in shares.iter() {
+            if share > 0 {
+                credit_minted(&env, &holder, batch_id, share)?;
+            }
+        }
+
+        if tripped {
+            trip_circuit_breaker(&env)?;
+        }
+        Ok(())
+    }
+
+    // ── Issuance records ──────────────────────────────────────────────────────
+
+    /// Returns whether `report_hash` has already backed a mint.
+    pub fn is_report_hash_used(env: Env, report_hash: Bytes) -> bool {
+        is_report_hash_used(&env, &report_hash)
+    }
+
+    /// Keeps a used report hash and its issuance record from being archived.
+    /// Anyone may pay for this; returns `false` for an unknown hash.
+    pub fn extend_report_hash_ttl(env: Env, report_hash: Bytes) -> bool {
+        extend_report_hash_ttl(&env, &report_hash)
+    }
+
+    /// Returns the mint that consumed `report_hash`, if any.
+    pub fn get_issuance(env: Env, report_hash: Bytes) -> Option<IssuanceRecord> {
+        read_issuance(&env, &report_hash)
+    }
+
+    /// Returns up to `limit` mints performed by `verifier`, starting at
+    /// position `cursor` (oldest first).
+    pub fn issuances_by_verifier(
+        env: Env,
+        verifier: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> Vec<IssuanceRecord> {
+        read_verifier_issuances(&env, &verifier, cursor, limit)
+    }
+
+    /// Returns up to `limit` mints received by `recipient`, starting at
+    /// position `cursor` (oldest first).
+    pub fn issuances_of(
+        env: Env,
+        recipient: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> Vec<IssuanceRecord> {
+        read_recipient_issuances(&env, &recipient, cursor, limit)
+    }
+}
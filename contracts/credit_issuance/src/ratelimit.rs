@@ -0,0 +1,127 @@
+use soroban_sdk::{contracttype, Address, Env, Vec};
+
+use crate::storage::{PolicyKey, BALANCE_BUMP_AMOUNT, BALANCE_LIFETIME_THRESHOLD};
+
+/// Number of buckets a window is split into. Volumes are tracked per bucket,
+/// so the window rolls forward one bucket (1/12 of its length) at a time and
+/// each tracked key holds at most this many entries.
+const WINDOW_BUCKETS: u32 = 12;
+
+/// Mint rate limits over a rolling window of `window_ledgers` ledgers.
+/// A `verifier_quota` or `breaker_threshold` of 0 disables that limit.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct RateLimits {
+    pub window_ledgers: u32,
+    /// Most a single verifier may mint within the window.
+    pub verifier_quota: i128,
+    /// Aggregate mint volume within the window that pauses the token.
+    pub breaker_threshold: i128,
+}
+
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct WindowBucket {
+    pub bucket: u32,
+    pub amount: i128,
+}
+
+pub fn read_rate_limits(e: &Env) -> Option<RateLimits> {
+    e.storage().instance().get(&PolicyKey::RateLimits)
+}
+
+pub fn write_rate_limits(e: &Env, limits: Option<RateLimits>) {
+    match limits {
+        Some(limits) => e.storage().instance().set(&PolicyKey::RateLimits, &limits),
+        None => e.storage().instance().remove(&PolicyKey::RateLimits),
+    }
+}
+
+fn bucket_len(limits: &RateLimits) -> u32 {
+    (limits.window_ledgers / WINDOW_BUCKETS).max(1)
+}
+
+fn current_bucket(e: &Env, limits: &RateLimits) -> u32 {
+    e.ledger().sequence() / bucket_len(limits)
+}
+
+/// Buckets of `key` still inside the window, oldest first.
+fn read_live_buckets(e: &Env, key: &PolicyKey, limits: &RateLimits) -> Vec<WindowBucket> {
+    let oldest = current_bucket(e, limits).saturating_sub(WINDOW_BUCKETS - 1);
+    let mut live = Vec::new(e);
+    let buckets: Vec<WindowBucket> = e
+        .storage()
+        .persistent()
+        .get(key)
+        .unwrap_or_else(|| Vec::new(e));
+    for b in buckets.iter() {
+        if b.bucket >= oldest {
+            live.push_back(b);
+        }
+    }
+    live
+}
+
+fn read_window_total(e: &Env, key: &PolicyKey, limits: &RateLimits) -> i128 {
+    read_live_buckets(e, key, limits)
+        .iter()
+        .map(|b| b.amount)
+        .sum()
+}
+
+fn add_to_window(e: &Env, key: PolicyKey, limits: &RateLimits, amount: i128) -> i128 {
+    let bucket = current_bucket(e, limits);
+    let mut buckets = read_live_buckets(e, &key, limits);
+    match buckets.last() {
+        Some(mut last) if last.bucket == bucket => {
+            last.amount += amount;
+            buckets.set(buckets.len() - 1, last);
+        }
+        _ => buckets.push_back(WindowBucket { bucket, amount }),
+    }
+    let total = buckets.iter().map(|b| b.amount).sum();
+
+    e.storage().persistent().set(&key, &buckets);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+    total
+}
+
+pub fn read_verifier_window_minted(e: &Env, verifier: &Address) -> i128 {
+    match read_rate_limits(e) {
+        Some(limits) => read_window_total(e, &PolicyKey::VerifierWindow(verifier.clone()), &limits),
+        None => 0,
+    }
+}
+
+pub fn read_window_minted(e: &Env) -> i128 {
+    match read_rate_limits(e) {
+        Some(limits) => read_window_total(e, &PolicyKey::MintWindow, &limits),
+        None => 0,
+    }
+}
+
+/// Returns `false` if minting `amount` would take `verifier` past its quota
+/// for the current window.
+pub fn within_verifier_quota(e: &Env, verifier: &Address, amount: i128) -> bool {
+    match read_rate_limits(e) {
+        Some(limits) if limits.verifier_quota > 0 => {
+            let key = PolicyKey::VerifierWindow(verifier.clone());
+            read_window_total(e, &key, &limits) + amount <= limits.verifier_quota
+        }
+        _ => true,
+    }
+}
+
+/// Adds a mint to the verifier and aggregate windows. Returns `true` when
+/// the aggregate volume in the window now exceeds the breaker threshold.
+pub fn record_window_mint(e: &Env, verifier: &Address, amount: i128) -> bool {
+    let limits = match read_rate_limits(e) {
+        Some(limits) => limits,
+        None => return false,
+    };
+    add_to_window(e, PolicyKey::VerifierWindow(verifier.clone()), &limits, amount);
+    let total = add_to_window(e, PolicyKey::MintWindow, &limits, amount);
+    limits.breaker_threshold > 0 && total > limits.breaker_threshold
+}
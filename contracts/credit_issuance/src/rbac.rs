@@ -0,0 +1,33 @@
+use soroban_sdk::{contractclient, Address, Env, String};
+
+use crate::error::Error;
+use crate::token::token_client;
+
+/// The role string that grants minting authority.
+/// Must exactly match the role name registered in the RBAC contract.
+pub const VERIFIER_ROLE: &str = "Verifier";
+
+/// Cross-contract client interface for the token's RBAC contract.
+#[contractclient(name = "RbacContractClient")]
+#[allow(dead_code)]
+pub trait RbacContractInterface {
+    /// Returns `true` when `address` holds `role` in the RBAC registry.
+    fn has_role(env: Env, address: Address, role: String) -> bool;
+}
+
+/// Asserts that `caller` both:
+///   1. Signed the current transaction (`require_auth`), and
+///   2. Holds the `Verifier` role in the token's RBAC contract.
+///
+/// Returns `Error::Unauthorized` if `caller` lacks the role, so the calling
+/// function can bail out before any state mutation.
+pub fn require_verifier(e: &Env, caller: &Address) -> Result<(), Error> {
+    // Ensure the address actually authorised this invocation.
+    caller.require_auth();
+
+    let client = RbacContractClient::new(e, &token_client(e).rbac_contract());
+    if !client.has_role(caller, &String::from_str(e, VERIFIER_ROLE)) {
+        return Err(Error::Unauthorized);
+    }
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use soroban_sdk::{contractclient, Address, Bytes, Env, String};
+
+use crate::error::Error;
+use crate::storage::read_verifier_registry;
+
+/// Longest report hash the registry check will look up. Registry hashes are
+/// hex or base64 digests, well under this.
+const MAX_REPORT_HASH_LEN: usize = 128;
+
+/// Cross-contract client interface for the external VerifierRegistry
+/// contract, which records the MRV reports verifiers submit for farmers.
+#[contractclient(name = "VerifierRegistryClient")]
+#[allow(dead_code)]
+pub trait VerifierRegistryInterface {
+    /// Returns `true` when `verifier` is registered and active.
+    fn is_verifier_active(env: Env, verifier: Address) -> bool;
+
+    /// Returns the report submitted with `metric_hash` as
+    /// `(verifier, farmer, submission_ledger)`.
+    fn get_report(env: Env, metric_hash: String) -> Option<(Address, Address, u32)>;
+}
+
+/// Checks that the registry holds a report with `report_hash`, submitted by
+/// `verifier` for `farmer`, and that `verifier` is still active. Reports are
+/// looked up by hash, so a farmer's earlier reports stay mintable after a
+/// newer one is submitted.
+pub fn require_registered_report(
+    e: &Env,
+    verifier: &Address,
+    farmer: &Address,
+    report_hash: &Bytes,
+) -> Result<(), Error> {
+    let client = VerifierRegistryClient::new(e, &read_verifier_registry(e));
+
+    let len = report_hash.len() as usize;
+    if len > MAX_REPORT_HASH_LEN {
+        return Err(Error::ReportNotFound);
+    }
+    let mut buf = [0u8; MAX_REPORT_HASH_LEN];
+    report_hash.copy_into_slice(&mut buf[..len]);
+    let metric_hash = String::from_bytes(e, &buf[..len]);
+
+    let (submitted_by, report_farmer, _) = client
+        .get_report(&metric_hash)
+        .ok_or(Error::ReportNotFound)?;
+    if submitted_by != *verifier || report_farmer != *farmer {
+        return Err(Error::ReportMismatch);
+    }
+    if !client.is_verifier_active(verifier) {
+        return Err(Error::VerifierInactive);
+    }
+    Ok(())
+}
@@ -0,0 +1,157 @@
+use soroban_sdk::{contracttype, Address, Bytes, Env, String};
+
+// ── TTL Constants ──────────────────────────────────────────────────────────────
+pub const INSTANCE_LIFETIME_THRESHOLD: u32 = 17280; // ~1 day
+pub const INSTANCE_BUMP_AMOUNT: u32 = 518400; // ~30 days
+
+pub const BALANCE_LIFETIME_THRESHOLD: u32 = 17280; // ~1 day
+pub const BALANCE_BUMP_AMOUNT: u32 = 518400; // ~30 days
+
+// ── Storage Keys ───────────────────────────────────────────────────────────────
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    // The token this module issues, and the modules that issue through it
+    Token,
+    ExAnte,
+    MintApprovals,
+
+    // Mints are checked against the VerifierRegistry
+    VerifierRegistry,
+    UsedReportHash(Bytes),
+
+    // Issuance provenance, keyed by report hash; per-account lists are in
+    // `index::IndexKey`
+    Issuance(Bytes),
+}
+
+/// Keys for issuance policy settings.
+#[derive(Clone)]
+#[contracttype]
+pub enum PolicyKey {
+    // Non-permanence buffer pool
+    Buffer,
+    BatchBufferBps(u32),
+
+    // Supply caps
+    GlobalCap,
+    VerifierCap(Address),
+    ProjectCap(String),
+    VerifierIssued(Address),
+    ProjectIssued(String),
+
+    // Rolling-window rate limits
+    RateLimits,
+    VerifierWindow(Address),
+    MintWindow,
+
+    // Four-eyes mints
+    CoApprovalThreshold,
+}
+
+// ── Linked contracts ───────────────────────────────────────────────────────────
+pub fn is_initialized(e: &Env) -> bool {
+    e.storage().instance().has(&DataKey::Token)
+}
+
+pub fn read_token(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::Token)
+        .expect("token not set: was initialize() called?")
+}
+
+pub fn read_ex_ante(e: &Env) -> Address {
+    e.storage().instance().get(&DataKey::ExAnte).unwrap()
+}
+
+pub fn read_mint_approvals(e: &Env) -> Address {
+    e.storage().instance().get(&DataKey::MintApprovals).unwrap()
+}
+
+pub fn write_linked_contracts(e: &Env, token: &Address, ex_ante: &Address, mint_approvals: &Address) {
+    e.storage().instance().set(&DataKey::Token, token);
+    e.storage().instance().set(&DataKey::ExAnte, ex_ante);
+    e.storage()
+        .instance()
+        .set(&DataKey::MintApprovals, mint_approvals);
+}
+
+// ── Verifier registry ──────────────────────────────────────────────────────────
+pub fn read_verifier_registry(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::VerifierRegistry)
+        .expect("verifier registry not set: was initialize() called?")
+}
+
+pub fn write_verifier_registry(e: &Env, registry: &Address) {
+    e.storage()
+        .instance()
+        .set(&DataKey::VerifierRegistry, registry);
+}
+
+/// Report hashes live in persistent storage, one entry per hash, so the
+/// instance entry loaded on every call does not grow with issuance volume.
+///
+/// An expired entry is archived rather than deleted. Any transaction that
+/// touches an archived key fails until the entry is restored (a
+/// `RestoreFootprintOp`), so an archived hash can never be mistaken for an
+/// unused one. `extend_report_hash_ttl` lets anyone keep a hash live.
+pub fn is_report_hash_used(e: &Env, hash: &Bytes) -> bool {
+    let key = DataKey::UsedReportHash(hash.clone());
+    if !e.storage().persistent().has(&key) {
+        return false;
+    }
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+    true
+}
+
+pub fn mark_report_hash_used(e: &Env, hash: &Bytes) {
+    let key = DataKey::UsedReportHash(hash.clone());
+    e.storage().persistent().set(&key, &true);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+/// Extends the TTL of a used report hash (and its issuance record, if any).
+/// Returns `false` if the hash is not stored.
+pub fn extend_report_hash_ttl(e: &Env, hash: &Bytes) -> bool {
+    let key = DataKey::UsedReportHash(hash.clone());
+    if !e.storage().persistent().has(&key) {
+        return false;
+    }
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+    let issuance_key = DataKey::Issuance(hash.clone());
+    if e.storage().persistent().has(&issuance_key) {
+        e.storage()
+            .persistent()
+            .extend_ttl(&issuance_key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+    }
+    true
+}
+
+// ── Co-approval ────────────────────────────────────────────────────────────────
+pub fn read_co_approval_threshold(e: &Env) -> Option<i128> {
+    e.storage().instance().get(&PolicyKey::CoApprovalThreshold)
+}
+
+pub fn write_co_approval_threshold(e: &Env, threshold: Option<i128>) {
+    match threshold {
+        Some(threshold) => e
+            .storage()
+            .instance()
+            .set(&PolicyKey::CoApprovalThreshold, &threshold),
+        None => e.storage().instance().remove(&PolicyKey::CoApprovalThreshold),
+    }
+}
+
+/// Whether a mint of `amount` must go through the mint approvals module.
+pub fn requires_co_approval(e: &Env, amount: i128) -> bool {
+    matches!(read_co_approval_threshold(e), Some(threshold) if amount > threshold)
+}
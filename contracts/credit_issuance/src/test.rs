@@ -0,0 +1,717 @@
+#![cfg(test)]
+
+use crate::index::{read_index_len, IndexKey, IndexKind};
+use crate::storage::DataKey;
+use crate::{CreditIssuance, CreditIssuanceClient, Error};
+use carbon_credit_token::{CarbonCreditToken, CarbonCreditTokenClient, Modules, DEFAULT_BATCH_ID};
+use credit_compliance::{CreditCompliance, CreditComplianceClient};
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{Address as _, Events as _, Ledger},
+    symbol_short, vec, Address, Bytes, Env, IntoVal, String,
+};
+
+#[contract]
+pub struct MockRbacContract;
+
+#[contractimpl]
+impl MockRbacContract {
+    pub fn has_role(env: Env, address: Address, role: String) -> bool {
+        // Simplest mock: everyone has every role unless denied
+        !env.storage().instance().has(&(address, role))
+    }
+
+    pub fn deny_role(env: Env, address: Address, role: String) {
+        env.storage().instance().set(&(address, role), &true);
+    }
+
+    pub fn get_role(_env: Env, _address: Address) -> u32 {
+        // Everyone is a Trader
+        2
+    }
+}
+
+#[contract]
+pub struct MockComplianceHook;
+
+#[contractimpl]
+impl MockComplianceHook {
+    pub fn can_transfer(_env: Env, _from: Address, _to: Address, amount: i128) -> bool {
+        amount <= 500
+    }
+}
+
+#[contract]
+pub struct MockVerifierRegistry;
+
+#[contractimpl]
+impl MockVerifierRegistry {
+    pub fn submit_report_hash(env: Env, verifier: Address, farmer: Address, metric_hash: String) {
+        let report = (verifier, farmer, env.ledger().sequence());
+        env.storage().instance().set(&metric_hash, &report);
+    }
+
+    pub fn set_active(env: Env, verifier: Address, active: bool) {
+        env.storage().instance().set(&(verifier,), &active);
+    }
+
+    pub fn is_verifier_active(env: Env, verifier: Address) -> bool {
+        env.storage().instance().get(&(verifier,)).unwrap_or(true)
+    }
+
+    pub fn get_report(env: Env, metric_hash: String) -> Option<(Address, Address, u32)> {
+        env.storage().instance().get(&metric_hash)
+    }
+}
+
+/// Files a report with `hash` from `verifier` for `farmer` in the module's
+/// registry, so a mint with that hash passes the registry check.
+fn submit_report(
+    issuance: &CreditIssuanceClient,
+    verifier: &Address,
+    farmer: &Address,
+    hash: &Bytes,
+) {
+    extern crate std;
+
+    let registry = MockVerifierRegistryClient::new(&issuance.env, &issuance.verifier_registry());
+    let hash: std::vec::Vec<u8> = hash.iter().collect();
+    registry.submit_report_hash(verifier, farmer, &String::from_bytes(&issuance.env, &hash));
+}
+
+/// Registers a token bound to this module and a real compliance module, and
+/// returns `(env, token, issuance, admin, verifier, user)`. The retirement,
+/// permits, reversals, ex-ante and mint approvals modules are bare
+/// addresses; with every auth mocked, their calls are made directly.
+fn setup_env<'a>() -> (
+    Env,
+    CarbonCreditTokenClient<'a>,
+    CreditIssuanceClient<'a>,
+    Address,
+    Address,
+    Address,
+) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let rbac_id = env.register_contract(None, MockRbacContract);
+    let registry_id = env.register_contract(None, MockVerifierRegistry);
+    let compliance_id = env.register_contract(None, CreditCompliance);
+    let issuance_id = env.register_contract(None, CreditIssuance);
+    let token_id = env.register_contract(None, CarbonCreditToken);
+    let token = CarbonCreditTokenClient::new(&env, &token_id);
+
+    token.initialize(
+        &admin,
+        &rbac_id,
+        &Modules {
+            issuance: issuance_id.clone(),
+            retirement: Address::generate(&env),
+            compliance: compliance_id.clone(),
+            permits: Address::generate(&env),
+            reversals: Address::generate(&env),
+        },
+        &String::from_str(&env, "Carbon Credit Token"),
+        &String::from_str(&env, "CCT"),
+        &0u32,
+        &String::from_str(&env, "Amazon Reforestation"),
+        &String::from_str(&env, "2023"),
+        &String::from_str(&env, "Brazil"),
+        &String::from_str(&env, "https://farmcredit.xyz/amazon-1"),
+    );
+    CreditComplianceClient::new(&env, &compliance_id).initialize(&token_id);
+    let issuance = CreditIssuanceClient::new(&env, &issuance_id);
+    let ex_ante = Address::generate(&env);
+    let mint_approvals = Address::generate(&env);
+    issuance.initialize(&token_id, &registry_id, &ex_ante, &mint_approvals);
+
+    let verifier = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    (env, token, issuance, admin, verifier, user)
+}
+
+fn create_agroforestry_batch(env: &Env, token: &CarbonCreditTokenClient) -> u32 {
+    token.create_batch(
+        &String::from_str(env, "Kenya Agroforestry"),
+        &String::from_str(env, "2024"),
+        &String::from_str(env, "Kenya"),
+        &String::from_str(env, "https://farmcredit.xyz/kenya-1"),
+    )
+}
+
+#[test]
+fn test_initialize_binds_token_once() {
+    let (env, token, issuance, _, _, _) = setup_env();
+
+    let registry = issuance.verifier_registry();
+    let other = Address::generate(&env);
+    let result = issuance.try_initialize(&token.address, &registry, &other, &other);
+    assert_eq!(result, Err(Ok(Error::AlreadyInitialized)));
+}
+
+#[test]
+fn test_mint_to_batch() {
+    let (env, token, issuance, _, verifier, user) = setup_env();
+    let batch_id = create_agroforestry_batch(&env, &token);
+
+    submit_report(&issuance, &verifier, &user, &Bytes::from_slice(&env, b"b1"));
+    issuance.mint(&verifier, &user, &100, &Bytes::from_slice(&env, b"b1"));
+    submit_report(&issuance, &verifier, &user, &Bytes::from_slice(&env, b"b2"));
+    issuance.mint_to_batch(&verifier, &user, &batch_id, &250, &Bytes::from_slice(&env, b"b2"));
+
+    assert_eq!(token.balance(&user), 350);
+    assert_eq!(token.batch_balance(&user, &DEFAULT_BATCH_ID), 100);
+    assert_eq!(token.batch_balance(&user, &batch_id), 250);
+    assert_eq!(token.get_batch(&batch_id).unwrap().issued, 250);
+
+    submit_report(&issuance, &verifier, &user, &Bytes::from_slice(&env, b"b3"));
+    let hash = Bytes::from_slice(&env, b"b3");
+    let result = issuance.try_mint_to_batch(&verifier, &user, &99, &10, &hash);
+    assert_eq!(result, Err(Ok(Error::BatchNotFound)));
+}
+
+#[test]
+fn test_mint_checked_by_compliance_module() {
+    let (env, token, issuance, _, verifier, user) = setup_env();
+    let compliance = CreditComplianceClient::new(&env, &token.modules().compliance);
+    let hash = Bytes::from_slice(&env, b"p1");
+
+    compliance.blacklist(&user);
+    submit_report(&issuance, &verifier, &user, &hash);
+    let result = issuance.try_mint(&verifier, &user, &100, &hash);
+    assert_eq!(result, Err(Ok(Error::Blacklisted)));
+    compliance.unblacklist(&user);
+
+    // The token's compliance checks are passed on with the token's code.
+    let hook_id = env.register_contract(None, MockComplianceHook);
+    compliance.set_compliance_hook(&Some(hook_id));
+    let result = issuance.try_mint(&verifier, &user, &501, &hash);
+    assert_eq!(result, Err(Ok(Error::TransferNotCompliant)));
+    assert!(!issuance.is_report_hash_used(&hash));
+    issuance.mint(&verifier, &user, &500, &hash);
+    assert_eq!(token.balance(&user), 500);
+}
+
+#[test]
+fn test_mint_requires_verifier_role() {
+    let (env, token, issuance, _, verifier, user) = setup_env();
+    let rbac = MockRbacContractClient::new(&env, &token.rbac_contract());
+    let hash = Bytes::from_slice(&env, b"v1");
+
+    rbac.deny_role(&verifier, &String::from_str(&env, "Verifier"));
+    submit_report(&issuance, &verifier, &user, &hash);
+    let result = issuance.try_mint(&verifier, &user, &100, &hash);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    assert_eq!(token.total_supply(), 0);
+}
+
+// ── Verifier registry binding ─────────────────────────────────────────────────
+
+fn registry_of<'a>(issuance: &CreditIssuanceClient) -> MockVerifierRegistryClient<'a> {
+    MockVerifierRegistryClient::new(&issuance.env, &issuance.verifier_registry())
+}
+
+#[test]
+fn test_mint_matching_registry_report() {
+    let (env, token, issuance, _, verifier, farmer) = setup_env();
+    let registry = registry_of(&issuance);
+
+    registry.submit_report_hash(&verifier, &farmer, &String::from_str(&env, "mrv-0001"));
+    issuance.mint(&verifier, &farmer, &500, &Bytes::from_slice(&env, b"mrv-0001"));
+    assert_eq!(token.balance(&farmer), 500);
+}
+
+#[test]
+fn test_mint_earlier_report_after_newer_submitted() {
+    let (env, token, issuance, _, verifier, farmer) = setup_env();
+    let registry = registry_of(&issuance);
+
+    registry.submit_report_hash(&verifier, &farmer, &String::from_str(&env, "mrv-0001"));
+    registry.submit_report_hash(&verifier, &farmer, &String::from_str(&env, "mrv-0002"));
+
+    issuance.mint(&verifier, &farmer, &500, &Bytes::from_slice(&env, b"mrv-0001"));
+    issuance.mint(&verifier, &farmer, &300, &Bytes::from_slice(&env, b"mrv-0002"));
+    assert_eq!(token.balance(&farmer), 800);
+}
+
+#[test]
+fn test_mint_without_registry_report_rejected() {
+    let (env, token, issuance, _, verifier, farmer) = setup_env();
+
+    let hash = Bytes::from_slice(&env, b"mrv-0001");
+    let result = issuance.try_mint(&verifier, &farmer, &500, &hash);
+    assert_eq!(result, Err(Ok(Error::ReportNotFound)));
+    assert_eq!(token.total_supply(), 0);
+}
+
+#[test]
+fn test_mint_registry_report_mismatch_rejected() {
+    let (env, _, issuance, _, verifier, farmer) = setup_env();
+    let registry = registry_of(&issuance);
+    let other_verifier = Address::generate(&env);
+
+    registry.submit_report_hash(&verifier, &farmer, &String::from_str(&env, "mrv-0001"));
+
+    // Hash that was never submitted.
+    let result = issuance.try_mint(&verifier, &farmer, &500, &Bytes::from_slice(&env, b"mrv-0002"));
+    assert_eq!(result, Err(Ok(Error::ReportNotFound)));
+
+    // Caller is not the verifier who submitted the report.
+    let result =
+        issuance.try_mint(&other_verifier, &farmer, &500, &Bytes::from_slice(&env, b"mrv-0001"));
+    assert_eq!(result, Err(Ok(Error::ReportMismatch)));
+
+    // Report exists, but for another farmer.
+    let result = issuance.try_mint(
+        &verifier,
+        &Address::generate(&env),
+        &500,
+        &Bytes::from_slice(&env, b"mrv-0001"),
+    );
+    assert_eq!(result, Err(Ok(Error::ReportMismatch)));
+}
+
+#[test]
+fn test_mint_by_inactive_registry_verifier_rejected() {
+    let (env, _, issuance, _, verifier, farmer) = setup_env();
+    let registry = registry_of(&issuance);
+
+    registry.submit_report_hash(&verifier, &farmer, &String::from_str(&env, "mrv-0001"));
+    registry.set_active(&verifier, &false);
+
+    let result = issuance.try_mint(&verifier, &farmer, &500, &Bytes::from_slice(&env, b"mrv-0001"));
+    assert_eq!(result, Err(Ok(Error::VerifierInactive)));
+}
+
+#[test]
+fn test_set_verifier_registry_replaces_registry() {
+    let (env, _, issuance, _, verifier, farmer) = setup_env();
+    let hash = Bytes::from_slice(&env, b"mrv-0001");
+    submit_report(&issuance, &verifier, &farmer, &hash);
+
+    let registry_id = env.register_contract(None, MockVerifierRegistry);
+    issuance.set_verifier_registry(&registry_id);
+    assert_eq!(issuance.verifier_registry(), registry_id);
+
+    let result = issuance.try_mint(&verifier, &farmer, &500, &hash);
+    assert_eq!(result, Err(Ok(Error::ReportNotFound)));
+}
+
+// ── Issuance provenance ───────────────────────────────────────────────────────
+
+#[test]
+fn test_mint_records_issuance() {
+    let (env, token, issuance, _, verifier, farmer) = setup_env();
+    let batch_id = create_agroforestry_batch(&env, &token);
+    let hash = Bytes::from_slice(&env, b"mrv-0001");
+
+    assert_eq!(issuance.get_issuance(&hash), None);
+    submit_report(&issuance, &verifier, &farmer, &hash);
+    issuance.mint_to_batch(&verifier, &farmer, &batch_id, &250, &hash);
+
+    let record = issuance.get_issuance(&hash).unwrap();
+    assert_eq!(record.report_hash, hash);
+    assert_eq!(record.verifier, verifier);
+    assert_eq!(record.recipient, farmer);
+    assert_eq!(record.batch_id, batch_id);
+    assert_eq!(record.amount, 250);
+    assert_eq!(record.ledger, env.ledger().sequence());
+    assert_eq!(record.timestamp, env.ledger().timestamp());
+}
+
+#[test]
+fn test_issuances_paginated_by_verifier_and_recipient() {
+    let (env, _, issuance, _, verifier, farmer) = setup_env();
+    let other_verifier = Address::generate(&env);
+    let other_farmer = Address::generate(&env);
+
+    submit_report(&issuance, &verifier, &farmer, &Bytes::from_slice(&env, b"r1"));
+    issuance.mint(&verifier, &farmer, &100, &Bytes::from_slice(&env, b"r1"));
+    submit_report(&issuance, &verifier, &other_farmer, &Bytes::from_slice(&env, b"r2"));
+    issuance.mint(&verifier, &other_farmer, &200, &Bytes::from_slice(&env, b"r2"));
+    submit_report(&issuance, &other_verifier, &farmer, &Bytes::from_slice(&env, b"r3"));
+    issuance.mint(&other_verifier, &farmer, &300, &Bytes::from_slice(&env, b"r3"));
+    submit_report(&issuance, &verifier, &farmer, &Bytes::from_slice(&env, b"r4"));
+    issuance.mint(&verifier, &farmer, &400, &Bytes::from_slice(&env, b"r4"));
+
+    let by_verifier = issuance.issuances_by_verifier(&verifier, &0, &10);
+    assert_eq!(by_verifier.len(), 3);
+    assert_eq!(by_verifier.get(0).unwrap().amount, 100);
+    assert_eq!(by_verifier.get(2).unwrap().amount, 400);
+
+    let page = issuance.issuances_by_verifier(&verifier, &1, &1);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().recipient, other_farmer);
+
+    let of_farmer = issuance.issuances_of(&farmer, &0, &10);
+    assert_eq!(of_farmer.len(), 3);
+    assert_eq!(of_farmer.get(1).unwrap().verifier, other_verifier);
+    assert_eq!(issuance.issuances_of(&farmer, &3, &10).len(), 0);
+
+    // Each position is its own ledger entry, so the index never outgrows one.
+    env.as_contract(&issuance.address, || {
+        let kind = IndexKind::VerifierIssuances;
+        assert_eq!(read_index_len(&env, kind, &verifier), 3);
+        let key = IndexKey::Entry(kind, verifier.clone(), 2);
+        let hash: Bytes = env.storage().persistent().get(&key).unwrap();
+        assert_eq!(hash, Bytes::from_slice(&env, b"r4"));
+    });
+}
+
+// ── Report hash tracking ──────────────────────────────────────────────────────
+
+#[test]
+fn test_report_hash_tracked_in_persistent_storage() {
+    let (env, _, issuance, _, verifier, farmer) = setup_env();
+    let hash = Bytes::from_slice(&env, b"mrv-0001");
+
+    assert!(!issuance.is_report_hash_used(&hash));
+    assert!(!issuance.extend_report_hash_ttl(&hash));
+
+    submit_report(&issuance, &verifier, &farmer, &hash);
+    issuance.mint(&verifier, &farmer, &100, &hash);
+    assert!(issuance.is_report_hash_used(&hash));
+    assert!(issuance.extend_report_hash_ttl(&hash));
+
+    env.as_contract(&issuance.address, || {
+        let key = DataKey::UsedReportHash(hash.clone());
+        assert!(env.storage().persistent().has(&key));
+        assert!(!env.storage().instance().has(&key));
+    });
+
+    let result = issuance.try_mint(&verifier, &farmer, &100, &hash);
+    assert_eq!(result, Err(Ok(Error::ReportHashUsed)));
+}
+
+// ── Buffer pool ───────────────────────────────────────────────────────────────
+
+#[test]
+fn test_mint_withholds_buffer_share() {
+    let (env, token, issuance, _, verifier, farmer) = setup_env();
+    let buffer = Address::generate(&env);
+    let hash = Bytes::from_slice(&env, b"mrv-0001");
+
+    issuance.set_buffer_config(&buffer, &1500);
+    submit_report(&issuance, &verifier, &farmer, &hash);
+    issuance.mint(&verifier, &farmer, &1000, &hash);
+
+    assert_eq!(token.balance(&farmer), 850);
+    assert_eq!(token.balance(&buffer), 150);
+    assert_eq!(token.batch_balance(&buffer, &1), 150);
+    assert_eq!(token.total_supply(), 1000);
+    assert_eq!(token.get_batch(&1).unwrap().issued, 1000);
+
+    let record = issuance.get_issuance(&hash).unwrap();
+    assert_eq!(record.amount, 1000);
+    assert_eq!(record.buffered, 150);
+
+    // Each account credited gets a plain SEP-41 mint event from the token.
+    let events = env.events().all();
+    for (to, amount) in [(farmer.clone(), 850i128), (buffer, 150i128)] {
+        let topics: soroban_sdk::Vec<soroban_sdk::Val> = (symbol_short!("mint"), to).into_val(&env);
+        assert!(events.contains((token.address.clone(), topics, amount.into_val(&env))));
+    }
+
+    // The issuance event carries the gross, net and buffered amounts.
+    let topics: soroban_sdk::Vec<soroban_sdk::Val> =
+        (symbol_short!("issued"), farmer, 1u32).into_val(&env);
+    let data = (1000i128, 850i128, 150i128).into_val(&env);
+    assert!(events.contains((issuance.address.clone(), topics, data)));
+}
+
+#[test]
+fn test_batch_buffer_override() {
+    let (env, token, issuance, _, verifier, farmer) = setup_env();
+    let buffer = Address::generate(&env);
+    let batch_id = create_agroforestry_batch(&env, &token);
+
+    let result = issuance.try_set_batch_buffer_bps(&batch_id, &2000);
+    assert_eq!(result, Err(Ok(Error::BufferNotConfigured)));
+
+    issuance.set_buffer_config(&buffer, &1000);
+    issuance.set_batch_buffer_bps(&batch_id, &2000);
+    assert_eq!(issuance.buffer_bps(&1), 1000);
+    assert_eq!(issuance.buffer_bps(&batch_id), 2000);
+
+    submit_report(&issuance, &verifier, &farmer, &Bytes::from_slice(&env, b"r1"));
+    issuance.mint(&verifier, &farmer, &1000, &Bytes::from_slice(&env, b"r1"));
+    submit_report(&issuance, &verifier, &farmer, &Bytes::from_slice(&env, b"r2"));
+    let hash = Bytes::from_slice(&env, b"r2");
+    issuance.mint_to_batch(&verifier, &farmer, &batch_id, &1000, &hash);
+    assert_eq!(token.balance(&buffer), 300);
+    assert_eq!(token.batch_balance(&buffer, &batch_id), 200);
+
+    let result = issuance.try_set_buffer_config(&buffer, &10_001);
+    assert_eq!(result, Err(Ok(Error::InvalidBasisPoints)));
+}
+
+// ── Issuance reversal ─────────────────────────────────────────────────────────
+
+#[test]
+fn test_record_reversal_books_against_issuance() {
+    let (env, token, issuance, _, verifier, farmer) = setup_env();
+    let hash = Bytes::from_slice(&env, b"mrv-0001");
+
+    submit_report(&issuance, &verifier, &farmer, &hash);
+    issuance.mint(&verifier, &farmer, &1000, &hash);
+
+    issuance.record_reversal(&hash, &600, &0);
+    assert_eq!(env.auths()[0].0, token.modules().reversals);
+    let record = issuance.get_issuance(&hash).unwrap();
+    assert_eq!((record.reversed, record.buffer_reversed), (600, 0));
+    assert_eq!(issuance.verifier_issued(&verifier), 400);
+
+    let result = issuance.try_record_reversal(&hash, &401, &0);
+    assert_eq!(result, Err(Ok(Error::ExceedsUnreversedAmount)));
+    let result = issuance.try_record_reversal(&Bytes::from_slice(&env, b"nope"), &1, &0);
+    assert_eq!(result, Err(Ok(Error::IssuanceNotFound)));
+}
+
+// ── Supply caps ───────────────────────────────────────────────────────────────
+
+#[test]
+fn test_global_cap_counts_retired_credits() {
+    let (env, token, issuance, _, verifier, farmer) = setup_env();
+
+    issuance.set_global_cap(&Some(1000));
+    assert_eq!(issuance.global_cap(), Some(1000));
+
+    submit_report(&issuance, &verifier, &farmer, &Bytes::from_slice(&env, b"r1"));
+    issuance.mint(&verifier, &farmer, &800, &Bytes::from_slice(&env, b"r1"));
+    token.retire_credits(&None, &farmer, &None, &500);
+
+    // Retiring does not free up room under the cap.
+    submit_report(&issuance, &verifier, &farmer, &Bytes::from_slice(&env, b"r2"));
+    let result = issuance.try_mint(&verifier, &farmer, &201, &Bytes::from_slice(&env, b"r2"));
+    assert_eq!(result, Err(Ok(Error::GlobalCapExceeded)));
+    issuance.mint(&verifier, &farmer, &200, &Bytes::from_slice(&env, b"r2"));
+
+    issuance.set_global_cap(&None);
+    submit_report(&issuance, &verifier, &farmer, &Bytes::from_slice(&env, b"r3"));
+    issuance.mint(&verifier, &farmer, &5000, &Bytes::from_slice(&env, b"r3"));
+
+    let result = issuance.try_set_global_cap(&Some(-1));
+    assert_eq!(result, Err(Ok(Error::InvalidCap)));
+}
+
+#[test]
+fn test_verifier_cap() {
+    let (env, _, issuance, _, verifier, farmer) = setup_env();
+    let other_verifier = Address::generate(&env);
+    let hash = Bytes::from_slice(&env, b"r1");
+
+    issuance.set_verifier_cap(&verifier, &Some(500));
+    assert_eq!(issuance.verifier_cap(&verifier), Some(500));
+    submit_report(&issuance, &verifier, &farmer, &hash);
+    issuance.mint(&verifier, &farmer, &500, &hash);
+    assert_eq!(issuance.verifier_issued(&verifier), 500);
+
+    submit_report(&issuance, &verifier, &farmer, &Bytes::from_slice(&env, b"r2"));
+    let result = issuance.try_mint(&verifier, &farmer, &1, &Bytes::from_slice(&env, b"r2"));
+    assert_eq!(result, Err(Ok(Error::VerifierCapExceeded)));
+    submit_report(&issuance, &other_verifier, &farmer, &Bytes::from_slice(&env, b"r3"));
+    issuance.mint(&other_verifier, &farmer, &1000, &Bytes::from_slice(&env, b"r3"));
+
+    // Reversed credits no longer count against the verifier.
+    issuance.record_reversal(&hash, &500, &0);
+    assert_eq!(issuance.verifier_issued(&verifier), 0);
+    issuance.mint(&verifier, &farmer, &500, &Bytes::from_slice(&env, b"r2"));
+}
+
+#[test]
+fn test_project_cap_spans_vintages() {
+    let (env, token, issuance, _, verifier, farmer) = setup_env();
+    let project = String::from_str(&env, "Kenya Agroforestry");
+    let batch_2024 = create_agroforestry_batch(&env, &token);
+    let batch_2025 = token.create_batch(
+        &project,
+        &String::from_str(&env, "2025"),
+        &String::from_str(&env, "Kenya"),
+        &String::from_str(&env, "https://farmcredit.xyz/kenya-2"),
+    );
+
+    issuance.set_project_cap(&project, &Some(300));
+    assert_eq!(issuance.project_cap(&project), Some(300));
+    let other_project = String::from_str(&env, "Amazon Reforestation");
+    assert_eq!(issuance.project_cap(&other_project), None);
+
+    submit_report(&issuance, &verifier, &farmer, &Bytes::from_slice(&env, b"r1"));
+    let hash = Bytes::from_slice(&env, b"r1");
+    issuance.mint_to_batch(&verifier, &farmer, &batch_2024, &200, &hash);
+    submit_report(&issuance, &verifier, &farmer, &Bytes::from_slice(&env, b"r2"));
+    let hash = Bytes::from_slice(&env, b"r2");
+    let result = issuance.try_mint_to_batch(&verifier, &farmer, &batch_2025, &101, &hash);
+    assert_eq!(result, Err(Ok(Error::ProjectCapExceeded)));
+    issuance.mint_to_batch(&verifier, &farmer, &batch_2025, &100, &hash);
+    assert_eq!(issuance.project_issued(&project), 300);
+
+    // Other projects are unaffected.
+    submit_report(&issuance, &verifier, &farmer, &Bytes::from_slice(&env, b"r3"));
+    issuance.mint(&verifier, &farmer, &1000, &Bytes::from_slice(&env, b"r3"));
+}
+
+// ── Rate limits / circuit breaker ─────────────────────────────────────────────
+
+#[test]
+fn test_verifier_quota_rolls_with_window() {
+    let (env, _, issuance, _, verifier, farmer) = setup_env();
+    let other_verifier = Address::generate(&env);
+
+    issuance.set_rate_limits(&120, &500, &0);
+    submit_report(&issuance, &verifier, &farmer, &Bytes::from_slice(&env, b"r1"));
+    issuance.mint(&verifier, &farmer, &300, &Bytes::from_slice(&env, b"r1"));
+    submit_report(&issuance, &verifier, &farmer, &Bytes::from_slice(&env, b"r2"));
+    issuance.mint(&verifier, &farmer, &200, &Bytes::from_slice(&env, b"r2"));
+    assert_eq!(issuance.verifier_window_minted(&verifier), 500);
+
+    submit_report(&issuance, &verifier, &farmer, &Bytes::from_slice(&env, b"r3"));
+    let result = issuance.try_mint(&verifier, &farmer, &1, &Bytes::from_slice(&env, b"r3"));
+    assert_eq!(result, Err(Ok(Error::VerifierQuotaExceeded)));
+    submit_report(&issuance, &other_verifier, &farmer, &Bytes::from_slice(&env, b"r4"));
+    issuance.mint(&other_verifier, &farmer, &500, &Bytes::from_slice(&env, b"r4"));
+
+    env.ledger().with_mut(|li| li.sequence_number += 130);
+    assert_eq!(issuance.verifier_window_minted(&verifier), 0);
+    issuance.mint(&verifier, &farmer, &500, &Bytes::from_slice(&env, b"r3"));
+}
+
+#[test]
+fn test_circuit_breaker_pauses_token() {
+    let (env, token, issuance, admin, verifier, farmer) = setup_env();
+
+    issuance.set_rate_limits(&120, &0, &1000);
+    submit_report(&issuance, &verifier, &farmer, &Bytes::from_slice(&env, b"r1"));
+    issuance.mint(&verifier, &farmer, &600, &Bytes::from_slice(&env, b"r1"));
+    assert!(!token.paused());
+
+    // The mint that crosses the threshold completes, then the token pauses.
+    submit_report(&issuance, &verifier, &farmer, &Bytes::from_slice(&env, b"r2"));
+    issuance.mint(&verifier, &farmer, &500, &Bytes::from_slice(&env, b"r2"));
+    assert_eq!(token.balance(&farmer), 1100);
+    assert_eq!(issuance.window_minted(), 1100);
+    assert!(token.paused());
+
+    let topics: soroban_sdk::Vec<soroban_sdk::Val> =
+        (symbol_short!("paused"), issuance.address.clone()).into_val(&env);
+    assert!(env.events().all().contains((token.address.clone(), topics, true.into_val(&env))));
+
+    submit_report(&issuance, &verifier, &farmer, &Bytes::from_slice(&env, b"r3"));
+    let result = issuance.try_mint(&verifier, &farmer, &1, &Bytes::from_slice(&env, b"r3"));
+    assert_eq!(result, Err(Ok(Error::ContractPaused)));
+
+    token.admin_unpause(&admin);
+    env.ledger().with_mut(|li| li.sequence_number += 130);
+    issuance.mint(&verifier, &farmer, &600, &Bytes::from_slice(&env, b"r3"));
+    assert!(!token.paused());
+}
+
+#[test]
+fn test_rate_limits_validation_and_removal() {
+    let (_, _, issuance, _, _, _) = setup_env();
+
+    let result = issuance.try_set_rate_limits(&120, &-1, &0);
+    assert_eq!(result, Err(Ok(Error::InvalidRateLimits)));
+
+    issuance.set_rate_limits(&120, &500, &1000);
+    assert_eq!(issuance.rate_limits().unwrap().verifier_quota, 500);
+    issuance.set_rate_limits(&0, &0, &0);
+    assert_eq!(issuance.rate_limits(), None);
+}
+
+// ── Co-approval threshold ─────────────────────────────────────────────────────
+
+#[test]
+fn test_large_mint_requires_co_approval() {
+    let (env, _, issuance, _, verifier, farmer) = setup_env();
+
+    issuance.set_co_approval_threshold(&Some(1000));
+    assert_eq!(issuance.co_approval_threshold(), Some(1000));
+    submit_report(&issuance, &verifier, &farmer, &Bytes::from_slice(&env, b"r1"));
+    let result = issuance.try_mint(&verifier, &farmer, &1001, &Bytes::from_slice(&env, b"r1"));
+    assert_eq!(result, Err(Ok(Error::CoApprovalRequired)));
+    issuance.mint(&verifier, &farmer, &1000, &Bytes::from_slice(&env, b"r1"));
+}
+
+// ── Batch mint ────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_mint_batch_to_many_recipients() {
+    let (env, token, issuance, _, verifier, farmer) = setup_env();
+    let farmer_b = Address::generate(&env);
+    let farmer_c = Address::generate(&env);
+
+    submit_report(&issuance, &verifier, &farmer, &Bytes::from_slice(&env, b"r1"));
+    submit_report(&issuance, &verifier, &farmer_b, &Bytes::from_slice(&env, b"r2"));
+    submit_report(&issuance, &verifier, &farmer_c, &Bytes::from_slice(&env, b"r3"));
+    let before = env.events().all().len();
+    issuance.mint_batch(
+        &verifier,
+        &vec![
+            &env,
+            (farmer.clone(), 100i128, Bytes::from_slice(&env, b"r1")),
+            (farmer_b.clone(), 200i128, Bytes::from_slice(&env, b"r2")),
+            (farmer_c.clone(), 300i128, Bytes::from_slice(&env, b"r3")),
+        ],
+    );
+    assert!(env.events().all().len() >= before + 3);
+
+    assert_eq!(token.balance(&farmer), 100);
+    assert_eq!(token.balance(&farmer_b), 200);
+    assert_eq!(token.balance(&farmer_c), 300);
+    assert_eq!(token.total_supply(), 600);
+    assert_eq!(issuance.issuances_by_verifier(&verifier, &0, &10).len(), 3);
+}
+
+#[test]
+fn test_mint_batch_is_atomic() {
+    let (env, token, issuance, _, verifier, farmer) = setup_env();
+    let farmer_b = Address::generate(&env);
+
+    submit_report(&issuance, &verifier, &farmer, &Bytes::from_slice(&env, b"r1"));
+    let result = issuance.try_mint_batch(
+        &verifier,
+        &vec![
+            &env,
+            (farmer.clone(), 100i128, Bytes::from_slice(&env, b"r1")),
+            (farmer_b.clone(), 200i128, Bytes::from_slice(&env, b"r1")),
+        ],
+    );
+    assert_eq!(result, Err(Ok(Error::ReportHashUsed)));
+    assert_eq!(token.balance(&farmer), 0);
+    assert_eq!(token.total_supply(), 0);
+    assert!(!issuance.is_report_hash_used(&Bytes::from_slice(&env, b"r1")));
+}
+
+#[test]
+fn test_mint_batch_checks_each_recipient() {
+    let (env, token, issuance, _, verifier, farmer) = setup_env();
+    let compliance = CreditComplianceClient::new(&env, &token.modules().compliance);
+    let farmer_b = Address::generate(&env);
+
+    compliance.blacklist(&farmer_b);
+    submit_report(&issuance, &verifier, &farmer, &Bytes::from_slice(&env, b"r1"));
+    submit_report(&issuance, &verifier, &farmer_b, &Bytes::from_slice(&env, b"r2"));
+    let result = issuance.try_mint_batch(
+        &verifier,
+        &vec![
+            &env,
+            (farmer.clone(), 100i128, Bytes::from_slice(&env, b"r1")),
+            (farmer_b.clone(), 200i128, Bytes::from_slice(&env, b"r2")),
+        ],
+    );
+    assert_eq!(result, Err(Ok(Error::Blacklisted)));
+    assert_eq!(token.total_supply(), 0);
+}
+
+// ── Pause ─────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_mint_when_paused_returns_error() {
+    let (env, token, issuance, admin, verifier, user) = setup_env();
+    token.admin_pause(&admin);
+    submit_report(&issuance, &verifier, &user, &Bytes::from_slice(&env, b"p1"));
+    let result = issuance.try_mint(&verifier, &user, &1000, &Bytes::from_slice(&env, b"p1"));
+    assert_eq!(result, Err(Ok(Error::ContractPaused)));
+}
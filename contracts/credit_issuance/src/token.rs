@@ -0,0 +1,94 @@
+use soroban_sdk::{contractclient, contracttype, Address, Env, String};
+
+use crate::error::Error;
+use crate::storage::read_token;
+
+/// Mirror of the token's `CreditBatch`; the encoding must match.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct CreditBatch {
+    pub id: u32,
+    pub project_name: String,
+    pub vintage: String,
+    pub location: String,
+    pub metadata_url: String,
+    pub issued: i128,
+    pub retired: i128,
+    pub reversed: i128,
+}
+
+/// Mirror of the token's `Modules`; the encoding must match.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct Modules {
+    pub issuance: Address,
+    pub retirement: Address,
+    pub compliance: Address,
+    pub permits: Address,
+    pub reversals: Address,
+}
+
+/// The parts of the carbon credit token this module calls.
+#[contractclient(name = "TokenClient")]
+#[allow(dead_code)]
+pub trait TokenInterface {
+    fn super_admin(env: Env) -> Address;
+    fn rbac_contract(env: Env) -> Address;
+    fn governance_mode(env: Env) -> bool;
+    fn modules(env: Env) -> Modules;
+    fn paused(env: Env) -> bool;
+    fn total_supply(env: Env) -> i128;
+    fn total_retired(env: Env) -> i128;
+    fn get_batch(env: Env, batch_id: u32) -> Option<CreditBatch>;
+
+    /// Pauses the token; accepts this module when its breaker trips.
+    fn admin_pause(env: Env, admin: Address) -> Result<(), Error>;
+
+    /// Credits newly issued credits. Accepts this module only.
+    fn issue(env: Env, to: Address, batch_id: u32, amount: i128) -> Result<(), Error>;
+}
+
+/// Cross-contract client interface for the token's compliance module.
+#[contractclient(name = "ComplianceClient")]
+#[allow(dead_code)]
+pub trait ComplianceInterface {
+    fn is_blacklisted(env: Env, addr: Address) -> bool;
+}
+
+pub fn token_client(e: &Env) -> TokenClient<'_> {
+    TokenClient::new(e, &read_token(e))
+}
+
+/// The address admin entrypoints require auth from: the token's SuperAdmin,
+/// or, in governance mode, its RBAC contract.
+pub fn read_admin_authority(e: &Env) -> Address {
+    let token = token_client(e);
+    if token.governance_mode() {
+        token.rbac_contract()
+    } else {
+        token.super_admin()
+    }
+}
+
+pub fn require_not_paused(e: &Env) -> Result<(), Error> {
+    if token_client(e).paused() {
+        Err(Error::ContractPaused)
+    } else {
+        Ok(())
+    }
+}
+
+pub fn require_not_blacklisted(e: &Env, addr: &Address) -> Result<(), Error> {
+    let compliance = token_client(e).modules().compliance;
+    if ComplianceClient::new(e, &compliance).is_blacklisted(addr) {
+        Err(Error::Blacklisted)
+    } else {
+        Ok(())
+    }
+}
+
+pub fn read_batch(e: &Env, batch_id: u32) -> Result<CreditBatch, Error> {
+    token_client(e)
+        .get_batch(&batch_id)
+        .ok_or(Error::BatchNotFound)
+}
@@ -0,0 +1,33 @@
+use soroban_sdk::contracterror;
+
+/// Codes match the token's, so an error reads the same whichever contract
+/// raised it.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    /// The provided amount is negative.
+    NegativeAmount = 1,
+    /// The contract is already initialized.
+    AlreadyInitialized = 4,
+    /// The provided expiration ledger is invalid (in the past).
+    InvalidExpirationLedger = 5,
+    /// The address is blacklisted.
+    Blacklisted = 6,
+    /// The contract is paused.
+    ContractPaused = 12,
+    /// The owner has not registered a permit signing key.
+    PermitKeyNotSet = 38,
+    /// The permit nonce does not match the owner's next nonce.
+    InvalidNonce = 39,
+}
+
+/// Passes on the error of a failed `try_` call to the token. Anything else,
+/// such as a trap in the token, aborts the call.
+pub fn forward<T, C, I>(result: Result<Result<T, C>, Result<Error, I>>) -> Result<T, Error> {
+    match result {
+        Ok(Ok(value)) => Ok(value),
+        Err(Ok(error)) => Err(error),
+        _ => panic!("token call failed"),
+    }
+}
@@ -0,0 +1,133 @@
+#![no_std]
+
+mod error;
+mod storage;
+mod token;
+#[cfg(test)]
+mod test;
+
+pub use error::Error;
+pub use token::AllowancePurpose;
+use soroban_sdk::{contract, contractimpl, xdr::ToXdr, Address, Bytes, BytesN, Env};
+
+use crate::error::forward;
+use crate::storage::{
+    increment_permit_nonce, is_initialized, read_permit_key, read_permit_nonce, read_token,
+    write_permit_key, write_token, INSTANCE_BUMP_AMOUNT, INSTANCE_LIFETIME_THRESHOLD,
+};
+use crate::token::{read_admin_authority, token_client};
+
+/// The message a permit signature covers: the XDR encoding of
+/// `(token contract, from, spender, amount, expiration_ledger, purpose, nonce)`.
+/// Including the token address keeps a permit from being replayed against
+/// another deployment, and including the purpose keeps a relayer from
+/// widening a retire-only permit.
+fn permit_message(
+    e: &Env,
+    from: &Address,
+    spender: &Address,
+    amount: i128,
+    expiration_ledger: u32,
+    purpose: AllowancePurpose,
+    nonce: u64,
+) -> Bytes {
+    (
+        read_token(e),
+        from.clone(),
+        spender.clone(),
+        amount,
+        expiration_ledger,
+        purpose,
+        nonce,
+    )
+        .to_xdr(e)
+}
+
+/// Permits module of the carbon credit token: allowances approved with an
+/// ed25519 signature made off-chain, so anyone (e.g. a relayer) can submit
+/// them on the owner's behalf.
+#[contract]
+pub struct CreditPermits;
+
+#[contractimpl]
+impl CreditPermits {
+    /// Binds the module to `token`. Must be signed by the token's SuperAdmin
+    /// (or RBAC contract in governance mode). Can only be called once.
+    pub fn initialize(env: Env, token: Address) -> Result<(), Error> {
+        if is_initialized(&env) {
+            return Err(Error::AlreadyInitialized);
+        }
+
+        write_token(&env, &token);
+        read_admin_authority(&env).require_auth();
+        Ok(())
+    }
+
+    /// Replaces the module code with the uploaded wasm `new_wasm_hash`.
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+        read_admin_authority(&env).require_auth();
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+    }
+
+    /// Registers the ed25519 key whose signatures `permit` accepts for `owner`.
+    pub fn set_permit_key(env: Env, owner: Address, public_key: BytesN<32>) -> Result<(), Error> {
+        owner.require_auth();
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        write_permit_key(&env, &owner, &public_key);
+        Ok(())
+    }
+
+    /// Approves `spender` on the token on behalf of `from` with a signature
+    /// made by `from`'s permit key. The signature covers the message built
+    /// by `permit_message`, and `nonce` must be `from`'s next permit nonce.
+    /// Grants an allowance limited to `purpose`, like the token's
+    /// `approve_with_purpose`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn permit(
+        env: Env,
+        from: Address,
+        spender: Address,
+        amount: i128,
+        expiration_ledger: u32,
+        purpose: AllowancePurpose,
+        nonce: u64,
+        signature: BytesN<64>,
+    ) -> Result<(), Error> {
+        let public_key = read_permit_key(&env, &from).ok_or(Error::PermitKeyNotSet)?;
+        if nonce != read_permit_nonce(&env, &from) {
+            return Err(Error::InvalidNonce);
+        }
+        let message = permit_message(
+            &env,
+            &from,
+            &spender,
+            amount,
+            expiration_ledger,
+            purpose,
+            nonce,
+        );
+        env.crypto().ed25519_verify(&public_key, &message, &signature);
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        increment_permit_nonce(&env, &from);
+        forward(token_client(&env).try_approve_by_permit(
+            &from,
+            &spender,
+            &amount,
+            &expiration_ledger,
+            &purpose,
+        ))
+    }
+
+    /// Returns the nonce the next permit from `owner` must carry.
+    pub fn permit_nonce(env: Env, owner: Address) -> u64 {
+        read_permit_nonce(&env, &owner)
+    }
+}
@@ -0,0 +1,69 @@
+use soroban_sdk::{contracttype, Address, BytesN, Env};
+
+// ── TTL Constants ──────────────────────────────────────────────────────────────
+pub const INSTANCE_LIFETIME_THRESHOLD: u32 = 17280; // ~1 day
+pub const INSTANCE_BUMP_AMOUNT: u32 = 518400; // ~30 days
+
+pub const BALANCE_LIFETIME_THRESHOLD: u32 = 17280; // ~1 day
+pub const BALANCE_BUMP_AMOUNT: u32 = 518400; // ~30 days
+
+// ── Storage Keys ───────────────────────────────────────────────────────────────
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    // The token this module approves on
+    Token,
+
+    // Registered signing keys and the next nonce per owner
+    PermitKey(Address),
+    PermitNonce(Address),
+}
+
+// ── Token ──────────────────────────────────────────────────────────────────────
+pub fn is_initialized(e: &Env) -> bool {
+    e.storage().instance().has(&DataKey::Token)
+}
+
+pub fn read_token(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::Token)
+        .expect("token not set: was initialize() called?")
+}
+
+pub fn write_token(e: &Env, token: &Address) {
+    e.storage().instance().set(&DataKey::Token, token);
+}
+
+// ── Permit keys and nonces ─────────────────────────────────────────────────────
+/// The ed25519 key `owner` signs permits with, if registered.
+pub fn read_permit_key(e: &Env, owner: &Address) -> Option<BytesN<32>> {
+    e.storage()
+        .persistent()
+        .get(&DataKey::PermitKey(owner.clone()))
+}
+
+pub fn write_permit_key(e: &Env, owner: &Address, public_key: &BytesN<32>) {
+    let key = DataKey::PermitKey(owner.clone());
+    e.storage().persistent().set(&key, public_key);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+/// The nonce the next permit from `owner` must carry.
+pub fn read_permit_nonce(e: &Env, owner: &Address) -> u64 {
+    e.storage()
+        .persistent()
+        .get(&DataKey::PermitNonce(owner.clone()))
+        .unwrap_or(0)
+}
+
+pub fn increment_permit_nonce(e: &Env, owner: &Address) {
+    let key = DataKey::PermitNonce(owner.clone());
+    let nonce = read_permit_nonce(e, owner) + 1;
+    e.storage().persistent().set(&key, &nonce);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+}
@@ -0,0 +1,179 @@
+#![cfg(test)]
+
+use crate::{permit_message, AllowancePurpose, CreditPermits, CreditPermitsClient, Error};
+use carbon_credit_token::{
+    AllowancePurpose as TokenPurpose, CarbonCreditToken, CarbonCreditTokenClient,
+    Error as TokenError, Modules, DEFAULT_BATCH_ID,
+};
+use credit_compliance::{CreditCompliance, CreditComplianceClient};
+use soroban_sdk::{contract, contractimpl, testutils::Address as _, Address, BytesN, Env, String};
+
+#[contract]
+pub struct MockRbacContract;
+
+#[contractimpl]
+impl MockRbacContract {
+    pub fn get_role(_env: Env, _address: Address) -> u32 {
+        // Everyone is a Trader
+        2
+    }
+}
+
+/// Registers a token bound to this module and a real compliance module, and
+/// returns `(env, token, permits, owner)`. The other modules are bare
+/// addresses: with every auth mocked, credits are issued by calling the
+/// token's `issue` directly.
+fn setup_env<'a>() -> (
+    Env,
+    CarbonCreditTokenClient<'a>,
+    CreditPermitsClient<'a>,
+    Address,
+) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let rbac_id = env.register_contract(None, MockRbacContract);
+    let compliance_id = env.register_contract(None, CreditCompliance);
+    let permits_id = env.register_contract(None, CreditPermits);
+    let token_id = env.register_contract(None, CarbonCreditToken);
+    let token = CarbonCreditTokenClient::new(&env, &token_id);
+
+    token.initialize(
+        &admin,
+        &rbac_id,
+        &Modules {
+            issuance: Address::generate(&env),
+            retirement: Address::generate(&env),
+            compliance: compliance_id.clone(),
+            permits: permits_id.clone(),
+            reversals: Address::generate(&env),
+        },
+        &String::from_str(&env, "Carbon Credit Token"),
+        &String::from_str(&env, "CCT"),
+        &0u32,
+        &String::from_str(&env, "Amazon Reforestation"),
+        &String::from_str(&env, "2023"),
+        &String::from_str(&env, "Brazil"),
+        &String::from_str(&env, "https://farmcredit.xyz/amazon-1"),
+    );
+    CreditComplianceClient::new(&env, &compliance_id).initialize(&token_id);
+    let permits = CreditPermitsClient::new(&env, &permits_id);
+    permits.initialize(&token_id);
+
+    let owner = Address::generate(&env);
+    token.issue(&owner, &DEFAULT_BATCH_ID, &1000);
+
+    (env, token, permits, owner)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn sign_permit(
+    token: &CarbonCreditTokenClient,
+    key: &ed25519_dalek::SigningKey,
+    from: &Address,
+    spender: &Address,
+    amount: i128,
+    expiration_ledger: u32,
+    purpose: AllowancePurpose,
+    nonce: u64,
+) -> BytesN<64> {
+    use ed25519_dalek::Signer;
+    use soroban_sdk::xdr::ToXdr;
+    extern crate std;
+
+    let env = &token.env;
+    let message = (
+        token.address.clone(),
+        from.clone(),
+        spender.clone(),
+        amount,
+        expiration_ledger,
+        purpose,
+        nonce,
+    )
+        .to_xdr(env);
+    let message: std::vec::Vec<u8> = message.iter().collect();
+    BytesN::from_array(env, &key.sign(&message).to_bytes())
+}
+
+#[test]
+fn test_initialize_binds_token_once() {
+    let (_, token, permits, _) = setup_env();
+
+    let result = permits.try_initialize(&token.address);
+    assert_eq!(result, Err(Ok(Error::AlreadyInitialized)));
+}
+
+#[test]
+fn test_permit_sets_allowance_and_consumes_nonce() {
+    let (env, token, permits, owner) = setup_env();
+    let spender = Address::generate(&env);
+    let key = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+
+    permits.set_permit_key(&owner, &BytesN::from_array(&env, &key.verifying_key().to_bytes()));
+
+    let any = AllowancePurpose::Any;
+    let signature = sign_permit(&token, &key, &owner, &spender, 300, 1000, any, 0);
+    permits.permit(&owner, &spender, &300, &1000, &any, &0, &signature);
+    assert_eq!(token.allowance(&owner, &spender), 300);
+    assert_eq!(permits.permit_nonce(&owner), 1);
+
+    token.transfer_from(&spender, &owner, &spender, &300);
+    assert_eq!(token.balance(&spender), 300);
+
+    // The same signature cannot be replayed.
+    let result = permits.try_permit(&owner, &spender, &300, &1000, &any, &0, &signature);
+    assert_eq!(result, Err(Ok(Error::InvalidNonce)));
+}
+
+#[test]
+fn test_permit_requires_registered_key() {
+    let (env, token, permits, owner) = setup_env();
+    let spender = Address::generate(&env);
+    let key = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+
+    let any = AllowancePurpose::Any;
+    let signature = sign_permit(&token, &key, &owner, &spender, 300, 1000, any, 0);
+    let result = permits.try_permit(&owner, &spender, &300, &1000, &any, &0, &signature);
+    assert_eq!(result, Err(Ok(Error::PermitKeyNotSet)));
+}
+
+#[test]
+fn test_permit_signs_purpose() {
+    let (env, token, permits, owner) = setup_env();
+    let spender = Address::generate(&env);
+    let key = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+
+    permits.set_permit_key(&owner, &BytesN::from_array(&env, &key.verifying_key().to_bytes()));
+
+    // A relayer cannot widen a retire-only permit: the purpose is signed.
+    let retire = AllowancePurpose::Retire;
+    env.as_contract(&permits.address, || {
+        let any = permit_message(&env, &owner, &spender, 300, 1000, AllowancePurpose::Any, 0);
+        assert_ne!(any, permit_message(&env, &owner, &spender, 300, 1000, retire, 0));
+    });
+
+    let signature = sign_permit(&token, &key, &owner, &spender, 300, 1000, retire, 0);
+    permits.permit(&owner, &spender, &300, &1000, &retire, &0, &signature);
+    assert_eq!(token.allowance_purpose(&owner, &spender), TokenPurpose::Retire);
+    let result = token.try_transfer_from(&spender, &owner, &spender, &300);
+    assert_eq!(result, Err(Ok(TokenError::AllowancePurposeMismatch)));
+}
+
+#[test]
+fn test_permit_for_blacklisted_owner_rejected() {
+    let (env, token, permits, owner) = setup_env();
+    let spender = Address::generate(&env);
+    let key = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+
+    permits.set_permit_key(&owner, &BytesN::from_array(&env, &key.verifying_key().to_bytes()));
+    CreditComplianceClient::new(&env, &token.modules().compliance).blacklist(&owner);
+
+    // The token's error is passed on, and the nonce is not consumed.
+    let any = AllowancePurpose::Any;
+    let signature = sign_permit(&token, &key, &owner, &spender, 300, 1000, any, 0);
+    let result = permits.try_permit(&owner, &spender, &300, &1000, &any, &0, &signature);
+    assert_eq!(result, Err(Ok(Error::Blacklisted)));
+    assert_eq!(permits.permit_nonce(&owner), 0);
+}
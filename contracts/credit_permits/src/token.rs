@@ -0,0 +1,50 @@
+use soroban_sdk::{contractclient, contracttype, Address, Env};
+
+use crate::error::Error;
+use crate::storage::read_token;
+
+/// Mirror of the token's `AllowancePurpose`; the encoding must match.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+#[repr(u32)]
+pub enum AllowancePurpose {
+    Any = 0,
+    Transfer = 1,
+    Burn = 2,
+    Retire = 3,
+}
+
+/// The parts of the carbon credit token this module calls.
+#[contractclient(name = "TokenClient")]
+#[allow(dead_code)]
+pub trait TokenInterface {
+    fn super_admin(env: Env) -> Address;
+    fn rbac_contract(env: Env) -> Address;
+    fn governance_mode(env: Env) -> bool;
+
+    /// Writes an allowance for `from` without their auth. Accepts this
+    /// module only.
+    fn approve_by_permit(
+        env: Env,
+        from: Address,
+        spender: Address,
+        amount: i128,
+        expiration_ledger: u32,
+        purpose: AllowancePurpose,
+    ) -> Result<(), Error>;
+}
+
+pub fn token_client(e: &Env) -> TokenClient<'_> {
+    TokenClient::new(e, &read_token(e))
+}
+
+/// The address admin entrypoints require auth from: the token's SuperAdmin,
+/// or, in governance mode, its RBAC contract.
+pub fn read_admin_authority(e: &Env) -> Address {
+    let token = token_client(e);
+    if token.governance_mode() {
+        token.rbac_contract()
+    } else {
+        token.super_admin()
+    }
+}
@@ -0,0 +1,77 @@
+use soroban_sdk::{contracttype, Address, Env, String, Vec};
+use crate::index::{push_index, read_index_page, IndexKind};
+use crate::storage::{DataKey, BALANCE_BUMP_AMOUNT, BALANCE_LIFETIME_THRESHOLD};
+
+/// A non-transferable (soulbound) proof of retirement. The project fields are
+/// a snapshot of the batch metadata at the time of retirement. `owner` is the
+/// beneficiary when the retirement was made on someone's behalf, `retiree` is
+/// always the account whose credits were burned.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct CertificateRecord {
+    pub id: u32,
+    pub owner: Address,
+    pub retiree: Address,
+    pub beneficiary: String,
+    pub amount: i128,
+    pub timestamp: u64,
+    pub retirement_id: u64,
+    pub batch_id: u32,
+    pub project_name: String,
+    pub vintage: String,
+    pub location: String,
+    pub metadata_url: String,
+}
+
+pub fn read_next_certificate_id(e: &Env) -> u32 {
+    let key = DataKey::NextCertificateID;
+    e.storage().instance().get(&key).unwrap_or(0)
+}
+
+pub fn increment_next_certificate_id(e: &Env) -> u32 {
+    let id = read_next_certificate_id(e) + 1;
+    let key = DataKey::NextCertificateID;
+    e.storage().instance().set(&key, &id);
+    id
+}
+
+/// Raises the certificate counter to `id` so new certificates are numbered
+/// after those issued under an earlier layout.
+pub fn raise_next_certificate_id(e: &Env, id: u32) {
+    if id > read_next_certificate_id(e) {
+        e.storage().instance().set(&DataKey::NextCertificateID, &id);
+    }
+}
+
+pub fn write_certificate(e: &Env, cert: CertificateRecord) {
+    let key = DataKey::Certificate(cert.id);
+    e.storage().persistent().set(&key, &cert);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+
+    push_index(e, IndexKind::OwnerCertificates, &cert.owner, &cert.id);
+}
+
+pub fn read_certificate(e: &Env, id: u32) -> Option<CertificateRecord> {
+    let key = DataKey::Certificate(id);
+    e.storage().persistent().get(&key)
+}
+
+/// Returns up to `limit` of `owner`'s certificates, starting at position
+/// `cursor` in issue order.
+pub fn read_owner_certificates(
+    e: &Env,
+    owner: &Address,
+    cursor: u32,
+    limit: u32,
+) -> Vec<CertificateRecord> {
+    let ids: Vec<u32> = read_index_page(e, IndexKind::OwnerCertificates, owner, cursor, limit);
+    let mut certs = Vec::new(e);
+    for id in ids.iter() {
+        if let Some(cert) = read_certificate(e, id) {
+            certs.push_back(cert);
+        }
+    }
+    certs
+}
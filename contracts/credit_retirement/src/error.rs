@@ -0,0 +1,51 @@
+use soroban_sdk::contracterror;
+
+/// Codes match the token's, so an error reads the same whichever contract
+/// raised it.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    /// The provided amount is negative.
+    NegativeAmount = 1,
+    /// The account does not have enough balance.
+    InsufficientBalance = 2,
+    /// The allowance is not enough for the transfer.
+    InsufficientAllowance = 3,
+    /// The contract is already initialized.
+    AlreadyInitialized = 4,
+    /// The address is blacklisted.
+    Blacklisted = 6,
+    /// Caller is not authorized.
+    Unauthorized = 7,
+    /// Retirement amount must be greater than zero.
+    ZeroRetirementAmount = 8,
+    /// The contract is paused.
+    ContractPaused = 12,
+    /// No batch exists with the given id.
+    BatchNotFound = 13,
+    /// No certificate exists with the given id.
+    CertificateNotFound = 15,
+    /// A retirement on behalf of someone else must name a beneficiary other
+    /// than the payer.
+    InvalidBeneficiary = 16,
+    /// The allowance was granted for a different purpose.
+    AllowancePurposeMismatch = 17,
+    /// The amount exceeds the account's balance that is not frozen.
+    BalanceFrozen = 40,
+    /// In permissioned-holder mode, a counterparty holds no Trader, Verifier
+    /// or Admin role.
+    HolderNotPermitted = 43,
+    /// The registered compliance hook rejected the movement.
+    TransferNotCompliant = 44,
+}
+
+/// Passes on the error of a failed `try_` call to the token. Anything else,
+/// such as a trap in the token, aborts the call.
+pub fn forward<T, C, I>(result: Result<Result<T, C>, Result<Error, I>>) -> Result<T, Error> {
+    match result {
+        Ok(Ok(value)) => Ok(value),
+        Err(Ok(error)) => Err(error),
+        _ => panic!("token call failed"),
+    }
+}
@@ -0,0 +1,62 @@
+use soroban_sdk::{contracttype, symbol_short, Address, Bytes, Env, String};
+
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct RetirementData {
+    pub retirement_id: u64,
+    pub amount: i128,
+    pub timestamp: u64,
+    pub report_hash: Bytes,
+    pub methodology: String,
+    pub beneficiary: Address,
+    pub beneficiary_name: String,
+}
+
+
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct RetirementEvent {
+    pub retirement_id: u64,
+    pub from: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+    pub report_hash: Bytes,
+    pub methodology: String,
+    pub beneficiary: Address,
+    pub beneficiary_name: String,
+}
+
+
+impl RetirementEvent {
+    pub fn publish(self, env: &Env) {
+        let data = RetirementData {
+            retirement_id: self.retirement_id,
+            amount: self.amount,
+            timestamp: self.timestamp,
+            report_hash: self.report_hash,
+            methodology: self.methodology,
+            beneficiary: self.beneficiary,
+            beneficiary_name: self.beneficiary_name,
+        };
+        env.events()
+            .publish((symbol_short!("retire"), self.from), data);
+    }
+}
+
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct CertificateGeneratedEvent {
+    pub certificate_id: u32,
+    pub corporate: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+impl CertificateGeneratedEvent {
+    pub fn publish(self, env: &Env) {
+        env.events().publish(
+            (symbol_short!("cert"), self.corporate, self.certificate_id),
+            (self.amount, self.timestamp),
+        );
+    }
+}
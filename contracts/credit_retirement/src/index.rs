@@ -0,0 +1,72 @@
+use soroban_sdk::{contracttype, Address, Env, IntoVal, TryFromVal, Val, Vec};
+
+use crate::storage::{BALANCE_BUMP_AMOUNT, BALANCE_LIFETIME_THRESHOLD};
+
+/// Append-only per-account lists, e.g. the retirements an account made.
+#[derive(Clone, Copy)]
+#[contracttype]
+pub enum IndexKind {
+    RetireeRetirements,
+    OwnerCertificates,
+}
+
+/// Keys for the per-account lists. Each position is stored under its own key
+/// next to the list length, so appending costs the same however long the
+/// list is and no single ledger entry grows with an account's history.
+#[derive(Clone)]
+#[contracttype]
+pub enum IndexKey {
+    Len(IndexKind, Address),
+    Entry(IndexKind, Address, u32),
+}
+
+pub fn read_index_len(e: &Env, kind: IndexKind, owner: &Address) -> u32 {
+    e.storage()
+        .persistent()
+        .get(&IndexKey::Len(kind, owner.clone()))
+        .unwrap_or(0)
+}
+
+/// Appends `value` to `owner`'s list of `kind`.
+pub fn push_index<V: IntoVal<Env, Val>>(e: &Env, kind: IndexKind, owner: &Address, value: &V) {
+    let len = read_index_len(e, kind, owner);
+
+    let entry_key = IndexKey::Entry(kind, owner.clone(), len);
+    e.storage().persistent().set(&entry_key, value);
+    e.storage().persistent().extend_ttl(
+        &entry_key,
+        BALANCE_LIFETIME_THRESHOLD,
+        BALANCE_BUMP_AMOUNT,
+    );
+
+    let len_key = IndexKey::Len(kind, owner.clone());
+    e.storage().persistent().set(&len_key, &(len + 1));
+    e.storage()
+        .persistent()
+        .extend_ttl(&len_key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+/// Returns up to `limit` values from `owner`'s list of `kind`, starting at
+/// position `cursor` in the order they were appended.
+pub fn read_index_page<V>(
+    e: &Env,
+    kind: IndexKind,
+    owner: &Address,
+    cursor: u32,
+    limit: u32,
+) -> Vec<V>
+where
+    V: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+{
+    let end = cursor
+        .saturating_add(limit)
+        .min(read_index_len(e, kind, owner));
+    let mut values = Vec::new(e);
+    for position in cursor..end {
+        let key = IndexKey::Entry(kind, owner.clone(), position);
+        if let Some(value) = e.storage().persistent().get(&key) {
+            values.push_back(value);
+        }
+    }
+    values
+}
@@ -0,0 +1,430 @@
+#![no_std]
+
+mod certificate;
+mod error;
+mod events;
+mod index;
+mod retirement;
+mod storage;
+mod token;
+#[cfg(test)]
+mod test;
+
+pub use certificate::CertificateRecord;
+pub use error::Error;
+pub use retirement::RetirementRecord;
+use soroban_sdk::{contract, contractimpl, Address, Bytes, BytesN, Env, String, Vec};
+
+use crate::certificate::{
+    increment_next_certificate_id, raise_next_certificate_id, read_certificate,
+    read_next_certificate_id, read_owner_certificates, write_certificate,
+};
+use crate::error::forward;
+use crate::events::{CertificateGeneratedEvent, RetirementEvent};
+use crate::retirement::{
+    increment_next_retirement_id, read_next_retirement_id, read_retiree_retirements,
+    read_retirement, write_retirement, RetirementDetails,
+};
+use crate::storage::{
+    is_initialized, read_token, write_token, INSTANCE_BUMP_AMOUNT, INSTANCE_LIFETIME_THRESHOLD,
+};
+use crate::token::{
+    read_admin_authority, require_not_blacklisted, token_client, BatchAmount, CreditBatch,
+};
+
+/// Batch the token creates from the project metadata passed to its
+/// `initialize`. Balances held before batch accounting are retired from it.
+const DEFAULT_BATCH_ID: u32 = 1;
+
+/// Has the token burn `amount` of `from`'s credits, from `batch_id` or the
+/// oldest batches first, and books the retirement. Callers are responsible
+/// for authorizing `from` or `spender`.
+fn retire_credits(
+    env: &Env,
+    spender: Option<Address>,
+    from: &Address,
+    batch_id: Option<u32>,
+    amount: i128,
+    details: RetirementDetails,
+) -> Result<(), Error> {
+    env.storage()
+        .instance()
+        .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+    let lots = forward(token_client(env).try_retire_credits(&spender, from, &batch_id, &amount))?;
+    record_retirement(env, from, amount, &lots, details)
+}
+
+/// Books a retirement the token has already burned: the retirement ledger
+/// entry, the offset certificates and the retirement events.
+fn record_retirement(
+    env: &Env,
+    from: &Address,
+    amount: i128,
+    lots: &Vec<BatchAmount>,
+    details: RetirementDetails,
+) -> Result<(), Error> {
+    let owner = details.beneficiary_address.unwrap_or_else(|| from.clone());
+    let timestamp = env.ledger().timestamp();
+
+    let retirement_id = increment_next_retirement_id(env);
+    write_retirement(
+        env,
+        RetirementRecord {
+            id: retirement_id,
+            retiree: from.clone(),
+            amount,
+            timestamp,
+            report_hash: details.report_hash.clone(),
+            methodology: details.methodology.clone(),
+            reason: details.reason,
+            beneficiary: details.beneficiary.clone(),
+            beneficiary_address: owner.clone(),
+        },
+    );
+
+    RetirementEvent {
+        retirement_id,
+        from: from.clone(),
+        amount,
+        timestamp,
+        report_hash: details.report_hash,
+        methodology: details.methodology,
+        beneficiary: owner.clone(),
+        beneficiary_name: details.beneficiary.clone(),
+    }
+    .publish(env);
+
+    // One certificate per batch retired, so each names a single project/vintage.
+    let mut certified = 0;
+    for lot in lots.iter() {
+        certified += lot.amount;
+        issue_certificate(env, &owner, from, &details.beneficiary, retirement_id, lot)?;
+    }
+    if certified < amount {
+        // Balance held before batch accounting: attribute to the default batch.
+        let lot = BatchAmount {
+            batch_id: DEFAULT_BATCH_ID,
+            amount: amount - certified,
+        };
+        issue_certificate(env, &owner, from, &details.beneficiary, retirement_id, lot)?;
+    }
+    Ok(())
+}
+
+/// Mints a soulbound certificate snapshotting the batch's project metadata.
+fn issue_certificate(
+    env: &Env,
+    owner: &Address,
+    retiree: &Address,
+    beneficiary: &String,
+    retirement_id: u64,
+    lot: BatchAmount,
+) -> Result<u32, Error> {
+    let batch = token_client(env)
+        .get_batch(&lot.batch_id)
+        .ok_or(Error::BatchNotFound)?;
+
+    let timestamp = env.ledger().timestamp();
+    let cert_id = increment_next_certificate_id(env);
+    write_certificate(
+        env,
+        CertificateRecord {
+            id: cert_id,
+            owner: owner.clone(),
+            retiree: retiree.clone(),
+            beneficiary: beneficiary.clone(),
+            amount: lot.amount,
+            timestamp,
+            retirement_id,
+            batch_id: lot.batch_id,
+            project_name: batch.project_name,
+            vintage: batch.vintage,
+            location: batch.location,
+            metadata_url: batch.metadata_url,
+        },
+    );
+
+    CertificateGeneratedEvent {
+        certificate_id: cert_id,
+        corporate: owner.clone(),
+        amount: lot.amount,
+        timestamp,
+    }
+    .publish(env);
+    Ok(cert_id)
+}
+
+/// Retirement module of the carbon credit token: the retirement ledger and
+/// soulbound offset certificates. The token burns what this module retires.
+#[contract]
+pub struct CreditRetirement;
+
+#[contractimpl]
+impl CreditRetirement {
+    /// Binds the module to `token`. Must be signed by the token's SuperAdmin
+    /// (or RBAC contract in governance mode). Can only be called once.
+    pub fn initialize(env: Env, token: Address) -> Result<(), Error> {
+        if is_initialized(&env) {
+            return Err(Error::AlreadyInitialized);
+        }
+
+        write_token(&env, &token);
+        read_admin_authority(&env).require_auth();
+        Ok(())
+    }
+
+    /// Replaces the module code with the uploaded wasm `new_wasm_hash`.
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+        read_admin_authority(&env).require_auth();
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+    }
+
+    /// Rewrites the certificates a first-release token kept as one list per
+    /// retiree, each `(retiree, id, amount, timestamp)`, as one certificate
+    /// per id attributed to `batch`, and numbers new certificates after
+    /// `next_id`. Called by the token's `migrate` only.
+    pub fn import_certificates(
+        env: Env,
+        batch: CreditBatch,
+        next_id: u32,
+        certificates: Vec<(Address, u64, i128, u64)>,
+    ) -> Result<(), Error> {
+        read_token(&env).require_auth();
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        for (retiree, id, amount, timestamp) in certificates.iter() {
+            write_certificate(
+                &env,
+                CertificateRecord {
+                    id: id as u32,
+                    owner: retiree.clone(),
+                    retiree,
+                    beneficiary: String::from_str(&env, ""),
+                    amount,
+                    timestamp,
+                    retirement_id: 0,
+                    batch_id: batch.id,
+                    project_name: batch.project_name.clone(),
+                    vintage: batch.vintage.clone(),
+                    location: batch.location.clone(),
+                    metadata_url: batch.metadata_url.clone(),
+                },
+            );
+        }
+        raise_next_certificate_id(&env, next_id);
+        Ok(())
+    }
+
+    // ── Retirement ────────────────────────────────────────────────────────────
+
+    pub fn retire(
+        env: Env,
+        from: Address,
+        amount: i128,
+        report_hash: Bytes,
+        methodology: String,
+    ) -> Result<(), Error> {
+        let empty = String::from_str(&env, "");
+        Self::retire_with_reason(
+            env,
+            from,
+            amount,
+            report_hash,
+            methodology,
+            empty.clone(),
+            empty,
+        )
+    }
+
+    /// Retires credits and records why and on whose behalf in the retirement
+    /// ledger.
+    pub fn retire_with_reason(
+        env: Env,
+        from: Address,
+        amount: i128,
+        report_hash: Bytes,
+        methodology: String,
+        reason: String,
+        beneficiary: String,
+    ) -> Result<(), Error> {
+        from.require_auth();
+        retire_credits(
+            &env,
+            None,
+            &from,
+            None,
+            amount,
+            RetirementDetails {
+                report_hash,
+                methodology,
+                reason,
+                beneficiary,
+                beneficiary_address: None,
+            },
+        )
+    }
+
+    /// Retires credits paid for by `from` on behalf of someone else, e.g. a
+    /// broker or checkout flow retiring for a customer. The certificate is
+    /// issued to `beneficiary` when given; otherwise `from` keeps it and
+    /// `beneficiary_name` identifies the customer.
+    #[allow(clippy::too_many_arguments)]
+    pub fn retire_for(
+        env: Env,
+        from: Address,
+        beneficiary: Option<Address>,
+        beneficiary_name: String,
+        amount: i128,
+        report_hash: Bytes,
+        methodology: String,
+        reason: String,
+    ) -> Result<(), Error> {
+        from.require_auth();
+
+        match &beneficiary {
+            Some(addr) if *addr == from => return Err(Error::InvalidBeneficiary),
+            Some(addr) => require_not_blacklisted(&env, addr)?,
+            None if beneficiary_name.len() == 0 => return Err(Error::InvalidBeneficiary),
+            None => {}
+        }
+
+        retire_credits(
+            &env,
+            None,
+            &from,
+            None,
+            amount,
+            RetirementDetails {
+                report_hash,
+                methodology,
+                reason,
+                beneficiary: beneficiary_name,
+                beneficiary_address: beneficiary,
+            },
+        )
+    }
+
+    /// Retires `from`'s credits using an allowance granted to `spender`, e.g.
+    /// a sustainability officer acting for a treasury wallet. The retirement
+    /// and certificate are attributed to `from`.
+    pub fn retire_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        amount: i128,
+        report_hash: Bytes,
+        methodology: String,
+    ) -> Result<(), Error> {
+        spender.require_auth();
+
+        let empty = String::from_str(&env, "");
+        retire_credits(
+            &env,
+            Some(spender),
+            &from,
+            None,
+            amount,
+            RetirementDetails {
+                report_hash,
+                methodology,
+                reason: empty.clone(),
+                beneficiary: empty,
+                beneficiary_address: None,
+            },
+        )
+    }
+
+    /// Retires credits of a single batch.
+    pub fn retire_in_batch(
+        env: Env,
+        from: Address,
+        batch_id: u32,
+        amount: i128,
+        report_hash: Bytes,
+        methodology: String,
+    ) -> Result<(), Error> {
+        from.require_auth();
+
+        let empty = String::from_str(&env, "");
+        retire_credits(
+            &env,
+            None,
+            &from,
+            Some(batch_id),
+            amount,
+            RetirementDetails {
+                report_hash,
+                methodology,
+                reason: empty.clone(),
+                beneficiary: empty,
+                beneficiary_address: None,
+            },
+        )
+    }
+
+    // ── Retirement ledger ─────────────────────────────────────────────────────
+
+    /// Returns a retirement ledger entry by id.
+    pub fn get_retirement(env: Env, id: u64) -> Option<RetirementRecord> {
+        read_retirement(&env, id)
+    }
+
+    /// Returns up to `limit` retirements made by `retiree`, starting at
+    /// position `cursor` (oldest first).
+    pub fn retirements_of(
+        env: Env,
+        retiree: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> Vec<RetirementRecord> {
+        read_retiree_retirements(&env, &retiree, cursor, limit)
+    }
+
+    /// Returns the total number of retirements recorded.
+    pub fn retirement_count(env: Env) -> u64 {
+        read_next_retirement_id(&env)
+    }
+
+    // ── Retirement certificates (soulbound, non-transferable) ────────────────
+
+    /// Returns a retirement certificate by id.
+    pub fn get_certificate(env: Env, id: u32) -> Option<CertificateRecord> {
+        read_certificate(&env, id)
+    }
+
+    /// Returns the owner of a certificate: the beneficiary the credits were
+    /// retired for, which is the retiree unless `retire_for` named someone
+    /// else. Certificates cannot be transferred.
+    pub fn owner_of(env: Env, id: u32) -> Result<Address, Error> {
+        read_certificate(&env, id)
+            .map(|cert| cert.owner)
+            .ok_or(Error::CertificateNotFound)
+    }
+
+    /// Returns the metadata URI of a certificate's project.
+    pub fn token_uri(env: Env, id: u32) -> Result<String, Error> {
+        read_certificate(&env, id)
+            .map(|cert| cert.metadata_url)
+            .ok_or(Error::CertificateNotFound)
+    }
+
+    /// Returns up to `limit` certificates held by `owner`, starting at
+    /// position `cursor` (oldest first).
+    pub fn certificates_of(
+        env: Env,
+        owner: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> Vec<CertificateRecord> {
+        read_owner_certificates(&env, &owner, cursor, limit)
+    }
+
+    /// Returns the total number of certificates issued globally.
+    pub fn certificate_count(env: Env) -> u32 {
+        read_next_certificate_id(&env)
+    }
+}
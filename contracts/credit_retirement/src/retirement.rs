@@ -0,0 +1,78 @@
+use soroban_sdk::{contracttype, Address, Bytes, Env, String, Vec};
+
+use crate::index::{push_index, read_index_page, IndexKind};
+use crate::storage::{DataKey, BALANCE_BUMP_AMOUNT, BALANCE_LIFETIME_THRESHOLD};
+
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct RetirementRecord {
+    pub id: u64,
+    pub retiree: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+    pub report_hash: Bytes,
+    pub methodology: String,
+    pub reason: String,
+    pub beneficiary: String,
+    /// Account credited with the retirement: the retiree unless it was made
+    /// on behalf of another address.
+    pub beneficiary_address: Address,
+}
+
+/// Caller-supplied details of a retirement, before it is assigned an id.
+pub struct RetirementDetails {
+    pub report_hash: Bytes,
+    pub methodology: String,
+    pub reason: String,
+    pub beneficiary: String,
+    pub beneficiary_address: Option<Address>,
+}
+
+pub fn read_next_retirement_id(e: &Env) -> u64 {
+    e.storage()
+        .instance()
+        .get(&DataKey::NextRetirementID)
+        .unwrap_or(0u64)
+}
+
+pub fn increment_next_retirement_id(e: &Env) -> u64 {
+    let id = read_next_retirement_id(e) + 1;
+    e.storage()
+        .instance()
+        .set(&DataKey::NextRetirementID, &id);
+    id
+}
+
+pub fn write_retirement(e: &Env, record: RetirementRecord) {
+    let key = DataKey::Retirement(record.id);
+    e.storage().persistent().set(&key, &record);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+
+    push_index(e, IndexKind::RetireeRetirements, &record.retiree, &record.id);
+}
+
+pub fn read_retirement(e: &Env, id: u64) -> Option<RetirementRecord> {
+    e.storage()
+        .persistent()
+        .get(&DataKey::Retirement(id))
+}
+
+/// Returns up to `limit` of `retiree`'s retirements, starting at position
+/// `cursor` in the order they were made.
+pub fn read_retiree_retirements(
+    e: &Env,
+    retiree: &Address,
+    cursor: u32,
+    limit: u32,
+) -> Vec<RetirementRecord> {
+    let ids: Vec<u64> = read_index_page(e, IndexKind::RetireeRetirements, retiree, cursor, limit);
+    let mut records = Vec::new(e);
+    for id in ids.iter() {
+        if let Some(record) = read_retirement(e, id) {
+            records.push_back(record);
+        }
+    }
+    records
+}
@@ -0,0 +1,530 @@
+#![cfg(test)]
+
+use crate::index::{read_index_len, IndexKey, IndexKind};
+use crate::{CreditRetirement, CreditRetirementClient, Error};
+use carbon_credit_token::{
+    AllowancePurpose, CarbonCreditToken, CarbonCreditTokenClient, Error as TokenError, Modules,
+    DEFAULT_BATCH_ID,
+};
+use credit_compliance::{CreditCompliance, CreditComplianceClient};
+use soroban_sdk::{contract, contractimpl, testutils::Address as _, Address, Bytes, Env, String};
+
+#[contract]
+pub struct MockRbacContract;
+
+#[contractimpl]
+impl MockRbacContract {
+    pub fn get_role(_env: Env, _address: Address) -> u32 {
+        // Everyone is a Trader
+        2
+    }
+}
+
+#[contract]
+pub struct MockComplianceHook;
+
+#[contractimpl]
+impl MockComplianceHook {
+    pub fn can_transfer(_env: Env, _from: Address, _to: Address, amount: i128) -> bool {
+        amount <= 100
+    }
+}
+
+/// Registers a token bound to this module and a real compliance module, and
+/// returns `(env, token, retirement, user)`. The other modules are bare
+/// addresses: with every auth mocked, credits are issued by calling the
+/// token's `issue` directly.
+fn setup_env<'a>() -> (
+    Env,
+    CarbonCreditTokenClient<'a>,
+    CreditRetirementClient<'a>,
+    Address,
+) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let rbac_id = env.register_contract(None, MockRbacContract);
+    let compliance_id = env.register_contract(None, CreditCompliance);
+    let retirement_id = env.register_contract(None, CreditRetirement);
+    let token_id = env.register_contract(None, CarbonCreditToken);
+    let token = CarbonCreditTokenClient::new(&env, &token_id);
+
+    token.initialize(
+        &admin,
+        &rbac_id,
+        &Modules {
+            issuance: Address::generate(&env),
+            retirement: retirement_id.clone(),
+            compliance: compliance_id.clone(),
+            permits: Address::generate(&env),
+            reversals: Address::generate(&env),
+        },
+        &String::from_str(&env, "Carbon Credit Token"),
+        &String::from_str(&env, "CCT"),
+        &0u32,
+        &String::from_str(&env, "Amazon Reforestation"),
+        &String::from_str(&env, "2023"),
+        &String::from_str(&env, "Brazil"),
+        &String::from_str(&env, "https://farmcredit.xyz/amazon-1"),
+    );
+    CreditComplianceClient::new(&env, &compliance_id).initialize(&token_id);
+    let retirement = CreditRetirementClient::new(&env, &retirement_id);
+    retirement.initialize(&token_id);
+
+    let user = Address::generate(&env);
+
+    (env, token, retirement, user)
+}
+
+fn compliance_of<'a>(token: &CarbonCreditTokenClient) -> CreditComplianceClient<'a> {
+    CreditComplianceClient::new(&token.env, &token.modules().compliance)
+}
+
+fn create_agroforestry_batch(env: &Env, token: &CarbonCreditTokenClient) -> u32 {
+    token.create_batch(
+        &String::from_str(env, "Kenya Agroforestry"),
+        &String::from_str(env, "2024"),
+        &String::from_str(env, "Kenya"),
+        &String::from_str(env, "https://farmcredit.xyz/kenya-1"),
+    )
+}
+
+#[test]
+fn test_initialize_binds_token_once() {
+    let (_, token, retirement, _) = setup_env();
+
+    let result = retirement.try_initialize(&token.address);
+    assert_eq!(result, Err(Ok(Error::AlreadyInitialized)));
+}
+
+#[test]
+fn test_retire_and_certificate_issuance() {
+    let (env, token, retirement, user) = setup_env();
+
+    let hash = Bytes::from_slice(&env, b"report_hash_2");
+    let methodology = String::from_str(&env, "VCS");
+
+    token.issue(&user, &DEFAULT_BATCH_ID, &1000);
+    assert_eq!(token.balance(&user), 1000);
+
+    // Retire some tokens
+    retirement.retire(&user, &300, &hash, &methodology);
+
+    assert_eq!(token.balance(&user), 700);
+    assert_eq!(token.total_retired(), 300);
+
+    // Verify NFT creation
+    assert_eq!(retirement.certificate_count(), 1);
+    let cert = retirement.get_certificate(&1).unwrap();
+    assert_eq!(cert.owner, user);
+    assert_eq!(cert.amount, 300);
+    assert_eq!(cert.project_name, String::from_str(&env, "Amazon Reforestation"));
+    assert_eq!(cert.vintage, String::from_str(&env, "2023"));
+    assert_eq!(cert.location, String::from_str(&env, "Brazil"));
+    assert_eq!(cert.metadata_url, String::from_str(&env, "https://farmcredit.xyz/amazon-1"));
+}
+
+#[test]
+fn test_multiple_retirements() {
+    let (env, token, retirement, user) = setup_env();
+
+    let hash2 = Bytes::from_slice(&env, b"h2");
+    let hash3 = Bytes::from_slice(&env, b"h3");
+    let methodology = String::from_str(&env, "VCS");
+
+    token.issue(&user, &DEFAULT_BATCH_ID, &1000);
+
+    retirement.retire(&user, &100, &hash2, &methodology);
+    retirement.retire(&user, &200, &hash3, &methodology);
+
+    assert_eq!(retirement.certificate_count(), 2);
+    assert_eq!(retirement.get_certificate(&1).unwrap().amount, 100);
+    assert_eq!(retirement.get_certificate(&2).unwrap().amount, 200);
+    assert_eq!(token.total_retired(), 300);
+}
+
+#[test]
+fn test_retire_in_batch() {
+    let (env, token, retirement, user) = setup_env();
+    let batch_id = create_agroforestry_batch(&env, &token);
+
+    token.issue(&user, &DEFAULT_BATCH_ID, &100);
+    token.issue(&user, &batch_id, &100);
+
+    retirement.retire_in_batch(
+        &user,
+        &batch_id,
+        &30,
+        &Bytes::from_slice(&env, b"r1"),
+        &String::from_str(&env, "VCS"),
+    );
+    assert_eq!(token.batch_balance(&user, &batch_id), 70);
+    assert_eq!(token.get_batch(&batch_id).unwrap().retired, 30);
+    assert_eq!(token.get_batch(&1).unwrap().retired, 0);
+    assert_eq!(retirement.get_certificate(&1).unwrap().batch_id, batch_id);
+
+    let result = retirement.try_retire_in_batch(
+        &user,
+        &batch_id,
+        &71,
+        &Bytes::from_slice(&env, b"r2"),
+        &String::from_str(&env, "VCS"),
+    );
+    assert_eq!(result, Err(Ok(Error::InsufficientBalance)));
+}
+
+// ── Retirement ledger ─────────────────────────────────────────────────────────
+
+#[test]
+fn test_retire_with_reason_writes_ledger_entry() {
+    let (env, token, retirement, user) = setup_env();
+
+    token.issue(&user, &DEFAULT_BATCH_ID, &1000);
+    retirement.retire_with_reason(
+        &user,
+        &250,
+        &Bytes::from_slice(&env, b"l2"),
+        &String::from_str(&env, "VCS"),
+        &String::from_str(&env, "FY2024 scope 1 offset"),
+        &String::from_str(&env, "Acme Corp"),
+    );
+
+    assert_eq!(retirement.retirement_count(), 1);
+    let record = retirement.get_retirement(&1).unwrap();
+    assert_eq!(record.retiree, user);
+    assert_eq!(record.amount, 250);
+    assert_eq!(record.reason, String::from_str(&env, "FY2024 scope 1 offset"));
+    assert_eq!(record.beneficiary, String::from_str(&env, "Acme Corp"));
+    assert_eq!(record.methodology, String::from_str(&env, "VCS"));
+    assert!(retirement.get_retirement(&2).is_none());
+}
+
+#[test]
+fn test_every_retire_is_recorded() {
+    let (env, token, retirement, user) = setup_env();
+    let methodology = String::from_str(&env, "VCS");
+
+    token.issue(&user, &DEFAULT_BATCH_ID, &1000);
+    retirement.retire(&user, &100, &Bytes::from_slice(&env, b"l2"), &methodology);
+    retirement.retire_in_batch(&user, &1, &50, &Bytes::from_slice(&env, b"l3"), &methodology);
+
+    assert_eq!(retirement.retirement_count(), 2);
+    let record = retirement.get_retirement(&1).unwrap();
+    assert_eq!(record.amount, 100);
+    assert_eq!(record.reason, String::from_str(&env, ""));
+    assert_eq!(retirement.get_retirement(&2).unwrap().amount, 50);
+}
+
+#[test]
+fn test_retirements_of_paginates_per_retiree() {
+    let (env, token, retirement, user) = setup_env();
+    let other = Address::generate(&env);
+    let methodology = String::from_str(&env, "VCS");
+
+    token.issue(&user, &DEFAULT_BATCH_ID, &1000);
+    token.issue(&other, &DEFAULT_BATCH_ID, &1000);
+    retirement.retire(&user, &10, &Bytes::from_slice(&env, b"p3"), &methodology);
+    retirement.retire(&other, &20, &Bytes::from_slice(&env, b"p4"), &methodology);
+    retirement.retire(&user, &30, &Bytes::from_slice(&env, b"p5"), &methodology);
+    retirement.retire(&user, &40, &Bytes::from_slice(&env, b"p6"), &methodology);
+
+    let first_page = retirement.retirements_of(&user, &0, &2);
+    assert_eq!(first_page.len(), 2);
+    assert_eq!(first_page.get(0).unwrap().amount, 10);
+    assert_eq!(first_page.get(1).unwrap().amount, 30);
+
+    let second_page = retirement.retirements_of(&user, &2, &2);
+    assert_eq!(second_page.len(), 1);
+    assert_eq!(second_page.get(0).unwrap().id, 4);
+
+    assert_eq!(retirement.retirements_of(&user, &5, &2).len(), 0);
+    assert_eq!(retirement.retirements_of(&other, &0, &10).len(), 1);
+
+    env.as_contract(&retirement.address, || {
+        let key = IndexKey::Entry(IndexKind::RetireeRetirements, user.clone(), 1);
+        let id: u64 = env.storage().persistent().get(&key).unwrap();
+        assert_eq!(id, 3);
+    });
+}
+
+// ── Retirement certificates ───────────────────────────────────────────────────
+
+#[test]
+fn test_certificate_owner_uri_and_pagination() {
+    let (env, token, retirement, user) = setup_env();
+    let methodology = String::from_str(&env, "VCS");
+
+    token.issue(&user, &DEFAULT_BATCH_ID, &1000);
+    retirement.retire(&user, &10, &Bytes::from_slice(&env, b"c2"), &methodology);
+    retirement.retire(&user, &20, &Bytes::from_slice(&env, b"c3"), &methodology);
+    retirement.retire(&user, &30, &Bytes::from_slice(&env, b"c4"), &methodology);
+
+    assert_eq!(retirement.owner_of(&2), user);
+    assert_eq!(
+        retirement.token_uri(&2),
+        String::from_str(&env, "https://farmcredit.xyz/amazon-1")
+    );
+    assert_eq!(retirement.get_certificate(&2).unwrap().retirement_id, 2);
+
+    let page = retirement.certificates_of(&user, &1, &5);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().amount, 20);
+    assert_eq!(page.get(1).unwrap().amount, 30);
+    assert_eq!(retirement.certificates_of(&user, &3, &5).len(), 0);
+
+    env.as_contract(&retirement.address, || {
+        let kind = IndexKind::OwnerCertificates;
+        assert_eq!(read_index_len(&env, kind, &user), 3);
+        let id: u32 = env
+            .storage()
+            .persistent()
+            .get(&IndexKey::Entry(kind, user.clone(), 2))
+            .unwrap();
+        assert_eq!(id, 3);
+    });
+
+    assert_eq!(retirement.try_owner_of(&4), Err(Ok(Error::CertificateNotFound)));
+    assert_eq!(retirement.try_token_uri(&4), Err(Ok(Error::CertificateNotFound)));
+}
+
+#[test]
+fn test_certificate_snapshots_each_retired_batch() {
+    let (env, token, retirement, user) = setup_env();
+    let batch_id = create_agroforestry_batch(&env, &token);
+
+    token.issue(&user, &DEFAULT_BATCH_ID, &100);
+    token.issue(&user, &batch_id, &100);
+
+    // Spans both batches: one certificate per project/vintage.
+    retirement.retire(&user, &150, &Bytes::from_slice(&env, b"c3"), &String::from_str(&env, "VCS"));
+
+    assert_eq!(retirement.certificate_count(), 2);
+    let first = retirement.get_certificate(&1).unwrap();
+    assert_eq!(first.batch_id, 1);
+    assert_eq!(first.amount, 100);
+    assert_eq!(first.vintage, String::from_str(&env, "2023"));
+
+    let second = retirement.get_certificate(&2).unwrap();
+    assert_eq!(second.batch_id, batch_id);
+    assert_eq!(second.amount, 50);
+    assert_eq!(second.project_name, String::from_str(&env, "Kenya Agroforestry"));
+    assert_eq!(second.retirement_id, first.retirement_id);
+}
+
+// ── Retire on behalf of a beneficiary ─────────────────────────────────────────
+
+#[test]
+fn test_retire_for_issues_certificate_to_beneficiary() {
+    let (env, token, retirement, broker) = setup_env();
+    let customer = Address::generate(&env);
+
+    token.issue(&broker, &DEFAULT_BATCH_ID, &500);
+    retirement.retire_for(
+        &broker,
+        &Some(customer.clone()),
+        &String::from_str(&env, "Jane Doe"),
+        &120,
+        &Bytes::from_slice(&env, b"f2"),
+        &String::from_str(&env, "VCS"),
+        &String::from_str(&env, "checkout #42"),
+    );
+
+    assert_eq!(token.balance(&broker), 380);
+    assert_eq!(token.balance(&customer), 0);
+
+    let cert = retirement.get_certificate(&1).unwrap();
+    assert_eq!(cert.owner, customer);
+    assert_eq!(cert.retiree, broker);
+    assert_eq!(cert.beneficiary, String::from_str(&env, "Jane Doe"));
+    assert_eq!(retirement.certificates_of(&customer, &0, &10).len(), 1);
+    assert_eq!(retirement.certificates_of(&broker, &0, &10).len(), 0);
+
+    let record = retirement.get_retirement(&1).unwrap();
+    assert_eq!(record.retiree, broker);
+    assert_eq!(record.beneficiary_address, customer);
+}
+
+#[test]
+fn test_retire_for_name_only_keeps_certificate_with_payer() {
+    let (env, token, retirement, broker) = setup_env();
+
+    token.issue(&broker, &DEFAULT_BATCH_ID, &500);
+    retirement.retire_for(
+        &broker,
+        &None,
+        &String::from_str(&env, "Walk-in customer"),
+        &50,
+        &Bytes::from_slice(&env, b"f2"),
+        &String::from_str(&env, "VCS"),
+        &String::from_str(&env, ""),
+    );
+
+    let cert = retirement.get_certificate(&1).unwrap();
+    assert_eq!(cert.owner, broker);
+    assert_eq!(cert.beneficiary, String::from_str(&env, "Walk-in customer"));
+}
+
+#[test]
+fn test_retire_for_requires_a_distinct_beneficiary() {
+    let (env, token, retirement, broker) = setup_env();
+    let hash = Bytes::from_slice(&env, b"f2");
+    let methodology = String::from_str(&env, "VCS");
+    let empty = String::from_str(&env, "");
+
+    token.issue(&broker, &DEFAULT_BATCH_ID, &500);
+
+    let result =
+        retirement.try_retire_for(&broker, &None, &empty, &10, &hash, &methodology, &empty);
+    assert_eq!(result, Err(Ok(Error::InvalidBeneficiary)));
+
+    let result = retirement.try_retire_for(
+        &broker,
+        &Some(broker.clone()),
+        &empty,
+        &10,
+        &hash,
+        &methodology,
+        &empty,
+    );
+    assert_eq!(result, Err(Ok(Error::InvalidBeneficiary)));
+
+    let customer = Address::generate(&env);
+    compliance_of(&token).blacklist(&customer);
+    let result = retirement.try_retire_for(
+        &broker,
+        &Some(customer),
+        &empty,
+        &10,
+        &hash,
+        &methodology,
+        &empty,
+    );
+    assert_eq!(result, Err(Ok(Error::Blacklisted)));
+}
+
+// ── Allowance-based retirement ────────────────────────────────────────────────
+
+#[test]
+fn test_retire_from_spends_allowance_and_credits_owner() {
+    let (env, token, retirement, treasury) = setup_env();
+    let officer = Address::generate(&env);
+
+    token.issue(&treasury, &DEFAULT_BATCH_ID, &1000);
+    token.approve(&treasury, &officer, &300, &1000);
+
+    retirement.retire_from(
+        &officer,
+        &treasury,
+        &200,
+        &Bytes::from_slice(&env, b"a2"),
+        &String::from_str(&env, "Gold Standard"),
+    );
+
+    assert_eq!(token.balance(&treasury), 800);
+    assert_eq!(token.allowance(&treasury, &officer), 100);
+    assert_eq!(token.total_retired(), 200);
+
+    let cert = retirement.get_certificate(&1).unwrap();
+    assert_eq!(cert.owner, treasury);
+    assert_eq!(cert.retiree, treasury);
+    assert_eq!(retirement.get_retirement(&1).unwrap().retiree, treasury);
+    assert_eq!(retirement.certificates_of(&officer, &0, &10).len(), 0);
+}
+
+#[test]
+fn test_retire_from_rejects_insufficient_allowance() {
+    let (env, token, retirement, treasury) = setup_env();
+    let officer = Address::generate(&env);
+
+    token.issue(&treasury, &DEFAULT_BATCH_ID, &1000);
+    token.approve(&treasury, &officer, &50, &1000);
+
+    let result = retirement.try_retire_from(
+        &officer,
+        &treasury,
+        &51,
+        &Bytes::from_slice(&env, b"a2"),
+        &String::from_str(&env, "VCS"),
+    );
+    assert_eq!(result, Err(Ok(Error::InsufficientAllowance)));
+    assert_eq!(token.balance(&treasury), 1000);
+}
+
+#[test]
+fn test_retire_only_allowance_spent_by_retire_from() {
+    let (env, token, retirement, treasury) = setup_env();
+    let custodian = Address::generate(&env);
+
+    token.issue(&treasury, &DEFAULT_BATCH_ID, &1000);
+    token.approve_with_purpose(&treasury, &custodian, &300, &1000, &AllowancePurpose::Retire);
+
+    retirement.retire_from(
+        &custodian,
+        &treasury,
+        &100,
+        &Bytes::from_slice(&env, b"p2"),
+        &String::from_str(&env, "VCS"),
+    );
+    assert_eq!(token.allowance(&treasury, &custodian), 200);
+    assert_eq!(token.allowance_purpose(&treasury, &custodian), AllowancePurpose::Retire);
+
+    token.approve_with_purpose(&treasury, &custodian, &300, &1000, &AllowancePurpose::Transfer);
+    let result = retirement.try_retire_from(
+        &custodian,
+        &treasury,
+        &100,
+        &Bytes::from_slice(&env, b"p3"),
+        &String::from_str(&env, "VCS"),
+    );
+    assert_eq!(result, Err(Ok(Error::AllowancePurposeMismatch)));
+}
+
+// ── Compliance ────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_frozen_amount_cannot_be_retired() {
+    let (env, token, retirement, treasury) = setup_env();
+    let compliance = compliance_of(&token);
+
+    token.issue(&treasury, &DEFAULT_BATCH_ID, &1000);
+    compliance.freeze_amount(&treasury, &600, &String::from_str(&env, "court order 42"));
+
+    let result = retirement.try_retire(
+        &treasury,
+        &401,
+        &Bytes::from_slice(&env, b"p2"),
+        &String::from_str(&env, "VCS"),
+    );
+    assert_eq!(result, Err(Ok(Error::BalanceFrozen)));
+    let methodology = String::from_str(&env, "VCS");
+    retirement.retire(&treasury, &400, &Bytes::from_slice(&env, b"p2"), &methodology);
+    assert_eq!(token.balance(&treasury), 600);
+    assert_eq!(retirement.certificate_count(), 1);
+}
+
+#[test]
+fn test_compliance_hook_gates_retirements() {
+    let (env, token, retirement, treasury) = setup_env();
+    let hook_id = env.register_contract(None, MockComplianceHook);
+
+    token.issue(&treasury, &DEFAULT_BATCH_ID, &1000);
+    compliance_of(&token).set_compliance_hook(&Some(hook_id));
+
+    let result = retirement.try_retire(
+        &treasury,
+        &101,
+        &Bytes::from_slice(&env, b"p2"),
+        &String::from_str(&env, "VCS"),
+    );
+    assert_eq!(result, Err(Ok(Error::TransferNotCompliant)));
+    assert_eq!(retirement.retirement_count(), 0);
+
+    // The token applies the same check to retirements it is asked for directly.
+    let result = token.try_retire_credits(&None, &treasury, &None, &101);
+    assert_eq!(result, Err(Ok(TokenError::TransferNotCompliant)));
+
+    let methodology = String::from_str(&env, "VCS");
+    retirement.retire(&treasury, &100, &Bytes::from_slice(&env, b"p2"), &methodology);
+    assert_eq!(token.balance(&treasury), 900);
+}
@@ -0,0 +1,88 @@
+use soroban_sdk::{contractclient, contracttype, Address, Env, String, Vec};
+
+use crate::error::Error;
+use crate::storage::read_token;
+
+/// Mirror of the token's `CreditBatch`; the encoding must match.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct CreditBatch {
+    pub id: u32,
+    pub project_name: String,
+    pub vintage: String,
+    pub location: String,
+    pub metadata_url: String,
+    pub issued: i128,
+    pub retired: i128,
+    pub reversed: i128,
+}
+
+/// Mirror of the token's `BatchAmount`; the encoding must match.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct BatchAmount {
+    pub batch_id: u32,
+    pub amount: i128,
+}
+
+/// Mirror of the token's `Modules`; the encoding must match.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct Modules {
+    pub issuance: Address,
+    pub retirement: Address,
+    pub compliance: Address,
+    pub permits: Address,
+    pub reversals: Address,
+}
+
+/// The parts of the carbon credit token this module calls.
+#[contractclient(name = "TokenClient")]
+#[allow(dead_code)]
+pub trait TokenInterface {
+    fn super_admin(env: Env) -> Address;
+    fn rbac_contract(env: Env) -> Address;
+    fn governance_mode(env: Env) -> bool;
+    fn modules(env: Env) -> Modules;
+    fn get_batch(env: Env, batch_id: u32) -> Option<CreditBatch>;
+
+    /// Burns credits as a retirement. Accepts this module only.
+    fn retire_credits(
+        env: Env,
+        spender: Option<Address>,
+        from: Address,
+        batch_id: Option<u32>,
+        amount: i128,
+    ) -> Result<Vec<BatchAmount>, Error>;
+}
+
+/// Cross-contract client interface for the token's compliance module.
+#[contractclient(name = "ComplianceClient")]
+#[allow(dead_code)]
+pub trait ComplianceInterface {
+    fn is_blacklisted(env: Env, addr: Address) -> bool;
+}
+
+pub fn token_client(e: &Env) -> TokenClient<'_> {
+    TokenClient::new(e, &read_token(e))
+}
+
+/// The address admin entrypoints require auth from: the token's SuperAdmin,
+/// or, in governance mode, its RBAC contract.
+pub fn read_admin_authority(e: &Env) -> Address {
+    let token = token_client(e);
+    if token.governance_mode() {
+        token.rbac_contract()
+    } else {
+        token.super_admin()
+    }
+}
+
+pub fn require_not_blacklisted(e: &Env, addr: &Address) -> Result<(), Error> {
+    let compliance = token_client(e).modules().compliance;
+    if ComplianceClient::new(e, &compliance).is_blacklisted(addr) {
+        Err(Error::Blacklisted)
+    } else {
+        Ok(())
+    }
+}
@@ -0,0 +1,35 @@
+use soroban_sdk::contracterror;
+
+/// Codes match the token's, so an error reads the same whichever contract
+/// raised it.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    /// The provided amount is negative.
+    NegativeAmount = 1,
+    /// The account does not have enough balance.
+    InsufficientBalance = 2,
+    /// The contract is already initialized.
+    AlreadyInitialized = 4,
+    /// No batch exists with the given id.
+    BatchNotFound = 13,
+    /// No mint was made with the given report hash.
+    IssuanceNotFound = 21,
+    /// The issuance has already been reversed in full.
+    IssuanceAlreadyReversed = 22,
+    /// No buffer pool account has been configured.
+    BufferNotConfigured = 24,
+    /// The amount exceeds the part of the issuance not yet reversed.
+    ExceedsUnreversedAmount = 25,
+}
+
+/// Passes on the error of a failed `try_` call to the token or the issuance
+/// module. Anything else, such as a trap in the callee, aborts the call.
+pub fn forward<T, C, I>(result: Result<Result<T, C>, Result<Error, I>>) -> Result<T, Error> {
+    match result {
+        Ok(Ok(value)) => Ok(value),
+        Err(Ok(error)) => Err(error),
+        _ => panic!("module call failed"),
+    }
+}
@@ -0,0 +1,56 @@
+use soroban_sdk::{contracttype, symbol_short, Address, Bytes, Env};
+
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct ClawbackEvent {
+    pub report_hash: Bytes,
+    pub holder: Address,
+    pub batch_id: u32,
+    pub amount: i128,
+}
+
+impl ClawbackEvent {
+    pub fn publish(self, env: &Env) {
+        env.events().publish(
+            (symbol_short!("clawback"), self.holder),
+            (self.report_hash, self.batch_id, self.amount),
+        );
+    }
+}
+
+/// Summary of a `reverse_issuance` call. `shortfall` is the part of the
+/// issuance still unrecovered, e.g. because it was retired or moved to
+/// holders that were not listed.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct IssuanceReversedEvent {
+    pub report_hash: Bytes,
+    pub recovered: i128,
+    pub shortfall: i128,
+}
+
+impl IssuanceReversedEvent {
+    pub fn publish(self, env: &Env) {
+        env.events().publish(
+            (symbol_short!("reversed"), self.report_hash),
+            (self.recovered, self.shortfall),
+        );
+    }
+}
+
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct BufferCancelEvent {
+    pub report_hash: Bytes,
+    pub account: Address,
+    pub amount: i128,
+}
+
+impl BufferCancelEvent {
+    pub fn publish(self, env: &Env) {
+        env.events().publish(
+            (symbol_short!("buf_cncl"), self.account),
+            (self.report_hash, self.amount),
+        );
+    }
+}
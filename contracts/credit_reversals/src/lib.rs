@@ -0,0 +1,200 @@
+#![no_std]
+
+mod error;
+mod events;
+mod storage;
+mod token;
+#[cfg(test)]
+mod test;
+
+pub use error::Error;
+use soroban_sdk::{contract, contractimpl, vec, Address, Bytes, BytesN, Env, Vec};
+
+use crate::error::forward;
+use crate::events::{BufferCancelEvent, ClawbackEvent, IssuanceReversedEvent};
+use crate::storage::{is_initialized, write_token, INSTANCE_BUMP_AMOUNT, INSTANCE_LIFETIME_THRESHOLD};
+use crate::token::{issuance_client, read_admin_authority, token_client};
+
+fn check_nonnegative_amount(amount: i128) -> Result<(), Error> {
+    if amount < 0 {
+        Err(Error::NegativeAmount)
+    } else {
+        Ok(())
+    }
+}
+
+/// Takes `amount` of `batch_id` back from `holder` for a reversal, ignoring
+/// any freeze.
+fn claw_back(
+    env: &Env,
+    report_hash: &Bytes,
+    holder: &Address,
+    batch_id: u32,
+    amount: i128,
+) -> Result<(), Error> {
+    forward(token_client(env).try_claw_back(holder, &batch_id, &amount))?;
+    ClawbackEvent {
+        report_hash: report_hash.clone(),
+        holder: holder.clone(),
+        batch_id,
+        amount,
+    }
+    .publish(env);
+    Ok(())
+}
+
+/// Reversals module of the carbon credit token: claws back credits issued
+/// against an invalidated report, from holders or the buffer pool. The
+/// issuance module books what this module recovers.
+#[contract]
+pub struct CreditReversals;
+
+#[contractimpl]
+impl CreditReversals {
+    /// Binds the module to `token`. Must be signed by the token's SuperAdmin
+    /// (or RBAC contract in governance mode). Can only be called once.
+    pub fn initialize(env: Env, token: Address) -> Result<(), Error> {
+        if is_initialized(&env) {
+            return Err(Error::AlreadyInitialized);
+        }
+
+        write_token(&env, &token);
+        read_admin_authority(&env).require_auth();
+        Ok(())
+    }
+
+    /// Replaces the module code with the uploaded wasm `new_wasm_hash`.
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+        read_admin_authority(&env).require_auth();
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+    }
+
+    // ── Issuance reversal (SuperAdmin only) ───────────────────────────────────
+
+    /// Claws back credits minted from `report_hash` after the report is
+    /// invalidated. Credits of the issuance's batch are taken from the
+    /// original recipient first, then from `holders` in order, up to the net
+    /// amount the recipient was credited; the buffered share is taken from
+    /// the buffer pool account. Whatever cannot be
+    /// recovered (already retired, or held elsewhere) is reported as the
+    /// shortfall; the call may be repeated with further holders.
+    ///
+    /// Returns the amount recovered by this call.
+    pub fn reverse_issuance(
+        env: Env,
+        report_hash: Bytes,
+        holders: Vec<Address>,
+    ) -> Result<i128, Error> {
+        let authority = read_admin_authority(&env);
+        authority.require_auth();
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        let issuance_module = issuance_client(&env);
+        let issuance = issuance_module
+            .get_issuance(&report_hash)
+            .ok_or(Error::IssuanceNotFound)?;
+        let outstanding = issuance.amount - issuance.reversed;
+        if outstanding == 0 {
+            return Err(Error::IssuanceAlreadyReversed);
+        }
+
+        // Holders only ever received the net share; the buffered share went
+        // to the buffer pool account and is recovered from there.
+        let token = token_client(&env);
+        let buffer = issuance_module.buffer_config().map(|buffer| buffer.account);
+        let holder_reversed = issuance.reversed - issuance.buffer_reversed;
+        let mut holders_outstanding =
+            (issuance.amount - issuance.buffered - holder_reversed).clamp(0, outstanding);
+
+        let mut sources = vec![&env, issuance.recipient.clone()];
+        for holder in holders.iter() {
+            if !sources.contains(&holder) && Some(&holder) != buffer.as_ref() {
+                sources.push_back(holder);
+            }
+        }
+
+        let mut recovered = 0;
+        for holder in sources.iter() {
+            if holders_outstanding == 0 {
+                break;
+            }
+            let balance = token.batch_balance(&holder, &issuance.batch_id);
+            let take = balance.min(holders_outstanding);
+            if take == 0 {
+                continue;
+            }
+            claw_back(&env, &report_hash, &holder, issuance.batch_id, take)?;
+            recovered += take;
+            holders_outstanding -= take;
+        }
+
+        let mut from_buffer = 0;
+        if let Some(account) = buffer {
+            let buffer_outstanding = (issuance.buffered - issuance.buffer_reversed)
+                .clamp(0, outstanding - recovered);
+            let balance = token.batch_balance(&account, &issuance.batch_id);
+            from_buffer = balance.min(buffer_outstanding);
+            if from_buffer > 0 {
+                claw_back(&env, &report_hash, &account, issuance.batch_id, from_buffer)?;
+                recovered += from_buffer;
+            }
+        }
+
+        if recovered > 0 {
+            forward(issuance_module.try_record_reversal(&report_hash, &recovered, &from_buffer))?;
+        }
+
+        IssuanceReversedEvent {
+            report_hash,
+            recovered,
+            shortfall: outstanding - recovered,
+        }
+        .publish(&env);
+        Ok(recovered)
+    }
+
+    /// Cancels `amount` buffer pool credits to cover a reversal of the
+    /// issuance backed by `report_hash`. The cancelled credits count towards
+    /// the issuance's reversed amount, so together with `reverse_issuance`
+    /// at most the issued amount is ever unwound. They are taken from the
+    /// issuance's batch and, like a reversal, skip freezes and the
+    /// compliance hook.
+    pub fn cancel_buffer_credits(
+        env: Env,
+        report_hash: Bytes,
+        amount: i128,
+    ) -> Result<(), Error> {
+        let authority = read_admin_authority(&env);
+        authority.require_auth();
+        check_nonnegative_amount(amount)?;
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        let issuance_module = issuance_client(&env);
+        let buffer = issuance_module
+            .buffer_config()
+            .ok_or(Error::BufferNotConfigured)?;
+        let issuance = issuance_module
+            .get_issuance(&report_hash)
+            .ok_or(Error::IssuanceNotFound)?;
+        if amount > issuance.amount - issuance.reversed {
+            return Err(Error::ExceedsUnreversedAmount);
+        }
+
+        forward(token_client(&env).try_claw_back(&buffer.account, &issuance.batch_id, &amount))?;
+        forward(issuance_module.try_record_reversal(&report_hash, &amount, &amount))?;
+
+        BufferCancelEvent {
+            report_hash,
+            account: buffer.account,
+            amount,
+        }
+        .publish(&env);
+        Ok(())
+    }
+}
@@ -0,0 +1,297 @@
+#![cfg(test)]
+
+use crate::{CreditReversals, CreditReversalsClient, Error};
+use carbon_credit_token::{CarbonCreditToken, CarbonCreditTokenClient, Modules};
+use credit_compliance::{CreditCompliance, CreditComplianceClient};
+use credit_issuance::{CreditIssuance, CreditIssuanceClient};
+use soroban_sdk::{
+    contract, contractimpl, testutils::Address as _, vec, Address, Bytes, Env, String,
+};
+
+#[contract]
+pub struct MockRbacContract;
+
+#[contractimpl]
+impl MockRbacContract {
+    pub fn has_role(_env: Env, _address: Address, _role: String) -> bool {
+        true
+    }
+
+    pub fn get_role(_env: Env, _address: Address) -> u32 {
+        // Everyone is a Trader
+        2
+    }
+}
+
+#[contract]
+pub struct MockComplianceHook;
+
+#[contractimpl]
+impl MockComplianceHook {
+    pub fn can_transfer(_env: Env, _from: Address, _to: Address, _amount: i128) -> bool {
+        false
+    }
+}
+
+#[contract]
+pub struct MockVerifierRegistry;
+
+#[contractimpl]
+impl MockVerifierRegistry {
+    pub fn submit_report_hash(env: Env, verifier: Address, farmer: Address, metric_hash: String) {
+        let report = (verifier, farmer, env.ledger().sequence());
+        env.storage().instance().set(&metric_hash, &report);
+    }
+
+    pub fn is_verifier_active(_env: Env, _verifier: Address) -> bool {
+        true
+    }
+
+    pub fn get_report(env: Env, metric_hash: String) -> Option<(Address, Address, u32)> {
+        env.storage().instance().get(&metric_hash)
+    }
+}
+
+struct Setup<'a> {
+    env: Env,
+    token: CarbonCreditTokenClient<'a>,
+    issuance: CreditIssuanceClient<'a>,
+    reversals: CreditReversalsClient<'a>,
+    verifier: Address,
+    farmer: Address,
+}
+
+impl Setup<'_> {
+    /// Files a report for `to` and mints `amount` into `batch_id` with it.
+    fn mint(&self, to: &Address, batch_id: u32, amount: i128, hash: &[u8]) {
+        let registry_id = self.issuance.verifier_registry();
+        let registry = MockVerifierRegistryClient::new(&self.env, &registry_id);
+        let metric_hash = String::from_bytes(&self.env, hash);
+        registry.submit_report_hash(&self.verifier, to, &metric_hash);
+        let hash = Bytes::from_slice(&self.env, hash);
+        self.issuance
+            .mint_to_batch(&self.verifier, to, &batch_id, &amount, &hash);
+    }
+}
+
+/// Registers a token bound to this module and real issuance and compliance
+/// modules. The retirement, permits, ex-ante and mint approvals modules are
+/// bare addresses: with every auth mocked, credits are retired by calling
+/// the token's `retire_credits` directly.
+fn setup_env<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let rbac_id = env.register_contract(None, MockRbacContract);
+    let registry_id = env.register_contract(None, MockVerifierRegistry);
+    let compliance_id = env.register_contract(None, CreditCompliance);
+    let issuance_id = env.register_contract(None, CreditIssuance);
+    let reversals_id = env.register_contract(None, CreditReversals);
+    let token_id = env.register_contract(None, CarbonCreditToken);
+    let token = CarbonCreditTokenClient::new(&env, &token_id);
+
+    token.initialize(
+        &admin,
+        &rbac_id,
+        &Modules {
+            issuance: issuance_id.clone(),
+            retirement: Address::generate(&env),
+            compliance: compliance_id.clone(),
+            permits: Address::generate(&env),
+            reversals: reversals_id.clone(),
+        },
+        &String::from_str(&env, "Carbon Credit Token"),
+        &String::from_str(&env, "CCT"),
+        &0u32,
+        &String::from_str(&env, "Amazon Reforestation"),
+        &String::from_str(&env, "2023"),
+        &String::from_str(&env, "Brazil"),
+        &String::from_str(&env, "https://farmcredit.xyz/amazon-1"),
+    );
+    CreditComplianceClient::new(&env, &compliance_id).initialize(&token_id);
+    let issuance = CreditIssuanceClient::new(&env, &issuance_id);
+    let unused = Address::generate(&env);
+    issuance.initialize(&token_id, &registry_id, &unused, &unused);
+    let reversals = CreditReversalsClient::new(&env, &reversals_id);
+    reversals.initialize(&token_id);
+
+    let verifier = Address::generate(&env);
+    let farmer = Address::generate(&env);
+
+    Setup {
+        env,
+        token,
+        issuance,
+        reversals,
+        verifier,
+        farmer,
+    }
+}
+
+fn create_agroforestry_batch(env: &Env, token: &CarbonCreditTokenClient) -> u32 {
+    token.create_batch(
+        &String::from_str(env, "Kenya Agroforestry"),
+        &String::from_str(env, "2024"),
+        &String::from_str(env, "Kenya"),
+        &String::from_str(env, "https://farmcredit.xyz/kenya-1"),
+    )
+}
+
+#[test]
+fn test_initialize_binds_token_once() {
+    let s = setup_env();
+
+    let result = s.reversals.try_initialize(&s.token.address);
+    assert_eq!(result, Err(Ok(Error::AlreadyInitialized)));
+}
+
+#[test]
+fn test_reverse_issuance_claws_back_from_holders() {
+    let s = setup_env();
+    let (env, token) = (&s.env, &s.token);
+    let buyer = Address::generate(env);
+    let hash = Bytes::from_slice(env, b"mrv-0001");
+
+    s.mint(&s.farmer, 1, 1000, b"mrv-0001");
+    token.transfer(&s.farmer, &buyer, &400);
+
+    let recovered = s.reversals.reverse_issuance(&hash, &vec![env, buyer.clone()]);
+    assert_eq!(recovered, 1000);
+    assert_eq!(token.balance(&s.farmer), 0);
+    assert_eq!(token.balance(&buyer), 0);
+    assert_eq!(token.total_supply(), 0);
+    assert_eq!(s.issuance.get_issuance(&hash).unwrap().reversed, 1000);
+    assert_eq!(token.get_batch(&1).unwrap().reversed, 1000);
+
+    let result = s.reversals.try_reverse_issuance(&hash, &vec![env]);
+    assert_eq!(result, Err(Ok(Error::IssuanceAlreadyReversed)));
+}
+
+#[test]
+fn test_reverse_issuance_reports_shortfall() {
+    let s = setup_env();
+    let (env, token) = (&s.env, &s.token);
+    let buyer = Address::generate(env);
+    let hash = Bytes::from_slice(env, b"mrv-0001");
+
+    s.mint(&s.farmer, 1, 1000, b"mrv-0001");
+    token.retire_credits(&None, &s.farmer, &None, &300);
+    token.transfer(&s.farmer, &buyer, &200);
+
+    // Only the recipient is searched: the retired and transferred credits are short.
+    assert_eq!(s.reversals.reverse_issuance(&hash, &vec![env]), 500);
+    assert_eq!(token.total_supply(), 200);
+    assert_eq!(s.issuance.get_issuance(&hash).unwrap().reversed, 500);
+
+    // A later call can recover what was transferred, but not what was retired.
+    assert_eq!(s.reversals.reverse_issuance(&hash, &vec![env, buyer.clone()]), 200);
+    assert_eq!(token.balance(&buyer), 0);
+    assert_eq!(token.total_supply(), 0);
+    assert_eq!(s.issuance.get_issuance(&hash).unwrap().reversed, 700);
+    assert_eq!(token.total_retired(), 300);
+}
+
+#[test]
+fn test_reverse_unknown_issuance_rejected() {
+    let s = setup_env();
+    let hash = Bytes::from_slice(&s.env, b"nope");
+    let result = s.reversals.try_reverse_issuance(&hash, &vec![&s.env]);
+    assert_eq!(result, Err(Ok(Error::IssuanceNotFound)));
+}
+
+#[test]
+fn test_reverse_issuance_ignores_freezes() {
+    let s = setup_env();
+    let (env, token) = (&s.env, &s.token);
+    let hash = Bytes::from_slice(env, b"mrv-0001");
+
+    s.mint(&s.farmer, 1, 1000, b"mrv-0001");
+    let compliance = CreditComplianceClient::new(env, &token.modules().compliance);
+    compliance.freeze_amount(&s.farmer, &1000, &String::from_str(env, "court order 42"));
+
+    assert_eq!(s.reversals.reverse_issuance(&hash, &vec![env]), 1000);
+    assert_eq!(token.balance(&s.farmer), 0);
+}
+
+// ── Buffer pool ───────────────────────────────────────────────────────────────
+
+#[test]
+fn test_reverse_issuance_spares_holders_other_credits() {
+    let s = setup_env();
+    let (env, token) = (&s.env, &s.token);
+    let buffer = Address::generate(env);
+    let bad = Bytes::from_slice(env, b"mrv-bad");
+
+    s.mint(&s.farmer, 1, 1000, b"mrv-good");
+    s.issuance.set_buffer_config(&buffer, &2000);
+    s.mint(&s.farmer, 1, 1000, b"mrv-bad");
+    assert_eq!(token.balance(&s.farmer), 1800);
+    assert_eq!(token.balance(&buffer), 200);
+
+    // 800 from the farmer, the withheld 200 from the buffer pool.
+    assert_eq!(s.reversals.reverse_issuance(&bad, &vec![env, buffer.clone()]), 1000);
+    assert_eq!(token.balance(&s.farmer), 1000);
+    assert_eq!(token.balance(&buffer), 0);
+    assert_eq!(token.total_supply(), 1000);
+
+    let record = s.issuance.get_issuance(&bad).unwrap();
+    assert_eq!(record.reversed, 1000);
+    assert_eq!(record.buffer_reversed, 200);
+    assert_eq!(token.get_batch(&1).unwrap().reversed, 1000);
+}
+
+#[test]
+fn test_cancel_buffer_credits_covers_reversal_shortfall() {
+    let s = setup_env();
+    let (env, token) = (&s.env, &s.token);
+    let neighbour = Address::generate(env);
+    let buffer = Address::generate(env);
+    let hash = Bytes::from_slice(env, b"mrv-0001");
+
+    let result = s.reversals.try_cancel_buffer_credits(&hash, &1);
+    assert_eq!(result, Err(Ok(Error::BufferNotConfigured)));
+
+    s.issuance.set_buffer_config(&buffer, &2000);
+    s.mint(&neighbour, 1, 2000, b"mrv-0000");
+    s.mint(&s.farmer, 1, 1000, b"mrv-0001");
+    token.retire_credits(&None, &s.farmer, &None, &300);
+
+    // 500 recovered from the farmer and 200 from the buffer; 300 retired.
+    assert_eq!(s.reversals.reverse_issuance(&hash, &vec![env]), 700);
+    assert_eq!(token.balance(&buffer), 400);
+
+    let result = s.reversals.try_cancel_buffer_credits(&hash, &301);
+    assert_eq!(result, Err(Ok(Error::ExceedsUnreversedAmount)));
+
+    // The pool covers the retired shortfall out of other issuances' share.
+    s.reversals.cancel_buffer_credits(&hash, &300);
+    assert_eq!(token.balance(&buffer), 100);
+    assert_eq!(token.total_supply(), 1700);
+    assert_eq!(s.issuance.get_issuance(&hash).unwrap().reversed, 1000);
+}
+
+#[test]
+fn test_cancel_buffer_credits_uses_issuance_batch() {
+    let s = setup_env();
+    let (env, token) = (&s.env, &s.token);
+    let buffer = Address::generate(env);
+    let batch_id = create_agroforestry_batch(env, token);
+    let hash = Bytes::from_slice(env, b"mrv-0002");
+
+    s.issuance.set_buffer_config(&buffer, &2000);
+    s.mint(&s.farmer, 1, 1000, b"mrv-0001");
+    s.mint(&s.farmer, batch_id, 1000, b"mrv-0002");
+
+    // Like a reversal, the cancellation skips the compliance hook.
+    let hook_id = env.register_contract(None, MockComplianceHook);
+    let compliance = CreditComplianceClient::new(env, &token.modules().compliance);
+    compliance.set_compliance_hook(&Some(hook_id));
+
+    s.reversals.cancel_buffer_credits(&hash, &150);
+    assert_eq!(token.batch_balance(&buffer, &1), 200);
+    assert_eq!(token.batch_balance(&buffer, &batch_id), 50);
+    assert_eq!(token.get_batch(&1).unwrap().reversed, 0);
+    assert_eq!(token.get_batch(&batch_id).unwrap().reversed, 150);
+    assert_eq!(token.total_supply(), 1850);
+}
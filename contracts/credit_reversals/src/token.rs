@@ -0,0 +1,89 @@
+use soroban_sdk::{contractclient, contracttype, Address, Bytes, Env};
+
+use crate::error::Error;
+use crate::storage::read_token;
+
+/// Mirror of the token's `Modules`; the encoding must match.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct Modules {
+    pub issuance: Address,
+    pub retirement: Address,
+    pub compliance: Address,
+    pub permits: Address,
+    pub reversals: Address,
+}
+
+/// Mirror of the issuance module's `IssuanceRecord`; the encoding must match.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct IssuanceRecord {
+    pub report_hash: Bytes,
+    pub verifier: Address,
+    pub recipient: Address,
+    pub batch_id: u32,
+    pub amount: i128,
+    pub buffered: i128,
+    pub ledger: u32,
+    pub timestamp: u64,
+    pub reversed: i128,
+    pub buffer_reversed: i128,
+}
+
+/// Mirror of the issuance module's `BufferConfig`; the encoding must match.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct BufferConfig {
+    pub account: Address,
+    pub bps: u32,
+}
+
+/// The parts of the carbon credit token this module calls.
+#[contractclient(name = "TokenClient")]
+#[allow(dead_code)]
+pub trait TokenInterface {
+    fn super_admin(env: Env) -> Address;
+    fn rbac_contract(env: Env) -> Address;
+    fn governance_mode(env: Env) -> bool;
+    fn modules(env: Env) -> Modules;
+    fn batch_balance(env: Env, id: Address, batch_id: u32) -> i128;
+
+    /// Takes reversed credits back from a holder. Accepts this module only.
+    fn claw_back(env: Env, holder: Address, batch_id: u32, amount: i128) -> Result<(), Error>;
+}
+
+/// The parts of the issuance module this module calls.
+#[contractclient(name = "IssuanceClient")]
+#[allow(dead_code)]
+pub trait IssuanceInterface {
+    fn get_issuance(env: Env, report_hash: Bytes) -> Option<IssuanceRecord>;
+    fn buffer_config(env: Env) -> Option<BufferConfig>;
+
+    /// Books clawed-back credits against an issuance. Accepts this module
+    /// only.
+    fn record_reversal(
+        env: Env,
+        report_hash: Bytes,
+        amount: i128,
+        from_buffer: i128,
+    ) -> Result<(), Error>;
+}
+
+pub fn token_client(e: &Env) -> TokenClient<'_> {
+    TokenClient::new(e, &read_token(e))
+}
+
+pub fn issuance_client(e: &Env) -> IssuanceClient<'_> {
+    IssuanceClient::new(e, &token_client(e).modules().issuance)
+}
+
+/// The address admin entrypoints require auth from: the token's SuperAdmin,
+/// or, in governance mode, its RBAC contract.
+pub fn read_admin_authority(e: &Env) -> Address {
+    let token = token_client(e);
+    if token.governance_mode() {
+        token.rbac_contract()
+    } else {
+        token.super_admin()
+    }
+}
@@ -0,0 +1,66 @@
+use soroban_sdk::contracterror;
+
+/// Codes match the token's, so an error reads the same whichever contract
+/// raised it.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    /// The provided amount is negative.
+    NegativeAmount = 1,
+    /// The account does not have enough balance.
+    InsufficientBalance = 2,
+    /// The contract is already initialized.
+    AlreadyInitialized = 4,
+    /// The address is blacklisted.
+    Blacklisted = 6,
+    /// Caller is not authorized.
+    Unauthorized = 7,
+    /// The report hash has already been used.
+    ReportHashUsed = 11,
+    /// The contract is paused.
+    ContractPaused = 12,
+    /// No batch exists with the given id.
+    BatchNotFound = 13,
+    /// The verifier registry has no report on file with the given hash.
+    ReportNotFound = 18,
+    /// The registry report was submitted by another verifier or for another
+    /// recipient.
+    ReportMismatch = 19,
+    /// The verifier is not active in the verifier registry.
+    VerifierInactive = 20,
+    /// No buffer pool account has been configured.
+    BufferNotConfigured = 24,
+    /// The mint would take total supply plus retired credits past the global cap.
+    GlobalCapExceeded = 27,
+    /// The mint would take the verifier's issuance past its cap.
+    VerifierCapExceeded = 28,
+    /// The mint would take the project's issuance, across all of its
+    /// vintages, past its cap.
+    ProjectCapExceeded = 29,
+    /// The mint would exceed the verifier's quota for the current window.
+    VerifierQuotaExceeded = 31,
+    /// Mints above the co-approval threshold must go through `propose_mint`.
+    CoApprovalRequired = 32,
+    /// No ex-ante tranche exists with the given id.
+    ExAnteTrancheNotFound = 36,
+    /// The amount exceeds the tranche's unconverted, uncancelled balance.
+    ExceedsExAnteOutstanding = 37,
+    /// In permissioned-holder mode, a counterparty holds no Trader, Verifier
+    /// or Admin role.
+    HolderNotPermitted = 43,
+    /// The registered compliance hook rejected the movement.
+    TransferNotCompliant = 44,
+    /// The amount must be greater than zero.
+    ZeroAmount = 47,
+}
+
+/// Passes on the error of a failed `try_` call to the token or one of its
+/// modules. Anything else, such as a trap in the callee, aborts the call.
+pub fn forward<T, C, I>(result: Result<Result<T, C>, Result<Error, I>>) -> Result<T, Error> {
+    match result {
+        Ok(Ok(value)) => Ok(value),
+        Err(Ok(error)) => Err(error),
+        _ => panic!("module call failed"),
+    }
+}
@@ -0,0 +1,71 @@
+use soroban_sdk::{contracttype, symbol_short, Address, Env};
+
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct ExAnteMintEvent {
+    pub tranche_id: u32,
+    pub farmer: Address,
+    pub batch_id: u32,
+    pub amount: i128,
+}
+
+impl ExAnteMintEvent {
+    pub fn publish(self, env: &Env) {
+        env.events().publish(
+            (symbol_short!("exa_mint"), self.farmer),
+            (self.tranche_id, self.batch_id, self.amount),
+        );
+    }
+}
+
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct ExAnteTransferEvent {
+    pub tranche_id: u32,
+    pub from: Address,
+    pub to: Address,
+    pub amount: i128,
+}
+
+impl ExAnteTransferEvent {
+    pub fn publish(self, env: &Env) {
+        env.events().publish(
+            (symbol_short!("exa_xfr"), self.from, self.to),
+            (self.tranche_id, self.amount),
+        );
+    }
+}
+
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct ExAnteConvertEvent {
+    pub tranche_id: u32,
+    pub holder: Address,
+    pub amount: i128,
+}
+
+impl ExAnteConvertEvent {
+    pub fn publish(self, env: &Env) {
+        env.events().publish(
+            (symbol_short!("exa_conv"), self.holder),
+            (self.tranche_id, self.amount),
+        );
+    }
+}
+
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct ExAnteCancelEvent {
+    pub tranche_id: u32,
+    pub holder: Address,
+    pub amount: i128,
+}
+
+impl ExAnteCancelEvent {
+    pub fn publish(self, env: &Env) {
+        env.events().publish(
+            (symbol_short!("exa_cncl"), self.holder),
+            (self.tranche_id, self.amount),
+        );
+    }
+}
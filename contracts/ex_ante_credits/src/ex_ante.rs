@@ -0,0 +1,184 @@
+use soroban_sdk::{contracttype, Address, Env, Vec};
+
+use crate::storage::{DataKey, PositionKey, BALANCE_BUMP_AMOUNT, BALANCE_LIFETIME_THRESHOLD};
+
+/// Forward-issued credits for a farmer's expected removals. They can be
+/// transferred but not retired until a verifier converts them to ex-post
+/// credits of `batch_id`; whatever is not converted is eventually cancelled.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct ExAnteTranche {
+    pub id: u32,
+    pub verifier: Address,
+    pub farmer: Address,
+    pub batch_id: u32,
+    pub amount: i128,
+    pub converted: i128,
+    pub cancelled: i128,
+}
+
+impl ExAnteTranche {
+    /// Ex-ante credits still awaiting conversion or cancellation.
+    pub fn outstanding(&self) -> i128 {
+        self.amount - self.converted - self.cancelled
+    }
+}
+
+/// A holder's share of a tranche: what is still locked, and what has been
+/// converted to ex-post credits or cancelled so far.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct ExAntePosition {
+    pub tranche_id: u32,
+    pub holder: Address,
+    pub balance: i128,
+    pub converted: i128,
+    pub cancelled: i128,
+}
+
+fn extend(e: &Env, key: &DataKey) {
+    e.storage()
+        .persistent()
+        .extend_ttl(key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+pub fn create_tranche(
+    e: &Env,
+    verifier: &Address,
+    farmer: &Address,
+    batch_id: u32,
+    amount: i128,
+) -> ExAnteTranche {
+    let id = e
+        .storage()
+        .instance()
+        .get::<DataKey, u32>(&DataKey::NextTrancheId)
+        .unwrap_or(0)
+        + 1;
+    e.storage()
+        .instance()
+        .set(&DataKey::NextTrancheId, &id);
+
+    let tranche = ExAnteTranche {
+        id,
+        verifier: verifier.clone(),
+        farmer: farmer.clone(),
+        batch_id,
+        amount,
+        converted: 0,
+        cancelled: 0,
+    };
+    write_tranche(e, &tranche);
+    tranche
+}
+
+pub fn read_tranche(e: &Env, id: u32) -> Option<ExAnteTranche> {
+    e.storage().persistent().get(&DataKey::Tranche(id))
+}
+
+pub fn write_tranche(e: &Env, tranche: &ExAnteTranche) {
+    let key = DataKey::Tranche(tranche.id);
+    e.storage().persistent().set(&key, tranche);
+    extend(e, &key);
+}
+
+/// Holders that have ever held part of the tranche, in order of arrival.
+pub fn read_tranche_holders(e: &Env, tranche_id: u32) -> Vec<Address> {
+    e.storage()
+        .persistent()
+        .get(&DataKey::TrancheHolders(tranche_id))
+        .unwrap_or_else(|| Vec::new(e))
+}
+
+pub fn read_holder_tranches(e: &Env, holder: &Address) -> Vec<u32> {
+    e.storage()
+        .persistent()
+        .get(&DataKey::HolderTranches(holder.clone()))
+        .unwrap_or_else(|| Vec::new(e))
+}
+
+pub fn read_position(e: &Env, tranche_id: u32, holder: &Address) -> ExAntePosition {
+    e.storage()
+        .persistent()
+        .get(&DataKey::Position(PositionKey {
+            tranche_id,
+            holder: holder.clone(),
+        }))
+        .unwrap_or(ExAntePosition {
+            tranche_id,
+            holder: holder.clone(),
+            balance: 0,
+            converted: 0,
+            cancelled: 0,
+        })
+}
+
+/// Stores a position, registering the holder with the tranche (and the
+/// tranche with the holder) the first time they meet.
+pub fn write_position(e: &Env, position: &ExAntePosition) {
+    let key = DataKey::Position(PositionKey {
+        tranche_id: position.tranche_id,
+        holder: position.holder.clone(),
+    });
+    if !e.storage().persistent().has(&key) {
+        let holders_key = DataKey::TrancheHolders(position.tranche_id);
+        let mut holders = read_tranche_holders(e, position.tranche_id);
+        holders.push_back(position.holder.clone());
+        e.storage().persistent().set(&holders_key, &holders);
+        extend(e, &holders_key);
+
+        let tranches_key = DataKey::HolderTranches(position.holder.clone());
+        let mut tranches = read_holder_tranches(e, &position.holder);
+        tranches.push_back(position.tranche_id);
+        e.storage().persistent().set(&tranches_key, &tranches);
+        extend(e, &tranches_key);
+    }
+    e.storage().persistent().set(&key, position);
+    extend(e, &key);
+}
+
+pub fn read_holder_positions(e: &Env, holder: &Address) -> Vec<ExAntePosition> {
+    let mut positions = Vec::new(e);
+    for tranche_id in read_holder_tranches(e, holder).iter() {
+        positions.push_back(read_position(e, tranche_id, holder));
+    }
+    positions
+}
+
+/// Splits `amount` of the tranche across its current holders in proportion
+/// to their locked balances. Shares are rounded down and the remainder goes
+/// to the earliest holders with room left, so the shares always sum to
+/// `amount` and never exceed a holder's balance. `amount` must not exceed
+/// the tranche's outstanding balance.
+pub fn allocate_pro_rata(e: &Env, tranche: &ExAnteTranche, amount: i128) -> Vec<(Address, i128)> {
+    let outstanding = tranche.outstanding();
+    let mut shares: Vec<(Address, i128)> = Vec::new(e);
+    if outstanding == 0 {
+        return shares;
+    }
+
+    let mut balances: Vec<i128> = Vec::new(e);
+    let mut allocated = 0;
+    for holder in read_tranche_holders(e, tranche.id).iter() {
+        let balance = read_position(e, tranche.id, &holder).balance;
+        if balance == 0 {
+            continue;
+        }
+        let share = balance * amount / outstanding;
+        allocated += share;
+        shares.push_back((holder, share));
+        balances.push_back(balance);
+    }
+
+    let mut remainder = amount - allocated;
+    for i in 0..shares.len() {
+        if remainder == 0 {
+            break;
+        }
+        let (holder, share) = shares.get_unchecked(i);
+        let extra = (balances.get_unchecked(i) - share).min(remainder);
+        shares.set(i, (holder, share + extra));
+        remainder -= extra;
+    }
+    shares
+}
@@ -0,0 +1,274 @@
+#![no_std]
+
+mod error;
+mod events;
+mod ex_ante;
+mod rbac;
+mod storage;
+mod token;
+#[cfg(test)]
+mod test;
+
+pub use error::Error;
+pub use ex_ante::{ExAntePosition, ExAnteTranche};
+use soroban_sdk::{contract, contractimpl, Address, Bytes, BytesN, Env, Vec};
+
+use crate::error::forward;
+use crate::events::{ExAnteCancelEvent, ExAnteConvertEvent, ExAnteMintEvent, ExAnteTransferEvent};
+use crate::ex_ante::{
+    allocate_pro_rata, create_tranche, read_holder_positions, read_position, read_tranche,
+    read_tranche_holders, write_position, write_tranche,
+};
+use crate::rbac::{has_role, require_verifier, ADMIN_ROLE, VERIFIER_ROLE};
+use crate::storage::{is_initialized, write_token, INSTANCE_BUMP_AMOUNT, INSTANCE_LIFETIME_THRESHOLD};
+use crate::token::{
+    compliance_client, issuance_client, read_admin_authority, require_not_blacklisted,
+    require_not_paused, token_client,
+};
+
+fn check_nonnegative_amount(amount: i128) -> Result<(), Error> {
+    if amount < 0 {
+        Err(Error::NegativeAmount)
+    } else {
+        Ok(())
+    }
+}
+
+/// Ex-ante module of the carbon credit token: forward-issued (locked)
+/// credits for a farmer's expected removals, converted into token credits
+/// through the issuance module once the removals are verified.
+#[contract]
+pub struct ExAnteCredits;
+
+#[contractimpl]
+impl ExAnteCredits {
+    /// Binds the module to `token`. Must be signed by the token's SuperAdmin
+    /// (or RBAC contract in governance mode). Can only be called once.
+    pub fn initialize(env: Env, token: Address) -> Result<(), Error> {
+        if is_initialized(&env) {
+            return Err(Error::AlreadyInitialized);
+        }
+
+        write_token(&env, &token);
+        read_admin_authority(&env).require_auth();
+        Ok(())
+    }
+
+    /// Replaces the module code with the uploaded wasm `new_wasm_hash`.
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+        read_admin_authority(&env).require_auth();
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+    }
+
+    /// Issues `amount` ex-ante credits to `farmer` against future removals in
+    /// `batch_id`, and returns the new tranche id. Ex-ante credits can be
+    /// transferred with `transfer_ex_ante` but not retired.
+    pub fn mint_ex_ante(
+        env: Env,
+        verifier: Address,
+        farmer: Address,
+        batch_id: u32,
+        amount: i128,
+    ) -> Result<u32, Error> {
+        check_nonnegative_amount(amount)?;
+        if amount == 0 {
+            return Err(Error::ZeroAmount);
+        }
+        require_not_paused(&env)?;
+        require_not_blacklisted(&env, &verifier)?;
+        require_not_blacklisted(&env, &farmer)?;
+
+        require_verifier(&env, &verifier)?;
+
+        if token_client(&env).get_batch(&batch_id).is_none() {
+            return Err(Error::BatchNotFound);
+        }
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        let tranche = create_tranche(&env, &verifier, &farmer, batch_id, amount);
+        let mut position = read_position(&env, tranche.id, &farmer);
+        position.balance = amount;
+        write_position(&env, &position);
+
+        ExAnteMintEvent {
+            tranche_id: tranche.id,
+            farmer,
+            batch_id,
+            amount,
+        }
+        .publish(&env);
+        Ok(tranche.id)
+    }
+
+    /// Transfers locked credits of a tranche. The transfer is checked by the
+    /// token's compliance module like a transfer of credits, except that
+    /// freezes only apply to credits already converted.
+    pub fn transfer_ex_ante(
+        env: Env,
+        from: Address,
+        to: Address,
+        tranche_id: u32,
+        amount: i128,
+    ) -> Result<(), Error> {
+        from.require_auth();
+        check_nonnegative_amount(amount)?;
+        require_not_paused(&env)?;
+
+        if read_tranche(&env, tranche_id).is_none() {
+            return Err(Error::ExAnteTrancheNotFound);
+        }
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        forward(compliance_client(&env).try_check_transfer(&None, &from, &to, &amount))?;
+        let mut sender = read_position(&env, tranche_id, &from);
+        if sender.balance < amount {
+            return Err(Error::InsufficientBalance);
+        }
+        sender.balance -= amount;
+        write_position(&env, &sender);
+
+        let mut receiver = read_position(&env, tranche_id, &to);
+        receiver.balance += amount;
+        write_position(&env, &receiver);
+
+        ExAnteTransferEvent {
+            tranche_id,
+            from,
+            to,
+            amount,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Converts `amount` of a tranche into ex-post credits once the removals
+    /// are verified. The conversion is an ordinary issuance against
+    /// `report_hash` for the tranche's farmer, and is shared out among the
+    /// current holders in proportion to their locked balances. Only the
+    /// verifier that issued the tranche may convert it.
+    pub fn convert_ex_ante(
+        env: Env,
+        verifier: Address,
+        tranche_id: u32,
+        amount: i128,
+        report_hash: Bytes,
+    ) -> Result<(), Error> {
+        check_nonnegative_amount(amount)?;
+        if amount == 0 {
+            return Err(Error::ZeroAmount);
+        }
+        require_not_paused(&env)?;
+        require_not_blacklisted(&env, &verifier)?;
+
+        require_verifier(&env, &verifier)?;
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        let mut tranche = read_tranche(&env, tranche_id).ok_or(Error::ExAnteTrancheNotFound)?;
+        if verifier != tranche.verifier {
+            return Err(Error::Unauthorized);
+        }
+        if amount > tranche.outstanding() {
+            return Err(Error::ExceedsExAnteOutstanding);
+        }
+
+        let shares = allocate_pro_rata(&env, &tranche, amount);
+        forward(issuance_client(&env).try_issue_ex_ante(
+            &verifier,
+            &tranche.farmer,
+            &tranche.batch_id,
+            &shares,
+            &report_hash,
+        ))?;
+
+        for (holder, share) in shares.iter() {
+            if share == 0 {
+                continue;
+            }
+            let mut position = read_position(&env, tranche_id, &holder);
+            position.balance -= share;
+            position.converted += share;
+            write_position(&env, &position);
+
+            ExAnteConvertEvent {
+                tranche_id,
+                holder,
+                amount: share,
+            }
+            .publish(&env);
+        }
+
+        tranche.converted += amount;
+        write_tranche(&env, &tranche);
+        Ok(())
+    }
+
+    /// Cancels whatever is left of a tranche after conversion, e.g. when the
+    /// verified removals fell short of the forward issuance. Returns the
+    /// amount cancelled. The verifier that issued the tranche may cancel it,
+    /// as may an RBAC Admin, e.g. once that verifier's role is revoked.
+    pub fn cancel_ex_ante(env: Env, caller: Address, tranche_id: u32) -> Result<i128, Error> {
+        caller.require_auth();
+        require_not_paused(&env)?;
+        require_not_blacklisted(&env, &caller)?;
+
+        let mut tranche = read_tranche(&env, tranche_id).ok_or(Error::ExAnteTrancheNotFound)?;
+        let role = if caller == tranche.verifier {
+            VERIFIER_ROLE
+        } else {
+            ADMIN_ROLE
+        };
+        if !has_role(&env, &caller, role) {
+            return Err(Error::Unauthorized);
+        }
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        let cancelled = tranche.outstanding();
+        for holder in read_tranche_holders(&env, tranche_id).iter() {
+            let mut position = read_position(&env, tranche_id, &holder);
+            if position.balance == 0 {
+                continue;
+            }
+            let amount = position.balance;
+            position.cancelled += amount;
+            position.balance = 0;
+            write_position(&env, &position);
+
+            ExAnteCancelEvent {
+                tranche_id,
+                holder,
+                amount,
+            }
+            .publish(&env);
+        }
+
+        tranche.cancelled += cancelled;
+        write_tranche(&env, &tranche);
+        Ok(cancelled)
+    }
+
+    pub fn get_ex_ante_tranche(env: Env, tranche_id: u32) -> Option<ExAnteTranche> {
+        read_tranche(&env, tranche_id)
+    }
+
+    /// Returns `holder`'s locked, converted and cancelled amounts in a tranche.
+    pub fn ex_ante_position(env: Env, tranche_id: u32, holder: Address) -> ExAntePosition {
+        read_position(&env, tranche_id, &holder)
+    }
+
+    /// Returns every tranche position `holder` has had, oldest first.
+    pub fn ex_ante_positions_of(env: Env, holder: Address) -> Vec<ExAntePosition> {
+        read_holder_positions(&env, &holder)
+    }
+}
@@ -0,0 +1,42 @@
+use soroban_sdk::{contractclient, Address, Env, String};
+
+use crate::error::Error;
+use crate::token::token_client;
+
+/// The role string that grants minting authority.
+/// Must exactly match the role name registered in the RBAC contract.
+pub const VERIFIER_ROLE: &str = "Verifier";
+
+/// The RBAC role that may cancel any tranche, e.g. once its verifier's role
+/// is revoked.
+pub const ADMIN_ROLE: &str = "Admin";
+
+/// Cross-contract client interface for the token's RBAC contract.
+#[contractclient(name = "RbacContractClient")]
+#[allow(dead_code)]
+pub trait RbacContractInterface {
+    /// Returns `true` when `address` holds `role` in the RBAC registry.
+    fn has_role(env: Env, address: Address, role: String) -> bool;
+}
+
+/// Returns `true` when `address` holds `role` in the token's RBAC contract.
+pub fn has_role(e: &Env, address: &Address, role: &str) -> bool {
+    let client = RbacContractClient::new(e, &token_client(e).rbac_contract());
+    client.has_role(address, &String::from_str(e, role))
+}
+
+/// Asserts that `caller` both:
+///   1. Signed the current transaction (`require_auth`), and
+///   2. Holds the `Verifier` role in the token's RBAC contract.
+///
+/// Returns `Error::Unauthorized` if `caller` lacks the role, so the calling
+/// function can bail out before any state mutation.
+pub fn require_verifier(e: &Env, caller: &Address) -> Result<(), Error> {
+    // Ensure the address actually authorised this invocation.
+    caller.require_auth();
+
+    if !has_role(e, caller, VERIFIER_ROLE) {
+        return Err(Error::Unauthorized);
+    }
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use soroban_sdk::{contracttype, Address, Env};
+
+// ── TTL Constants ──────────────────────────────────────────────────────────────
+pub const INSTANCE_LIFETIME_THRESHOLD: u32 = 17280; // ~1 day
+pub const INSTANCE_BUMP_AMOUNT: u32 = 518400; // ~30 days
+
+pub const BALANCE_LIFETIME_THRESHOLD: u32 = 17280; // ~1 day
+pub const BALANCE_BUMP_AMOUNT: u32 = 518400; // ~30 days
+
+// ── Storage Keys ───────────────────────────────────────────────────────────────
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    // The token ex-ante credits convert into
+    Token,
+
+    // Tranches and the holders' positions in them
+    NextTrancheId,
+    Tranche(u32),
+    Position(PositionKey),
+    TrancheHolders(u32),
+    HolderTranches(Address),
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct PositionKey {
+    pub tranche_id: u32,
+    pub holder: Address,
+}
+
+// ── Token ──────────────────────────────────────────────────────────────────────
+pub fn is_initialized(e: &Env) -> bool {
+    e.storage().instance().has(&DataKey::Token)
+}
+
+pub fn read_token(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::Token)
+        .expect("token not set: was initialize() called?")
+}
+
+pub fn write_token(e: &Env, token: &Address) {
+    e.storage().instance().set(&DataKey::Token, token);
+}
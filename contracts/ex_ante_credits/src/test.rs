@@ -0,0 +1,301 @@
+#![cfg(test)]
+
+use crate::{Error, ExAnteCredits, ExAnteCreditsClient};
+use carbon_credit_token::{CarbonCreditToken, CarbonCreditTokenClient, Error as TokenError, Modules};
+use credit_compliance::{CreditCompliance, CreditComplianceClient};
+use credit_issuance::{CreditIssuance, CreditIssuanceClient};
+use soroban_sdk::{contract, contractimpl, testutils::Address as _, Address, Bytes, Env, String};
+
+#[contract]
+pub struct MockRbacContract;
+
+#[contractimpl]
+impl MockRbacContract {
+    pub fn has_role(env: Env, address: Address, role: String) -> bool {
+        // Simplest mock: everyone has every role unless denied
+        !env.storage().instance().has(&address)
+            && !env.storage().instance().has(&(address, role))
+    }
+
+    pub fn get_role(env: Env, address: Address) -> u32 {
+        // Everyone is a Trader unless denied
+        if env.storage().instance().has(&address) {
+            255
+        } else {
+            2
+        }
+    }
+
+    pub fn deny(env: Env, address: Address) {
+        env.storage().instance().set(&address, &true);
+    }
+
+    pub fn deny_role(env: Env, address: Address, role: String) {
+        env.storage().instance().set(&(address, role), &true);
+    }
+}
+
+#[contract]
+pub struct MockComplianceHook;
+
+#[contractimpl]
+impl MockComplianceHook {
+    pub fn can_transfer(_env: Env, _from: Address, _to: Address, amount: i128) -> bool {
+        amount <= 100
+    }
+}
+
+#[contract]
+pub struct MockVerifierRegistry;
+
+#[contractimpl]
+impl MockVerifierRegistry {
+    pub fn submit_report_hash(env: Env, verifier: Address, farmer: Address, metric_hash: String) {
+        let report = (verifier, farmer, env.ledger().sequence());
+        env.storage().instance().set(&metric_hash, &report);
+    }
+
+    pub fn is_verifier_active(_env: Env, _verifier: Address) -> bool {
+        true
+    }
+
+    pub fn get_report(env: Env, metric_hash: String) -> Option<(Address, Address, u32)> {
+        env.storage().instance().get(&metric_hash)
+    }
+}
+
+/// Files a report with `hash` from `verifier` for `farmer` in the issuance
+/// module's registry, so a conversion with that hash passes the registry
+/// check.
+fn submit_report(
+    env: &Env,
+    token: &CarbonCreditTokenClient,
+    verifier: &Address,
+    farmer: &Address,
+    hash: &[u8],
+) {
+    let issuance = CreditIssuanceClient::new(env, &token.modules().issuance);
+    let registry = MockVerifierRegistryClient::new(env, &issuance.verifier_registry());
+    registry.submit_report_hash(verifier, farmer, &String::from_bytes(env, hash));
+}
+
+/// Registers a token with real issuance and compliance modules and this
+/// module as the issuance module's ex-ante contract, and returns
+/// `(env, token, ex_ante, verifier, farmer)`. The other modules are bare
+/// addresses.
+fn setup_env<'a>() -> (
+    Env,
+    CarbonCreditTokenClient<'a>,
+    ExAnteCreditsClient<'a>,
+    Address,
+    Address,
+) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let rbac_id = env.register_contract(None, MockRbacContract);
+    let registry_id = env.register_contract(None, MockVerifierRegistry);
+    let compliance_id = env.register_contract(None, CreditCompliance);
+    let issuance_id = env.register_contract(None, CreditIssuance);
+    let ex_ante_id = env.register_contract(None, ExAnteCredits);
+    let token_id = env.register_contract(None, CarbonCreditToken);
+    let token = CarbonCreditTokenClient::new(&env, &token_id);
+
+    token.initialize(
+        &admin,
+        &rbac_id,
+        &Modules {
+            issuance: issuance_id.clone(),
+            retirement: Address::generate(&env),
+            compliance: compliance_id.clone(),
+            permits: Address::generate(&env),
+            reversals: Address::generate(&env),
+        },
+        &String::from_str(&env, "Carbon Credit Token"),
+        &String::from_str(&env, "CCT"),
+        &0u32,
+        &String::from_str(&env, "Amazon Reforestation"),
+        &String::from_str(&env, "2023"),
+        &String::from_str(&env, "Brazil"),
+        &String::from_str(&env, "https://farmcredit.xyz/amazon-1"),
+    );
+    CreditComplianceClient::new(&env, &compliance_id).initialize(&token_id);
+    let mint_approvals = Address::generate(&env);
+    CreditIssuanceClient::new(&env, &issuance_id).initialize(
+        &token_id,
+        &registry_id,
+        &ex_ante_id,
+        &mint_approvals,
+    );
+    let ex_ante = ExAnteCreditsClient::new(&env, &ex_ante_id);
+    ex_ante.initialize(&token_id);
+
+    let verifier = Address::generate(&env);
+    let farmer = Address::generate(&env);
+
+    (env, token, ex_ante, verifier, farmer)
+}
+
+fn compliance_of<'a>(token: &CarbonCreditTokenClient) -> CreditComplianceClient<'a> {
+    CreditComplianceClient::new(&token.env, &token.modules().compliance)
+}
+
+#[test]
+fn test_initialize_binds_token_once() {
+    let (_, token, ex_ante, _, _) = setup_env();
+
+    let result = ex_ante.try_initialize(&token.address);
+    assert_eq!(result, Err(Ok(Error::AlreadyInitialized)));
+}
+
+#[test]
+fn test_ex_ante_credits_transfer_but_do_not_count_as_balance() {
+    let (env, token, ex_ante, verifier, farmer) = setup_env();
+    let buyer = Address::generate(&env);
+
+    let tranche_id = ex_ante.mint_ex_ante(&verifier, &farmer, &1, &1000);
+    ex_ante.transfer_ex_ante(&farmer, &buyer, &tranche_id, &400);
+
+    assert_eq!(ex_ante.ex_ante_position(&tranche_id, &farmer).balance, 600);
+    assert_eq!(ex_ante.ex_ante_position(&tranche_id, &buyer).balance, 400);
+    assert_eq!(ex_ante.ex_ante_positions_of(&buyer).len(), 1);
+    assert_eq!(ex_ante.get_ex_ante_tranche(&tranche_id).unwrap().amount, 1000);
+
+    // Locked credits are not part of the ex-post supply and cannot be retired.
+    assert_eq!(token.balance(&buyer), 0);
+    assert_eq!(token.total_supply(), 0);
+    let result = token.try_retire_credits(&None, &buyer, &None, &400);
+    assert_eq!(result, Err(Ok(TokenError::InsufficientBalance)));
+
+    let result = ex_ante.try_transfer_ex_ante(&buyer, &farmer, &tranche_id, &401);
+    assert_eq!(result, Err(Ok(Error::InsufficientBalance)));
+
+    let result = ex_ante.try_mint_ex_ante(&verifier, &farmer, &99, &1000);
+    assert_eq!(result, Err(Ok(Error::BatchNotFound)));
+}
+
+#[test]
+fn test_convert_then_cancel_ex_ante_shortfall() {
+    let (env, token, ex_ante, verifier, farmer) = setup_env();
+    let buyer = Address::generate(&env);
+
+    let tranche_id = ex_ante.mint_ex_ante(&verifier, &farmer, &1, &1000);
+    ex_ante.transfer_ex_ante(&farmer, &buyer, &tranche_id, &400);
+
+    submit_report(&env, &token, &verifier, &farmer, b"mrv-0001");
+    ex_ante.convert_ex_ante(&verifier, &tranche_id, &500, &Bytes::from_slice(&env, b"mrv-0001"));
+    assert_eq!(token.balance(&farmer), 300);
+    assert_eq!(token.balance(&buyer), 200);
+    assert_eq!(token.total_supply(), 500);
+    let position = ex_ante.ex_ante_position(&tranche_id, &buyer);
+    assert_eq!((position.balance, position.converted), (200, 200));
+
+    submit_report(&env, &token, &verifier, &farmer, b"mrv-2");
+    let hash = Bytes::from_slice(&env, b"mrv-2");
+    let result = ex_ante.try_convert_ex_ante(&verifier, &tranche_id, &501, &hash);
+    assert_eq!(result, Err(Ok(Error::ExceedsExAnteOutstanding)));
+
+    // Only 500 of the forward issuance was verified: cancel the rest.
+    assert_eq!(ex_ante.cancel_ex_ante(&verifier, &tranche_id), 500);
+    let position = ex_ante.ex_ante_position(&tranche_id, &farmer);
+    assert_eq!((position.balance, position.converted, position.cancelled), (0, 300, 300));
+    let tranche = ex_ante.get_ex_ante_tranche(&tranche_id).unwrap();
+    assert_eq!((tranche.converted, tranche.cancelled), (500, 500));
+    assert_eq!(token.total_supply(), 500);
+}
+
+#[test]
+fn test_ex_ante_rejects_zero_amounts_and_other_verifiers() {
+    let (env, token, ex_ante, verifier, farmer) = setup_env();
+    let other_verifier = Address::generate(&env);
+
+    let result = ex_ante.try_mint_ex_ante(&verifier, &farmer, &1, &0);
+    assert_eq!(result, Err(Ok(Error::ZeroAmount)));
+
+    let tranche_id = ex_ante.mint_ex_ante(&verifier, &farmer, &1, &1000);
+    let hash = Bytes::from_slice(&env, b"mrv-0001");
+    submit_report(&env, &token, &verifier, &farmer, b"mrv-0001");
+    let result = ex_ante.try_convert_ex_ante(&verifier, &tranche_id, &0, &hash);
+    assert_eq!(result, Err(Ok(Error::ZeroAmount)));
+    let result = ex_ante.try_convert_ex_ante(&other_verifier, &tranche_id, &500, &hash);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+    // The rejected calls did not use up the report hash.
+    ex_ante.convert_ex_ante(&verifier, &tranche_id, &500, &hash);
+    assert_eq!(token.balance(&farmer), 500);
+}
+
+#[test]
+fn test_ex_ante_conversion_remainder_goes_to_earliest_holders() {
+    let (env, token, ex_ante, verifier, farmer) = setup_env();
+    let buyer_a = Address::generate(&env);
+    let buyer_b = Address::generate(&env);
+
+    let tranche_id = ex_ante.mint_ex_ante(&verifier, &farmer, &1, &3);
+    ex_ante.transfer_ex_ante(&farmer, &buyer_a, &tranche_id, &1);
+    ex_ante.transfer_ex_ante(&farmer, &buyer_b, &tranche_id, &1);
+
+    submit_report(&env, &token, &verifier, &farmer, b"mrv-0001");
+    ex_ante.convert_ex_ante(&verifier, &tranche_id, &2, &Bytes::from_slice(&env, b"mrv-0001"));
+    assert_eq!(token.balance(&farmer), 1);
+    assert_eq!(token.balance(&buyer_a), 1);
+    assert_eq!(token.balance(&buyer_b), 0);
+    assert_eq!(ex_ante.ex_ante_position(&tranche_id, &buyer_b).balance, 1);
+}
+
+#[test]
+fn test_cancel_ex_ante_by_admin_once_verifier_revoked() {
+    let (env, token, ex_ante, verifier, farmer) = setup_env();
+    let admin = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    let rbac = MockRbacContractClient::new(&env, &token.rbac_contract());
+
+    let tranche_id = ex_ante.mint_ex_ante(&verifier, &farmer, &1, &1000);
+    rbac.deny_role(&verifier, &String::from_str(&env, "Verifier"));
+    rbac.deny_role(&outsider, &String::from_str(&env, "Admin"));
+
+    let result = ex_ante.try_cancel_ex_ante(&verifier, &tranche_id);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    let result = ex_ante.try_cancel_ex_ante(&outsider, &tranche_id);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+    assert_eq!(ex_ante.cancel_ex_ante(&admin, &tranche_id), 1000);
+    assert_eq!(ex_ante.ex_ante_position(&tranche_id, &farmer).cancelled, 1000);
+}
+
+// ── Compliance ────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_compliance_hook_gates_ex_ante_transfers() {
+    let (env, token, ex_ante, verifier, farmer) = setup_env();
+    let buyer = Address::generate(&env);
+    let hook_id = env.register_contract(None, MockComplianceHook);
+
+    let tranche_id = ex_ante.mint_ex_ante(&verifier, &farmer, &1, &1000);
+    compliance_of(&token).set_compliance_hook(&Some(hook_id));
+
+    let result = ex_ante.try_transfer_ex_ante(&farmer, &buyer, &tranche_id, &101);
+    assert_eq!(result, Err(Ok(Error::TransferNotCompliant)));
+    ex_ante.transfer_ex_ante(&farmer, &buyer, &tranche_id, &100);
+    assert_eq!(ex_ante.ex_ante_position(&tranche_id, &buyer).balance, 100);
+}
+
+#[test]
+fn test_ex_ante_transfers_checked_like_credits() {
+    let (env, token, ex_ante, verifier, farmer) = setup_env();
+    let outsider = Address::generate(&env);
+    let compliance = compliance_of(&token);
+    MockRbacContractClient::new(&env, &token.rbac_contract()).deny(&outsider);
+
+    let tranche_id = ex_ante.mint_ex_ante(&verifier, &farmer, &1, &1000);
+
+    compliance.set_permissioned_mode(&true);
+    let result = ex_ante.try_transfer_ex_ante(&farmer, &outsider, &tranche_id, &100);
+    assert_eq!(result, Err(Ok(Error::HolderNotPermitted)));
+    compliance.set_permissioned_mode(&false);
+
+    compliance.blacklist(&farmer);
+    let result = ex_ante.try_transfer_ex_ante(&farmer, &outsider, &tranche_id, &100);
+    assert_eq!(result, Err(Ok(Error::Blacklisted)));
+}
@@ -0,0 +1,47 @@
+use soroban_sdk::{contracttype, symbol_short, Address, Env};
+
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct MintProposedEvent {
+    pub request_id: u64,
+    pub proposer: Address,
+    pub to: Address,
+    pub amount: i128,
+}
+
+impl MintProposedEvent {
+    pub fn publish(self, env: &Env) {
+        env.events().publish(
+            (symbol_short!("mint_prop"), self.request_id),
+            (self.proposer, self.to, self.amount),
+        );
+    }
+}
+
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct MintApprovedEvent {
+    pub request_id: u64,
+    pub approver: Address,
+}
+
+impl MintApprovedEvent {
+    pub fn publish(self, env: &Env) {
+        env.events()
+            .publish((symbol_short!("mint_appr"), self.request_id), self.approver);
+    }
+}
+
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct MintCancelledEvent {
+    pub request_id: u64,
+    pub caller: Address,
+}
+
+impl MintCancelledEvent {
+    pub fn publish(self, env: &Env) {
+        env.events()
+            .publish((symbol_short!("mint_cncl"), self.request_id), self.caller);
+    }
+}
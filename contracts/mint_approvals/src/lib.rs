@@ -0,0 +1,195 @@
+#![no_std]
+
+mod error;
+mod events;
+mod mint_request;
+mod rbac;
+mod storage;
+mod token;
+#[cfg(test)]
+mod test;
+
+pub use error::Error;
+pub use mint_request::MintRequest;
+use soroban_sdk::{contract, contractimpl, Address, Bytes, BytesN, Env, Vec};
+
+use crate::error::forward;
+use crate::events::{MintApprovedEvent, MintCancelledEvent, MintProposedEvent};
+use crate::mint_request::{
+    increment_next_mint_request_id, read_mint_request, read_pending_mint_requests,
+    remove_mint_request, write_mint_request,
+};
+use crate::rbac::{has_role, require_verifier, ADMIN_ROLE, VERIFIER_ROLE};
+use crate::storage::{is_initialized, write_token, INSTANCE_BUMP_AMOUNT, INSTANCE_LIFETIME_THRESHOLD};
+use crate::token::{
+    issuance_client, read_admin_authority, require_not_blacklisted, require_not_paused,
+    token_client,
+};
+
+fn check_nonnegative_amount(amount: i128) -> Result<(), Error> {
+    if amount < 0 {
+        Err(Error::NegativeAmount)
+    } else {
+        Ok(())
+    }
+}
+
+/// Mint approvals module of the carbon credit token: four-eyes mints above
+/// the issuance module's co-approval threshold. A verifier proposes the
+/// mint, and a second verifier or an RBAC Admin approves it.
+#[contract]
+pub struct MintApprovals;
+
+#[contractimpl]
+impl MintApprovals {
+    /// Binds the module to `token`. Must be signed by the token's SuperAdmin
+    /// (or RBAC contract in governance mode). Can only be called once.
+    pub fn initialize(env: Env, token: Address) -> Result<(), Error> {
+        if is_initialized(&env) {
+            return Err(Error::AlreadyInitialized);
+        }
+
+        write_token(&env, &token);
+        read_admin_authority(&env).require_auth();
+        Ok(())
+    }
+
+    /// Replaces the module code with the uploaded wasm `new_wasm_hash`.
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+        read_admin_authority(&env).require_auth();
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+    }
+
+    /// Queues a mint for co-approval and returns its request id. The request
+    /// can be approved up to and including `expiration_ledger`.
+    pub fn propose_mint(
+        env: Env,
+        verifier: Address,
+        to: Address,
+        batch_id: u32,
+        amount: i128,
+        report_hash: Bytes,
+        expiration_ledger: u32,
+    ) -> Result<u64, Error> {
+        check_nonnegative_amount(amount)?;
+        require_not_paused(&env)?;
+        require_not_blacklisted(&env, &verifier)?;
+        require_not_blacklisted(&env, &to)?;
+
+        require_verifier(&env, &verifier)?;
+
+        if expiration_ledger < env.ledger().sequence() {
+            return Err(Error::InvalidExpirationLedger);
+        }
+        if issuance_client(&env).is_report_hash_used(&report_hash) {
+            return Err(Error::ReportHashUsed);
+        }
+        if token_client(&env).get_batch(&batch_id).is_none() {
+            return Err(Error::BatchNotFound);
+        }
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        let request_id = increment_next_mint_request_id(&env);
+        write_mint_request(
+            &env,
+            &MintRequest {
+                id: request_id,
+                proposer: verifier.clone(),
+                to: to.clone(),
+                batch_id,
+                amount,
+                report_hash,
+                expiration_ledger,
+            },
+        );
+
+        MintProposedEvent {
+            request_id,
+            proposer: verifier,
+            to,
+            amount,
+        }
+        .publish(&env);
+        Ok(request_id)
+    }
+
+    /// Co-approves a pending request and mints it. The approver must be a
+    /// verifier other than the proposer, or an Admin in the RBAC contract,
+    /// and the proposer must still hold the Verifier role.
+    /// The mint is checked against the proposer's registry report, caps and
+    /// quota as if the proposer had minted directly.
+    pub fn approve_mint(env: Env, approver: Address, request_id: u64) -> Result<(), Error> {
+        approver.require_auth();
+        require_not_paused(&env)?;
+        require_not_blacklisted(&env, &approver)?;
+
+        let request = read_mint_request(&env, request_id).ok_or(Error::MintRequestNotFound)?;
+        if request.expiration_ledger < env.ledger().sequence() {
+            return Err(Error::MintRequestExpired);
+        }
+        if approver == request.proposer {
+            return Err(Error::CoApproverNotDistinct);
+        }
+        if !has_role(&env, &approver, VERIFIER_ROLE) && !has_role(&env, &approver, ADMIN_ROLE) {
+            return Err(Error::Unauthorized);
+        }
+        if !has_role(&env, &request.proposer, VERIFIER_ROLE) {
+            return Err(Error::Unauthorized);
+        }
+        require_not_blacklisted(&env, &request.proposer)?;
+        require_not_blacklisted(&env, &request.to)?;
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        remove_mint_request(&env, request_id);
+        forward(issuance_client(&env).try_issue_approved(
+            &request.proposer,
+            &request.to,
+            &request.batch_id,
+            &request.amount,
+            &request.report_hash,
+        ))?;
+
+        MintApprovedEvent {
+            request_id,
+            approver,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Withdraws a pending request. Only its proposer or the SuperAdmin may
+    /// cancel; expired requests are removed the same way.
+    pub fn cancel_mint_request(env: Env, caller: Address, request_id: u64) -> Result<(), Error> {
+        caller.require_auth();
+
+        let request = read_mint_request(&env, request_id).ok_or(Error::MintRequestNotFound)?;
+        if caller != request.proposer && caller != read_admin_authority(&env) {
+            return Err(Error::Unauthorized);
+        }
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        remove_mint_request(&env, request_id);
+        MintCancelledEvent { request_id, caller }.publish(&env);
+        Ok(())
+    }
+
+    pub fn get_mint_request(env: Env, request_id: u64) -> Option<MintRequest> {
+        read_mint_request(&env, request_id)
+    }
+
+    /// Returns the ids of requests that were neither approved nor cancelled,
+    /// oldest first. Expired requests are dropped from the list the next time
+    /// a request is proposed, approved or cancelled.
+    pub fn pending_mint_requests(env: Env) -> Vec<u64> {
+        read_pending_mint_requests(&env)
+    }
+}
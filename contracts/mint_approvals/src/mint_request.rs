@@ -0,0 +1,87 @@
+use soroban_sdk::{contracttype, Address, Bytes, Env, Vec};
+
+use crate::storage::{DataKey, BALANCE_BUMP_AMOUNT, BALANCE_LIFETIME_THRESHOLD};
+
+/// A proposed issuance awaiting co-approval by a second verifier or an
+/// RBAC Admin.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct MintRequest {
+    pub id: u64,
+    pub proposer: Address,
+    pub to: Address,
+    pub batch_id: u32,
+    pub amount: i128,
+    pub report_hash: Bytes,
+    /// Last ledger at which the request can be approved.
+    pub expiration_ledger: u32,
+}
+
+pub fn increment_next_mint_request_id(e: &Env) -> u64 {
+    let id = e
+        .storage()
+        .instance()
+        .get::<DataKey, u64>(&DataKey::NextMintRequestId)
+        .unwrap_or(0)
+        + 1;
+    e.storage()
+        .instance()
+        .set(&DataKey::NextMintRequestId, &id);
+    id
+}
+
+pub fn read_mint_request(e: &Env, id: u64) -> Option<MintRequest> {
+    e.storage().persistent().get(&DataKey::MintRequest(id))
+}
+
+pub fn read_pending_mint_requests(e: &Env) -> Vec<u64> {
+    e.storage()
+        .persistent()
+        .get(&DataKey::PendingMintRequests)
+        .unwrap_or_else(|| Vec::new(e))
+}
+
+/// Stores the pending list, dropping requests that have expired.
+fn write_pending_mint_requests(e: &Env, ids: &Vec<u64>) {
+    let sequence = e.ledger().sequence();
+    let mut live = Vec::new(e);
+    for id in ids.iter() {
+        let expired =
+            read_mint_request(e, id).is_none_or(|request| request.expiration_ledger < sequence);
+        if !expired {
+            live.push_back(id);
+        }
+    }
+
+    let key = DataKey::PendingMintRequests;
+    if live.is_empty() {
+        e.storage().persistent().remove(&key);
+        return;
+    }
+    e.storage().persistent().set(&key, &live);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+pub fn write_mint_request(e: &Env, request: &MintRequest) {
+    let key = DataKey::MintRequest(request.id);
+    e.storage().persistent().set(&key, request);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+
+    let mut pending = read_pending_mint_requests(e);
+    pending.push_back(request.id);
+    write_pending_mint_requests(e, &pending);
+}
+
+/// Removes a request once it has been approved or cancelled.
+pub fn remove_mint_request(e: &Env, id: u64) {
+    e.storage().persistent().remove(&DataKey::MintRequest(id));
+    let mut pending = read_pending_mint_requests(e);
+    if let Some(pos) = pending.first_index_of(id) {
+        pending.remove(pos);
+        write_pending_mint_requests(e, &pending);
+    }
+}
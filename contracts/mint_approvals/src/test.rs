@@ -0,0 +1,250 @@
+#![cfg(test)]
+
+use crate::{Error, MintApprovals, MintApprovalsClient};
+use carbon_credit_token::{CarbonCreditToken, CarbonCreditTokenClient, Modules};
+use credit_compliance::{CreditCompliance, CreditComplianceClient};
+use credit_issuance::{CreditIssuance, CreditIssuanceClient};
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{Address as _, Ledger},
+    vec, Address, Bytes, Env, String,
+};
+
+#[contract]
+pub struct MockRbacContract;
+
+#[contractimpl]
+impl MockRbacContract {
+    pub fn has_role(env: Env, address: Address, role: String) -> bool {
+        // Simplest mock: everyone has every role unless denied
+        !env.storage().instance().has(&(address, role))
+    }
+
+    pub fn get_role(_env: Env, _address: Address) -> u32 {
+        // Everyone is a Trader
+        2
+    }
+
+    pub fn deny_role(env: Env, address: Address, role: String) {
+        env.storage().instance().set(&(address, role), &true);
+    }
+}
+
+#[contract]
+pub struct MockVerifierRegistry;
+
+#[contractimpl]
+impl MockVerifierRegistry {
+    pub fn submit_report_hash(env: Env, verifier: Address, farmer: Address, metric_hash: String) {
+        let report = (verifier, farmer, env.ledger().sequence());
+        env.storage().instance().set(&metric_hash, &report);
+    }
+
+    pub fn is_verifier_active(_env: Env, _verifier: Address) -> bool {
+        true
+    }
+
+    pub fn get_report(env: Env, metric_hash: String) -> Option<(Address, Address, u32)> {
+        env.storage().instance().get(&metric_hash)
+    }
+}
+
+/// Files a report with `hash` from `verifier` for `farmer` in the issuance
+/// module's registry, so an approved mint with that hash passes the registry
+/// check.
+fn submit_report(
+    issuance: &CreditIssuanceClient,
+    verifier: &Address,
+    farmer: &Address,
+    hash: &Bytes,
+) {
+    extern crate std;
+
+    let registry = MockVerifierRegistryClient::new(&issuance.env, &issuance.verifier_registry());
+    let hash: std::vec::Vec<u8> = hash.iter().collect();
+    registry.submit_report_hash(verifier, farmer, &String::from_bytes(&issuance.env, &hash));
+}
+
+/// Registers a token with real issuance and compliance modules and this
+/// module as the issuance module's mint approvals contract, and returns
+/// `(env, token, issuance, approvals, admin, verifier, farmer)`.
+fn setup_env<'a>() -> (
+    Env,
+    CarbonCreditTokenClient<'a>,
+    CreditIssuanceClient<'a>,
+    MintApprovalsClient<'a>,
+    Address,
+    Address,
+    Address,
+) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let rbac_id = env.register_contract(None, MockRbacContract);
+    let registry_id = env.register_contract(None, MockVerifierRegistry);
+    let compliance_id = env.register_contract(None, CreditCompliance);
+    let issuance_id = env.register_contract(None, CreditIssuance);
+    let approvals_id = env.register_contract(None, MintApprovals);
+    let token_id = env.register_contract(None, CarbonCreditToken);
+    let token = CarbonCreditTokenClient::new(&env, &token_id);
+
+    token.initialize(
+        &admin,
+        &rbac_id,
+        &Modules {
+            issuance: issuance_id.clone(),
+            retirement: Address::generate(&env),
+            compliance: compliance_id.clone(),
+            permits: Address::generate(&env),
+            reversals: Address::generate(&env),
+        },
+        &String::from_str(&env, "Carbon Credit Token"),
+        &String::from_str(&env, "CCT"),
+        &0u32,
+        &String::from_str(&env, "Amazon Reforestation"),
+        &String::from_str(&env, "2023"),
+        &String::from_str(&env, "Brazil"),
+        &String::from_str(&env, "https://farmcredit.xyz/amazon-1"),
+    );
+    CreditComplianceClient::new(&env, &compliance_id).initialize(&token_id);
+    let issuance = CreditIssuanceClient::new(&env, &issuance_id);
+    let ex_ante = Address::generate(&env);
+    issuance.initialize(&token_id, &registry_id, &ex_ante, &approvals_id);
+    let approvals = MintApprovalsClient::new(&env, &approvals_id);
+    approvals.initialize(&token_id);
+
+    let verifier = Address::generate(&env);
+    let farmer = Address::generate(&env);
+
+    (env, token, issuance, approvals, admin, verifier, farmer)
+}
+
+#[test]
+fn test_initialize_binds_token_once() {
+    let (_, token, _, approvals, _, _, _) = setup_env();
+
+    let result = approvals.try_initialize(&token.address);
+    assert_eq!(result, Err(Ok(Error::AlreadyInitialized)));
+}
+
+#[test]
+fn test_propose_and_approve_mint() {
+    let (env, token, issuance, approvals, _, verifier, farmer) = setup_env();
+    let co_verifier = Address::generate(&env);
+    let hash = Bytes::from_slice(&env, b"r1");
+
+    issuance.set_co_approval_threshold(&Some(1000));
+    submit_report(&issuance, &verifier, &farmer, &hash);
+    let id = approvals.propose_mint(&verifier, &farmer, &1, &5000, &hash, &1000);
+    assert_eq!(approvals.pending_mint_requests(), vec![&env, id]);
+    assert_eq!(approvals.get_mint_request(&id).unwrap().amount, 5000);
+    assert_eq!(token.balance(&farmer), 0);
+
+    let result = approvals.try_approve_mint(&verifier, &id);
+    assert_eq!(result, Err(Ok(Error::CoApproverNotDistinct)));
+
+    approvals.approve_mint(&co_verifier, &id);
+    assert_eq!(token.balance(&farmer), 5000);
+    assert_eq!(issuance.get_issuance(&hash).unwrap().verifier, verifier);
+    assert_eq!(approvals.pending_mint_requests().len(), 0);
+    assert_eq!(approvals.get_mint_request(&id), None);
+
+    let result = approvals.try_approve_mint(&co_verifier, &id);
+    assert_eq!(result, Err(Ok(Error::MintRequestNotFound)));
+
+    // The report hash is spent, so it cannot back another request.
+    let result = approvals.try_propose_mint(&verifier, &farmer, &1, &5000, &hash, &1000);
+    assert_eq!(result, Err(Ok(Error::ReportHashUsed)));
+}
+
+#[test]
+fn test_approve_mint_passes_on_issuance_errors() {
+    let (env, token, issuance, approvals, _, verifier, farmer) = setup_env();
+    let co_verifier = Address::generate(&env);
+    let hash = Bytes::from_slice(&env, b"r1");
+
+    // No report on file: the request is queued, but the mint fails on approval.
+    let id = approvals.propose_mint(&verifier, &farmer, &1, &5000, &hash, &1000);
+    let result = approvals.try_approve_mint(&co_verifier, &id);
+    assert_eq!(result, Err(Ok(Error::ReportNotFound)));
+    assert_eq!(approvals.pending_mint_requests(), vec![&env, id]);
+
+    submit_report(&issuance, &verifier, &farmer, &hash);
+    issuance.set_global_cap(&Some(4999));
+    let result = approvals.try_approve_mint(&co_verifier, &id);
+    assert_eq!(result, Err(Ok(Error::GlobalCapExceeded)));
+
+    issuance.set_global_cap(&None);
+    approvals.approve_mint(&co_verifier, &id);
+    assert_eq!(token.balance(&farmer), 5000);
+}
+
+#[test]
+fn test_mint_request_expiry_and_cancellation() {
+    let (env, token, issuance, approvals, admin, verifier, farmer) = setup_env();
+    let co_verifier = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let expiry = env.ledger().sequence() + 10;
+
+    let hash1 = Bytes::from_slice(&env, b"r1");
+    let hash2 = Bytes::from_slice(&env, b"r2");
+
+    submit_report(&issuance, &verifier, &farmer, &hash1);
+    let first = approvals.propose_mint(&verifier, &farmer, &1, &5000, &hash1, &expiry);
+    submit_report(&issuance, &verifier, &farmer, &hash2);
+    let second = approvals.propose_mint(&verifier, &farmer, &1, &700, &hash2, &expiry);
+    assert_eq!(approvals.pending_mint_requests(), vec![&env, first, second]);
+
+    let result = approvals.try_cancel_mint_request(&stranger, &first);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    approvals.cancel_mint_request(&verifier, &first);
+    assert_eq!(approvals.pending_mint_requests(), vec![&env, second]);
+
+    env.ledger().with_mut(|li| li.sequence_number += 11);
+    let result = approvals.try_approve_mint(&co_verifier, &second);
+    assert_eq!(result, Err(Ok(Error::MintRequestExpired)));
+
+    // The expired request drops off the pending list on the next write.
+    let hash3 = Bytes::from_slice(&env, b"r3");
+    submit_report(&issuance, &verifier, &farmer, &hash3);
+    let third = approvals.propose_mint(&verifier, &farmer, &1, &900, &hash3, &(expiry + 100));
+    assert_eq!(approvals.pending_mint_requests(), vec![&env, third]);
+
+    approvals.cancel_mint_request(&admin, &second);
+    approvals.cancel_mint_request(&admin, &third);
+    assert_eq!(approvals.pending_mint_requests().len(), 0);
+    assert_eq!(token.total_supply(), 0);
+
+    let result = approvals.try_propose_mint(&verifier, &farmer, &1, &900, &hash3, &1);
+    assert_eq!(result, Err(Ok(Error::InvalidExpirationLedger)));
+}
+
+#[test]
+fn test_approve_mint_rechecks_proposer_role() {
+    let (env, token, issuance, approvals, _, verifier, farmer) = setup_env();
+    let co_verifier = Address::generate(&env);
+    let rbac = MockRbacContractClient::new(&env, &token.rbac_contract());
+
+    let hash = Bytes::from_slice(&env, b"r1");
+
+    submit_report(&issuance, &verifier, &farmer, &hash);
+    let id = approvals.propose_mint(&verifier, &farmer, &1, &5000, &hash, &1000);
+    rbac.deny_role(&verifier, &String::from_str(&env, "Verifier"));
+
+    let result = approvals.try_approve_mint(&co_verifier, &id);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    assert_eq!(token.balance(&farmer), 0);
+    assert_eq!(approvals.pending_mint_requests(), vec![&env, id]);
+}
+
+#[test]
+fn test_issue_approved_accepts_mint_approvals_only() {
+    let (env, token, issuance, approvals, _, verifier, farmer) = setup_env();
+    let hash = Bytes::from_slice(&env, b"r1");
+
+    submit_report(&issuance, &verifier, &farmer, &hash);
+    issuance.issue_approved(&verifier, &farmer, &1, &5000, &hash);
+    assert_eq!(env.auths()[0].0, approvals.address);
+    assert_eq!(token.balance(&farmer), 5000);
+}
@@ -7,13 +7,14 @@ mod storage;
 mod test;
 
 pub use error::Error;
-use soroban_sdk::{contract, contractimpl, Address, Env, String, Symbol, Vec};
+pub use storage::{ContractCall, ProposalAction};
+use soroban_sdk::{contract, contractimpl, Address, Env, String, Symbol, Val, Vec};
 
-use crate::storage::{RoleType, Proposal, ProposalAction};
+use crate::storage::{RoleType, Proposal};
 
 fn execute_proposal_logic(env: &Env, action: &ProposalAction) -> Result<(), Error> {
     match action {
-        ProposalAction::ChangeSuperAdmins { admins, threshold } => {
+        ProposalAction::ChangeSuperAdmins(admins, threshold) => {
             if admins.is_empty() {
                 return Err(Error::EmptyAdmins);
             }
@@ -109,6 +110,9 @@ fn execute_proposal_logic(env: &Env, action: &ProposalAction) -> Result<(), Erro
                 }
             }
         }
+        ProposalAction::InvokeContract(call) => {
+            env.invoke_contract::<Val>(&call.contract, &call.function, call.args.clone());
+        }
     }
     Ok(())
 }
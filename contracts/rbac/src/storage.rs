@@ -1,4 +1,4 @@
-use soroban_sdk::{contracttype, Address, Env, Vec, Symbol};
+use soroban_sdk::{contracttype, Address, Env, Vec, Symbol, Val};
 
 // ── TTL Constants (standardized across all contracts) ────────────────────────
 pub const INSTANCE_LIFETIME_THRESHOLD: u32 = 17280; // ~1 day at 5s/ledger
@@ -18,12 +18,24 @@ pub enum RoleType {
 #[derive(Clone, Debug, PartialEq)]
 #[contracttype]
 pub enum ProposalAction {
-    ChangeSuperAdmins { admins: Vec<Address>, threshold: u32 },
+    ChangeSuperAdmins(Vec<Address>, u32),
     SetTimelockDelay(u64),
     GrantAdmin(Address),
     RevokeAdmin(Address),
     AssignRolesBatch(Vec<(Address, Symbol)>),
     RevokeRolesBatch(Vec<(Address, Symbol)>),
+    /// Calls another contract with this contract as the invoker, so targets
+    /// that delegate administration here (e.g. the carbon credit token in
+    /// governance mode) accept the call.
+    InvokeContract(ContractCall),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct ContractCall {
+    pub contract: Address,
+    pub function: Symbol,
+    pub args: Vec<Val>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
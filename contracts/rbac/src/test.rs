@@ -1,9 +1,9 @@
 #![cfg(test)]
 
-use soroban_sdk::{testutils::{Address as _, Ledger}, Address, Env, String, Symbol, vec};
+use soroban_sdk::{contract, contractimpl, testutils::{Address as _, Ledger}, Address, Env, IntoVal, String, Symbol, vec};
 
 use crate::{error::Error, RbacContract, RbacContractClient};
-use crate::storage::{ProposalAction, RoleType};
+use crate::storage::{ContractCall, ProposalAction, RoleType};
 
 fn setup_single(env: &Env) -> (RbacContractClient<'static>, Address) {
     let contract_id = env.register_contract(None, RbacContract);
@@ -284,10 +284,7 @@ fn test_change_super_admins_via_proposal() {
     let new_admin2 = Address::generate(&env);
     let new_admins = vec![&env, new_admin1.clone(), new_admin2.clone()];
 
-    let action = ProposalAction::ChangeSuperAdmins {
-        admins: new_admins,
-        threshold: 2,
-    };
+    let action = ProposalAction::ChangeSuperAdmins(new_admins, 2);
 
     let proposal_id = client.propose_action(&admin1, &action);
     client.approve_proposal(&admin2, &proposal_id);
@@ -299,4 +296,43 @@ fn test_change_super_admins_via_proposal() {
 
     // Verify old super admins are revoked
     assert!(!client.has_role(&admin1, &String::from_str(&env, "SuperAdmin")));
-}
\ No newline at end of file
+}
+
+#[contract]
+pub struct MockGovernedContract;
+
+#[contractimpl]
+impl MockGovernedContract {
+    pub fn set_value(env: Env, governor: Address, value: u32) {
+        governor.require_auth();
+        env.storage().instance().set(&governor, &value);
+    }
+
+    pub fn value(env: Env, governor: Address) -> Option<u32> {
+        env.storage().instance().get(&governor)
+    }
+}
+
+#[test]
+fn test_invoke_contract_via_proposal() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admins) = setup_multi(&env, 2, 0);
+    let admin1 = admins.get(0).unwrap();
+    let admin2 = admins.get(1).unwrap();
+    let target_id = env.register_contract(None, MockGovernedContract);
+    let target = MockGovernedContractClient::new(&env, &target_id);
+
+    let action = ProposalAction::InvokeContract(ContractCall {
+        contract: target_id.clone(),
+        function: Symbol::new(&env, "set_value"),
+        args: vec![&env, client.address.into_val(&env), 7u32.into_val(&env)],
+    });
+    let proposal_id = client.propose_action(&admin1, &action);
+    assert_eq!(target.value(&client.address), None);
+
+    client.approve_proposal(&admin2, &proposal_id);
+    client.execute_proposal(&proposal_id);
+    assert_eq!(target.value(&client.address), Some(7));
+}
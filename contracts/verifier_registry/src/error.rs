@@ -18,4 +18,6 @@ pub enum Error {
     InvalidHash = 6,
     /// No report found for the given farmer.
     ReportNotFound = 7,
+    /// The verifier is already active.
+    VerifierAlreadyActive = 8,
 }
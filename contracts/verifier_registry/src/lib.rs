@@ -7,9 +7,10 @@ use error::Error;
 use soroban_sdk::{contract, contractimpl, Address, Env, String};
 
 use storage::{
-    is_initialized, is_verifier_registered, read_report, read_super_admin, read_verifier_profile,
-    register_verifier, set_initialized, unregister_verifier, write_report, write_super_admin,
-    ReportData, VerifierProfile, INSTANCE_BUMP_AMOUNT, INSTANCE_LIFETIME_THRESHOLD,
+    is_initialized, is_verifier_registered, read_report, read_report_by_hash, read_super_admin,
+    read_verifier_profile, register_verifier, set_initialized, unregister_verifier, write_report,
+    write_report_by_hash, write_super_admin, ReportData, VerifierProfile, INSTANCE_BUMP_AMOUNT,
+    INSTANCE_LIFETIME_THRESHOLD,
 };
 
 #[contract]
@@ -163,6 +164,11 @@ impl VerifierRegistry {
             env.ledger().sequence(),
         );
         write_report(&env, &farmer, &report);
+        write_report_by_hash(
+            &env,
+            &metric_hash,
+            &(verifier.clone(), farmer.clone(), env.ledger().sequence()),
+        );
 
         env.events().publish(
             (soroban_sdk::symbol_short!("rpt_sub"),),
@@ -194,8 +200,9 @@ impl VerifierRegistry {
         }
 
         // Create and store the report record
-        let report: ReportData = (verifier, metric_hash, env.ledger().sequence());
+        let report: ReportData = (verifier.clone(), metric_hash.clone(), env.ledger().sequence());
         write_report(&env, &farmer, &report);
+        write_report_by_hash(&env, &metric_hash, &(verifier, farmer, env.ledger().sequence()));
 
         Ok(())
     }
@@ -225,6 +232,11 @@ impl VerifierRegistry {
         read_report(&env, &farmer)
     }
 
+    /// Returns the report submitted with a hash: (verifier, farmer, submission_ledger)
+    pub fn get_report(env: Env, metric_hash: String) -> Option<(Address, Address, u32)> {
+        read_report_by_hash(&env, &metric_hash)
+    }
+
     /// Returns the SuperAdmin address.
     pub fn get_super_admin(env: Env) -> Address {
         read_super_admin(&env)
@@ -3,6 +3,8 @@ use soroban_sdk::{contracttype, Address, Env, String};
 // TTL Constants (standardized across all contracts)
 pub const INSTANCE_LIFETIME_THRESHOLD: u32 = 17280; // ~1 day at 5s/ledger
 pub const INSTANCE_BUMP_AMOUNT: u32 = 518400; // ~30 days at 5s/ledger
+pub const REPORT_LIFETIME_THRESHOLD: u32 = 17280; // ~1 day at 5s/ledger
+pub const REPORT_BUMP_AMOUNT: u32 = 518400; // ~30 days at 5s/ledger
 
 /// Verifier profile containing public decentralized profile information
 #[derive(Clone)]
@@ -28,6 +30,8 @@ pub enum DataKey {
     Verifiers(Address),
     /// Report hash by farmer address (latest report) - stored as (verifier, hash, ledger)
     ReportByFarmer(Address),
+    /// Report by its hash - stored as (verifier, farmer, ledger)
+    ReportByHash(String),
 }
 
 /// Check if the contract has been initialized
@@ -103,3 +107,22 @@ pub fn read_report(e: &Env, farmer: &Address) -> Option<ReportData> {
         .instance()
         .get::<DataKey, ReportData>(&DataKey::ReportByFarmer(farmer.clone()))
 }
+
+/// Report record keyed by hash: (verifier_address, farmer_address, submission_ledger)
+pub type HashReportData = (Address, Address, u32);
+
+/// Write the report record for a hash
+pub fn write_report_by_hash(e: &Env, metric_hash: &String, report: &HashReportData) {
+    let key = DataKey::ReportByHash(metric_hash.clone());
+    e.storage().persistent().set(&key, report);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, REPORT_LIFETIME_THRESHOLD, REPORT_BUMP_AMOUNT);
+}
+
+/// Read the report record for a hash
+pub fn read_report_by_hash(e: &Env, metric_hash: &String) -> Option<HashReportData> {
+    e.storage()
+        .persistent()
+        .get::<DataKey, HashReportData>(&DataKey::ReportByHash(metric_hash.clone()))
+}
@@ -430,3 +430,34 @@ fn test_multiple_reports_same_farmer() {
     let (_, report_hash, _) = report.unwrap();
     assert_eq!(report_hash, hash2);
 }
+
+#[test]
+fn test_get_report_by_hash() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let super_admin = Address::generate(&env);
+    let registry = create_registry(&env, &super_admin);
+
+    let verifier = Address::generate(&env);
+    let name = String::from_str(&env, "Test Verifier");
+    let jurisdiction = String::from_str(&env, "USA");
+
+    registry.register_verifier(&verifier, &name, &jurisdiction);
+
+    let farmer = Address::generate(&env);
+
+    let hash1 = String::from_str(&env, "0x11111111111111111111111111111111");
+    registry.submit_report_hash_with_verifier(&verifier, &farmer, &hash1);
+    let hash2 = String::from_str(&env, "0x22222222222222222222222222222222");
+    registry.submit_report_hash(&verifier, &farmer, &hash2);
+
+    // Earlier reports stay retrievable after a newer one is submitted
+    let (report_verifier, report_farmer, _) = registry.get_report(&hash1).unwrap();
+    assert_eq!(report_verifier, verifier);
+    assert_eq!(report_farmer, farmer);
+    assert!(registry.get_report(&hash2).is_some());
+
+    let unknown = String::from_str(&env, "0x33333333333333333333333333333333");
+    assert!(registry.get_report(&unknown).is_none());
+}
@@ -12,11 +12,21 @@ ROOT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")/.." && pwd)"
 cd "$ROOT_DIR"
 
 echo "Building WASM artifacts..."
-cargo build --target wasm32-unknown-unknown --release -p rbac -p carbon_credit_token -p escrow
+cargo build --target wasm32-unknown-unknown --release -p rbac -p verifier-registry -p carbon_credit_token \
+  -p credit_issuance -p credit_retirement -p credit_compliance -p credit_permits \
+  -p credit_reversals -p ex_ante_credits -p mint_approvals -p escrow
 
 declare -A WASMS=(
   [rbac]="target/wasm32-unknown-unknown/release/rbac.wasm"
+  [verifier_registry]="target/wasm32-unknown-unknown/release/verifier_registry.wasm"
   [carbon_credit_token]="target/wasm32-unknown-unknown/release/carbon_credit_token.wasm"
+  [credit_issuance]="target/wasm32-unknown-unknown/release/credit_issuance.wasm"
+  [credit_retirement]="target/wasm32-unknown-unknown/release/credit_retirement.wasm"
+  [credit_compliance]="target/wasm32-unknown-unknown/release/credit_compliance.wasm"
+  [credit_permits]="target/wasm32-unknown-unknown/release/credit_permits.wasm"
+  [credit_reversals]="target/wasm32-unknown-unknown/release/credit_reversals.wasm"
+  [ex_ante_credits]="target/wasm32-unknown-unknown/release/ex_ante_credits.wasm"
+  [mint_approvals]="target/wasm32-unknown-unknown/release/mint_approvals.wasm"
   [escrow]="target/wasm32-unknown-unknown/release/escrow.wasm"
 )
 
@@ -39,7 +49,12 @@ function deploy_contract() {
 }
 
 deploy_contract "rbac" "${WASMS[rbac]}"
+deploy_contract "verifier_registry" "${WASMS[verifier_registry]}"
 deploy_contract "carbon_credit_token" "${WASMS[carbon_credit_token]}"
+for module in credit_issuance credit_retirement credit_compliance credit_permits \
+  credit_reversals ex_ante_credits mint_approvals; do
+  deploy_contract "$module" "${WASMS[$module]}"
+done
 deploy_contract "escrow" "${WASMS[escrow]}"
 
 cat <<'EOF'
@@ -48,8 +63,11 @@ Deployment complete.
 Next steps:
 1) Save the contract IDs returned by each deploy.
 2) Initialize RBAC first.
-3) Initialize Carbon Credit Token with the RBAC address.
-4) Initialize Escrow.
+3) Initialize the Verifier Registry.
+4) Initialize Carbon Credit Token with the RBAC and module addresses.
+5) Initialize each module with the Carbon Credit Token address; Credit Issuance
+   also takes the Verifier Registry, Ex-Ante Credits and Mint Approvals addresses.
+6) Initialize Escrow.
 
 See DEPLOYMENT.md for example initialization commands.
 EOF